@@ -0,0 +1,68 @@
+//! 生成错误码目录：`cargo run --example error_catalog -- [--html|--json]`
+//! 默认输出 Markdown 表格；领域项目可照此登记自己的原因类型
+
+use derive_more::From;
+use orion_error::{
+    AsUvsReason, ErrStrategy, ErrorCatalog, ErrorCode, HttpStatus, StrategyFor, UvsReason,
+};
+use thiserror::Error;
+
+#[derive(Debug, Error, From)]
+enum OrderReason {
+    #[error("order not found")]
+    NotFound,
+    #[error("insufficient stock")]
+    OutOfStock,
+    #[error("{0}")]
+    Uvs(UvsReason),
+}
+
+impl ErrorCode for OrderReason {
+    fn error_code(&self) -> i32 {
+        match self {
+            Self::NotFound => 1001,
+            Self::OutOfStock => 1002,
+            Self::Uvs(u) => u.error_code(),
+        }
+    }
+}
+
+impl HttpStatus for OrderReason {
+    fn http_status(&self) -> u16 {
+        match self {
+            Self::NotFound => 404,
+            Self::OutOfStock => 409,
+            Self::Uvs(u) => u.http_status(),
+        }
+    }
+}
+
+impl AsUvsReason for OrderReason {
+    fn as_uvs(&self) -> Option<&UvsReason> {
+        match self {
+            Self::Uvs(u) => Some(u),
+            _ => None,
+        }
+    }
+}
+
+impl StrategyFor for OrderReason {
+    fn strategy(&self) -> ErrStrategy {
+        match self {
+            Self::OutOfStock => ErrStrategy::Retry,
+            Self::NotFound => ErrStrategy::Throw,
+            Self::Uvs(u) => u.strategy(),
+        }
+    }
+}
+
+fn main() {
+    let mut catalog = ErrorCatalog::uvs();
+    catalog.register([OrderReason::NotFound, OrderReason::OutOfStock]);
+
+    match std::env::args().nth(1).as_deref() {
+        Some("--html") => print!("{}", catalog.to_html()),
+        Some("--json") => println!("{}", catalog.to_json()),
+        _ => print!("{}", catalog.to_markdown()),
+    }
+}
@@ -4,8 +4,8 @@
 
 use derive_more::From;
 use orion_error::{
-    print_error, ContextRecord, ErrorCode, ErrorConv, ErrorOwe, ErrorWith, OperationContext,
-    StructError, UvsReason,
+    define_domain_error, print_error, ContextRecord, ErrorCode, ErrorConv, ErrorOwe, ErrorOweIo,
+    ErrorWith, OperationContext, StructError, UvsReason,
 };
 use std::{
     fmt::{Display, Formatter},
@@ -31,21 +31,15 @@ impl ErrorCode for OrderReason {
     }
 }
 
-#[derive(Debug, PartialEq, Clone, Error, From)]
+#[derive(Debug, PartialEq, Clone, Error)]
 pub enum StoreReason {
     #[error("storage full")]
     StorageFull,
     #[error("{0}")]
     Uvs(UvsReason),
 }
-impl ErrorCode for StoreReason {
-    fn error_code(&self) -> i32 {
-        match self {
-            Self::Uvs(uvs_reason) => uvs_reason.error_code(),
-            _ => 500,
-        }
-    }
-}
+// 生成 StoreError / StoreResult 别名、From<UvsReason> 与 ErrorCode
+define_domain_error!(StoreReason => StoreError, StoreResult);
 
 #[derive(Debug, Error, PartialEq, Clone, From)]
 pub enum ParseReason {
@@ -98,7 +92,6 @@ impl From<StoreReason> for OrderReason {
     }
 }
 pub type OrderError = StructError<OrderReason>;
-pub type StoreError = StructError<StoreReason>;
 pub type ParseError = StructError<ParseReason>;
 pub type UserError = StructError<UserReason>;
 
@@ -131,8 +124,8 @@ pub mod storage {
 
     pub static STORAGE_CAPACITY: AtomicUsize = AtomicUsize::new(2);
     static ORDERS: Mutex<Vec<Order>> = Mutex::new(Vec::new());
-    pub fn save(order: Order) -> Result<(), StoreError> {
-        save_db_impl(order).owe_sys()
+    pub fn save(order: Order) -> StoreResult<()> {
+        save_db_impl(order).owe_io()
     }
 
     fn save_db_impl(order: Order) -> Result<(), std::io::Error> {
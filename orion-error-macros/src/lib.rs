@@ -0,0 +1,93 @@
+//! orion-error 的过程宏，通过主库的 `macros` feature 使用
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Ident, ItemFn, LitStr, ReturnType};
+
+/// 为函数自动创建 `OperationContext`、记录指定参数，并附加到返回的错误上
+///
+/// ```rust,ignore
+/// #[err_context(want = "place_order", args(user_id, amount))]
+/// fn place_order(user_id: u64, amount: f64) -> Result<Order, StructError<OrderReason>> {
+///     // ...
+/// }
+/// ```
+///
+/// - `want`：上下文目标，缺省为函数名
+/// - `args(..)`：需要记录的参数，参数类型需实现 `Display`，在函数体执行前记录
+///
+/// 返回类型需为 `Result<T, E>` 且 `E: ErrorWith`；支持 `async fn`。
+#[proc_macro_attribute]
+pub fn err_context(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut want: Option<LitStr> = None;
+    let mut args: Vec<Ident> = Vec::new();
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("want") {
+            want = Some(meta.value()?.parse()?);
+            Ok(())
+        } else if meta.path.is_ident("args") {
+            meta.parse_nested_meta(|arg| {
+                args.push(arg.path.require_ident()?.clone());
+                Ok(())
+            })
+        } else {
+            Err(meta.error("expected `want = \"...\"` or `args(...)`"))
+        }
+    });
+    parse_macro_input!(attr with parser);
+
+    let func = parse_macro_input!(item as ItemFn);
+    match expand(func, want, args) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand(func: ItemFn, want: Option<LitStr>, args: Vec<Ident>) -> syn::Result<TokenStream2> {
+    let ItemFn {
+        attrs,
+        vis,
+        sig,
+        block,
+    } = func;
+    let ret = match &sig.output {
+        ReturnType::Type(_, ty) => ty,
+        ReturnType::Default => {
+            return Err(syn::Error::new_spanned(
+                &sig,
+                "#[err_context] requires a function returning Result",
+            ))
+        }
+    };
+    let want = match want {
+        Some(want) => want,
+        None => LitStr::new(&sig.ident.to_string(), sig.ident.span()),
+    };
+    let records = args.iter().map(|arg| {
+        let key = arg.to_string();
+        quote! {
+            ::orion_error::ContextRecord::record(
+                &mut __orion_ctx,
+                #key,
+                ::std::string::ToString::to_string(&#arg),
+            );
+        }
+    });
+    let call = if sig.asyncness.is_some() {
+        quote! { async move #block.await }
+    } else {
+        quote! { (move || -> #ret #block)() }
+    };
+    Ok(quote! {
+        #(#attrs)*
+        #vis #sig {
+            #[allow(unused_mut)]
+            let mut __orion_ctx = ::orion_error::OperationContext::want(#want)
+                .with_mod_path(::std::module_path!());
+            #(#records)*
+            let __orion_result: #ret = #call;
+            ::orion_error::ErrorWith::with(__orion_result, __orion_ctx)
+        }
+    })
+}
@@ -0,0 +1,175 @@
+//! 批处理错误：逐项处理时按下标记录错误，支持遇错即停或收集全部
+
+use std::fmt::{self, Display};
+
+use super::{domain::DomainReason, ContextAdd, ErrorCode, OperationContext, StructError};
+use crate::ContextRecord;
+
+/// 批处理中收集到的多个错误，每个错误都带有 `index` 上下文
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorBundle<R: DomainReason> {
+    errors: Vec<StructError<R>>,
+}
+
+impl<R: DomainReason> Default for ErrorBundle<R> {
+    fn default() -> Self {
+        Self { errors: Vec::new() }
+    }
+}
+
+impl<R: DomainReason> ErrorBundle<R> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, err: StructError<R>) {
+        self.errors.push(err);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    pub fn errors(&self) -> &[StructError<R>] {
+        &self.errors
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, StructError<R>> {
+        self.errors.iter()
+    }
+
+    pub fn into_errors(self) -> Vec<StructError<R>> {
+        self.errors
+    }
+
+    /// 没有错误时返回 `Ok(value)`
+    pub fn into_result<T>(self, value: T) -> Result<T, Self> {
+        if self.is_empty() {
+            Ok(value)
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl<R: DomainReason> IntoIterator for ErrorBundle<R> {
+    type Item = StructError<R>;
+    type IntoIter = std::vec::IntoIter<StructError<R>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.errors.into_iter()
+    }
+}
+
+impl<'a, R: DomainReason> IntoIterator for &'a ErrorBundle<R> {
+    type Item = &'a StructError<R>;
+    type IntoIter = std::slice::Iter<'a, StructError<R>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.errors.iter()
+    }
+}
+
+impl<R: DomainReason> FromIterator<StructError<R>> for ErrorBundle<R> {
+    fn from_iter<I: IntoIterator<Item = StructError<R>>>(iter: I) -> Self {
+        Self {
+            errors: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// 首行为错误数量，随后每个错误一行（紧凑格式）
+impl<R: DomainReason + ErrorCode + Display> Display for ErrorBundle<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} errors", self.errors.len())?;
+        for err in &self.errors {
+            write!(f, "\n  - {err:#}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<R: DomainReason> std::error::Error for ErrorBundle<R> where Self: fmt::Debug + Display {}
+
+/// 逐项结果的收集方式，适用于 `Iterator<Item = Result<T, StructError<R>>>`
+pub trait ErrorCollect<T, R>: Iterator<Item = Result<T, StructError<R>>> + Sized
+where
+    R: DomainReason,
+{
+    /// 遇到第一个错误即停止，错误附加该项的 `index`
+    fn collect_fail_fast(self) -> Result<Vec<T>, StructError<R>> {
+        let mut out = Vec::new();
+        for (i, item) in self.enumerate() {
+            out.push(item.map_err(|e| with_index(e, i))?);
+        }
+        Ok(out)
+    }
+
+    /// 处理全部元素，分别收集成功值与（附加 `index` 的）错误
+    fn collect_all(self) -> (Vec<T>, ErrorBundle<R>) {
+        let mut out = Vec::new();
+        let mut bundle = ErrorBundle::new();
+        for (i, item) in self.enumerate() {
+            match item {
+                Ok(v) => out.push(v),
+                Err(e) => bundle.push(with_index(e, i)),
+            }
+        }
+        (out, bundle)
+    }
+}
+
+impl<I, T, R> ErrorCollect<T, R> for I
+where
+    I: Iterator<Item = Result<T, StructError<R>>>,
+    R: DomainReason,
+{
+}
+
+/// 直接压入 `index` 帧，不经 `ErrorWith::with`，以免把本文件记为错误位置
+fn with_index<R: DomainReason>(mut err: StructError<R>, index: usize) -> StructError<R> {
+    let mut ctx = OperationContext::new();
+    ctx.record("index", index);
+    err.add_context(ctx);
+    err
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ErrorOwe, UvsReason};
+
+    fn parse_all<'a>(
+        input: &'a [&'a str],
+    ) -> impl Iterator<Item = Result<i32, StructError<UvsReason>>> + 'a {
+        input.iter().map(|s| s.parse::<i32>().owe_data())
+    }
+
+    #[test]
+    fn test_collect_fail_fast_records_index() {
+        let ok = parse_all(&["1", "2"]).collect_fail_fast().unwrap();
+        assert_eq!(ok, vec![1, 2]);
+
+        let err = parse_all(&["1", "x", "y"]).collect_fail_fast().unwrap_err();
+        assert_eq!(err.error_code(), 200);
+        assert_eq!(err.contexts().last().unwrap().get("index"), Some("1"));
+    }
+
+    #[test]
+    fn test_collect_all_bundles_errors() {
+        let (values, errors) = parse_all(&["1", "x", "3", "y"]).collect_all();
+        assert_eq!(values, vec![1, 3]);
+        assert_eq!(errors.len(), 2);
+        let indexes: Vec<_> = errors
+            .iter()
+            .map(|e| e.contexts()[0].get("index").unwrap().to_string())
+            .collect();
+        assert_eq!(indexes, ["1", "3"]);
+        assert!(errors.to_string().starts_with("2 errors\n  - [200]"));
+        assert!(errors.into_result(()).is_err());
+    }
+}
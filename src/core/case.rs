@@ -5,8 +5,8 @@ mod tests {
     use thiserror::Error;
 
     use crate::{
-        core::convert_error, ContextRecord, ErrorCode, ErrorWith, OperationContext, StructError,
-        TestAssertWithMsg, UvsReason,
+        core::convert_error, format_error, write_error, ContextRecord, ErrorCode, ErrorWith,
+        OperationContext, StructError, TestAssertWithMsg, UvsReason,
     };
 
     // 测试用领域原因类型
@@ -27,6 +27,15 @@ mod tests {
         }
     }
 
+    impl crate::AsUvsReason for TestDomainReason {
+        fn as_uvs(&self) -> Option<&UvsReason> {
+            match self {
+                TestDomainReason::Uvs(r) => Some(r),
+                _ => None,
+            }
+        }
+    }
+
     // 另一个领域原因类型用于转换测试
     #[derive(Debug, PartialEq, Clone, Error, From)]
     enum OtherDomainReason {
@@ -123,4 +132,284 @@ mod tests {
         // 使用自定义断言trait
         result.assert("This should panic with domain error");
     }
+
+    #[test]
+    fn test_format_error_matches_writer_output() {
+        let err = StructError::from(TestDomainReason::Why1)
+            .with_detail("disk offline")
+            .want("load_order");
+
+        let rendered = format_error(&err);
+        assert!(rendered.starts_with("[error code200]"));
+        assert!(rendered.contains("-> Details: disk offline"));
+        assert!(rendered.ends_with(&format!("{}\n", "-".repeat(50))));
+
+        let mut buf: Vec<u8> = Vec::new();
+        write_error(&err, &mut buf).assert("write to vec");
+        assert_eq!(String::from_utf8(buf).unwrap(), rendered);
+    }
+
+    #[test]
+    fn test_detail_redaction() {
+        crate::register_sensitive_key("ut_case_secret");
+        let err = StructError::from(TestDomainReason::Why1)
+            .with_detail("connect failed: ut_case_secret=abc host=db");
+        assert_eq!(
+            err.detail(),
+            &Some("connect failed: ut_case_secret=****** host=db".to_string())
+        );
+        assert!(!format!("{err}").contains("abc"));
+    }
+
+    #[test]
+    fn test_builder_with_source() {
+        use std::error::Error as _;
+
+        let io = std::io::Error::new(std::io::ErrorKind::NotFound, "no such file");
+        let err = StructError::builder(TestDomainReason::Why1)
+            .detail("load config")
+            .position("src/config.rs:10")
+            .context(OperationContext::want("read_config"))
+            .source(io)
+            .build();
+        assert_eq!(err.detail().as_deref(), Some("load config"));
+        assert_eq!(err.imp().position().as_deref(), Some("src/config.rs:10"));
+        assert_eq!(err.target().as_deref(), Some("read_config"));
+        assert_eq!(err.source().unwrap().to_string(), "no such file");
+
+        // 转换后保留错误源
+        let other: StructError<OtherDomainReason> = convert_error(err.clone());
+        assert!(other.error_source().is_some());
+        assert_eq!(err.clone(), err);
+    }
+
+    #[test]
+    fn test_merge_context_folds_frames() {
+        let mut a = OperationContext::want("load");
+        a.record("file", "a.toml");
+        let mut b = OperationContext::want("load");
+        b.record("line", "3");
+        let c = OperationContext::want("start");
+        let err = StructError::from(TestDomainReason::Why1)
+            .with(a)
+            .with(c.clone())
+            .with(b)
+            .with(c)
+            .merge_context();
+        let frames = err.contexts();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].get("file"), Some("a.toml"));
+        assert_eq!(frames[0].get("line"), Some("3"));
+        assert_eq!(frames[1].target().as_deref(), Some("start"));
+    }
+
+    #[test]
+    fn test_compact_contexts_merges_adjacent_frames() {
+        let mut err =
+            StructError::from(TestDomainReason::Why1).with(OperationContext::want("start"));
+        for i in 0..3 {
+            let mut ctx = OperationContext::want("retry fetch");
+            ctx.record(format!("attempt{i}"), "failed");
+            err = err.with(ctx);
+        }
+        let err = err.with(OperationContext::want("start")).compact_contexts();
+        let frames = err.contexts();
+        // 不相邻的同名帧保持原有层次
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[1].target().as_deref(), Some("retry fetch"));
+        assert_eq!(frames[1].get("attempt0"), Some("failed"));
+        assert_eq!(frames[1].get("attempt2"), Some("failed"));
+        assert_eq!(frames[2].target().as_deref(), Some("start"));
+    }
+
+    #[test]
+    fn test_push_want_keeps_layers() {
+        // 默认 want 覆盖最近一帧的目标，push_want 总是追加
+        let replaced = StructError::from(TestDomainReason::Why1)
+            .want("read order")
+            .want("place_order");
+        assert_eq!(replaced.contexts().len(), 1);
+        assert_eq!(replaced.target().as_deref(), Some("place_order"));
+
+        let layered = StructError::from(TestDomainReason::Why1)
+            .push_want("read order")
+            .push_want("validate order")
+            .push_want("place_order");
+        let targets: Vec<_> = layered
+            .contexts()
+            .iter()
+            .map(|c| c.target().clone())
+            .collect();
+        assert_eq!(
+            targets,
+            [
+                Some("read order".to_string()),
+                Some("validate order".to_string()),
+                Some("place_order".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mut_accessors_and_into_parts() {
+        let mut err = StructError::from(TestDomainReason::Why1)
+            .with_detail("quota exceeded")
+            .with_position("src/a.rs:1")
+            .want("charge");
+        if let Some(detail) = err.detail_mut() {
+            detail.push_str(" (tenant=t-9)");
+        }
+        *err.reason_mut() = UvsReason::business_error().into();
+        let shared = err.clone();
+
+        let (reason, detail, position, contexts) = err.into_parts();
+        assert_eq!(reason, TestDomainReason::Uvs(UvsReason::business_error()));
+        assert_eq!(detail.as_deref(), Some("quota exceeded (tenant=t-9)"));
+        assert_eq!(position.as_deref(), Some("src/a.rs:1"));
+        assert_eq!(contexts[0].target().as_deref(), Some("charge"));
+        // 共享存储的克隆不受影响
+        assert_eq!(shared.contexts().len(), 1);
+    }
+
+    #[test]
+    fn test_as_uvs_and_is_category() {
+        use crate::AsUvsReason;
+
+        let err = StructError::from(TestDomainReason::from(UvsReason::network_error()));
+        assert_eq!(err.as_uvs(), Some(&UvsReason::NetworkError));
+        assert!(err.is_category("network"));
+        assert!(!err.is_category("timeout"));
+        assert!(!StructError::from(TestDomainReason::Why1).is_category("network"));
+    }
+
+    #[test]
+    fn test_eq_reason_ignores_context_and_position() {
+        use crate::ReasonEq;
+
+        let a = StructError::from(TestDomainReason::Why1)
+            .with_detail("d")
+            .with_position("a.rs:1")
+            .want("load");
+        let b = StructError::from(TestDomainReason::Why1).with_detail("d");
+        assert_ne!(a, b);
+        assert!(a.eq_reason(&b));
+        assert_eq!(ReasonEq(a.clone()), ReasonEq(b));
+        assert!(!a.eq_reason(&StructError::from(TestDomainReason::Why1)));
+    }
+
+    #[test]
+    fn test_clone_is_copy_on_write() {
+        let mut ctx = OperationContext::want("load");
+        ctx.record("file", "a.toml");
+        let base = StructError::from(TestDomainReason::Why1)
+            .with_detail("d")
+            .with(ctx);
+        let copy = base.clone();
+        assert!(std::ptr::eq(base.contexts(), copy.contexts()));
+
+        let changed = copy.with_detail("other").want("reload");
+        assert_eq!(base.detail().as_deref(), Some("d"));
+        assert_eq!(base.target().as_deref(), Some("load"));
+        assert_eq!(changed.detail().as_deref(), Some("other"));
+        assert_eq!(changed.target().as_deref(), Some("reload"));
+    }
+
+    #[test]
+    fn test_contextless_errors_share_empty_stack() {
+        let a = StructError::from(TestDomainReason::Why1);
+        let b = StructError::from(TestDomainReason::Why1).with_detail("b");
+        assert!(std::sync::Arc::ptr_eq(a.context(), b.context()));
+
+        let c = a.clone().want("load");
+        assert_eq!(c.contexts().len(), 1);
+        assert!(a.contexts().is_empty());
+    }
+
+    #[test]
+    fn test_small_contexts_stay_inline() {
+        let mut ctx = OperationContext::want("load");
+        for (k, v) in [("a", "1"), ("b", "2"), ("c", "3")] {
+            ctx.record(k, v);
+        }
+        assert!(!ctx.context().items.spilled());
+        let err = StructError::from(TestDomainReason::Why1)
+            .with(ctx.clone())
+            .with(ctx.clone());
+        assert!(!err.context().spilled());
+
+        ctx.record("d", "4");
+        assert!(ctx.context().items.spilled());
+        let err = err.with(ctx);
+        assert!(err.context().spilled());
+        assert_eq!(err.contexts()[2].context().items.len(), 4);
+    }
+
+    #[test]
+    fn test_context_map_prefixes_duplicate_keys() {
+        let mut read = OperationContext::want("read file");
+        read.record("path", "/etc/app.toml");
+        read.record("attempt", "1");
+        read.record("attempt", "2");
+        let mut load = OperationContext::want("load config");
+        load.record("path", "app.toml");
+        load.record("profile", "prod");
+        let mut unnamed = OperationContext::new();
+        unnamed.record("path", "x");
+        let err = StructError::from(TestDomainReason::Why1)
+            .with(read)
+            .with(load)
+            .with(unnamed);
+
+        let map = err.context_map();
+        assert_eq!(map["path"], "/etc/app.toml");
+        assert_eq!(map["attempt"], "2");
+        assert_eq!(map["load config.path"], "app.toml");
+        assert_eq!(map["profile"], "prod");
+        assert_eq!(map["2.path"], "x");
+        assert_eq!(map.len(), 5);
+
+        #[cfg(feature = "json")]
+        assert_eq!(err.context_json()["load config.path"], "app.toml");
+    }
+
+    #[test]
+    fn test_with_target_overrides_context_target() {
+        use crate::ToStructError;
+
+        let err = TestDomainReason::Why1.to_err();
+        assert_eq!(err.target(), None);
+        let err = err.with_target("order A-1");
+        assert_eq!(err.target().as_deref(), Some("order A-1"));
+        assert!(err.to_string().contains("-> Want: order A-1"));
+        assert!(err.contexts().is_empty());
+
+        let err = StructError::from(TestDomainReason::Why1)
+            .want("load order")
+            .with_target("order A-2");
+        assert_eq!(err.target().as_deref(), Some("order A-2"));
+        assert_eq!(err.contexts()[0].target().as_deref(), Some("load order"));
+    }
+
+    #[test]
+    fn test_position_trail_across_layers() {
+        let mut err = StructError::from(TestDomainReason::Why1).position("src/db.rs:1:1");
+        // 连续相同的位置只记录一次
+        err = err.position("src/db.rs:1:1").position("src/repo.rs:2:2");
+        assert_eq!(err.imp().position().as_deref(), Some("src/db.rs:1:1"));
+        assert_eq!(err.position_trail(), ["src/repo.rs:2:2"]);
+
+        let err = err.want("load order");
+        let positions = err.positions();
+        assert_eq!(positions.len(), 3);
+        assert!(positions[2].starts_with("src/core/case.rs:"));
+
+        // 轨迹只保留最近的标注点，with_position 仍覆盖首个位置
+        let mut err = err.with_position("src/main.rs:9:9");
+        for i in 0..20 {
+            err = err.position(format!("src/layer.rs:{i}:1"));
+        }
+        assert_eq!(err.imp().position().as_deref(), Some("src/main.rs:9:9"));
+        assert_eq!(err.position_trail().len(), 8);
+        assert_eq!(err.position_trail().last().unwrap(), "src/layer.rs:19:1");
+    }
 }
@@ -0,0 +1,275 @@
+//! 错误目录：登记领域原因，生成错误码文档（Markdown / HTML）与 JSON 清单
+
+use std::fmt::{self, Debug, Display, Write};
+
+use super::{
+    error::short_type_name, format::write_json_str, strategy::StrategyFor, ErrorCode, HttpStatus,
+    UvsReason,
+};
+
+/// 目录中的一行，对应一个登记的原因
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CatalogEntry {
+    pub code: i32,
+    /// 类型与变体名，例如 `UvsReason::NotFoundError`
+    pub name: String,
+    /// 通用原因的类别（见 `UvsReason::category_name`）；纯领域原因为 `domain`
+    pub category: &'static str,
+    pub retryable: bool,
+    pub http_status: u16,
+    pub message: String,
+}
+
+/// 错误目录；Markdown、HTML 与 JSON 输出均来自同一份登记
+#[derive(Debug, Clone, Default)]
+pub struct ErrorCatalog {
+    entries: Vec<CatalogEntry>,
+}
+
+impl ErrorCatalog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 预先登记全部通用原因（每个错误码一个代表变体）
+    pub fn uvs() -> Self {
+        let mut catalog = Self::new();
+        catalog.register([
+            UvsReason::validation_error(),
+            UvsReason::business_error(),
+            UvsReason::not_found_error(),
+            UvsReason::permission_error(),
+            UvsReason::logic_error(),
+            UvsReason::rule_error(),
+            UvsReason::data_error(),
+            UvsReason::system_error(),
+            UvsReason::network_error(),
+            UvsReason::resource_error(),
+            UvsReason::timeout_error(),
+            UvsReason::core_conf(),
+            UvsReason::external_error(),
+        ]);
+        catalog
+    }
+
+    /// 登记领域原因的样例值，每个变体提供一个即可；条目按错误码排序
+    pub fn register<R, I>(&mut self, reasons: I) -> &mut Self
+    where
+        R: ErrorCode + HttpStatus + StrategyFor + Display + Debug,
+        I: IntoIterator<Item = R>,
+    {
+        for reason in reasons {
+            self.entries.push(CatalogEntry {
+                code: reason.error_code(),
+                name: format!("{}::{}", short_type_name::<R>(), variant_name(&reason)),
+                category: reason.as_uvs().map_or("domain", UvsReason::category_name),
+                retryable: reason.strategy().is_retry(),
+                http_status: reason.http_status(),
+                message: reason.to_string(),
+            });
+        }
+        self.entries.sort_by_key(|e| e.code);
+        self
+    }
+
+    pub fn entries(&self) -> &[CatalogEntry] {
+        &self.entries
+    }
+
+    /// Markdown 表格，可直接放入 README 或文档站点
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::from(
+            "| Code | Name | Category | Retryable | HTTP | Message |\n\
+             |-----:|------|----------|:---------:|-----:|---------|\n",
+        );
+        for e in &self.entries {
+            let _ = writeln!(
+                out,
+                "| {} | `{}` | {} | {} | {} | {} |",
+                e.code,
+                e.name,
+                e.category,
+                if e.retryable { "yes" } else { "no" },
+                e.http_status,
+                e.message.replace('|', "\\|"),
+            );
+        }
+        out
+    }
+
+    /// 独立的 HTML `<table>` 片段
+    pub fn to_html(&self) -> String {
+        let mut out = String::from(
+            "<table>\n<thead><tr><th>Code</th><th>Name</th><th>Category</th>\
+             <th>Retryable</th><th>HTTP</th><th>Message</th></tr></thead>\n<tbody>\n",
+        );
+        for e in &self.entries {
+            let _ = writeln!(
+                out,
+                "<tr><td>{}</td><td><code>{}</code></td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                e.code,
+                HtmlEscape(&e.name),
+                e.category,
+                if e.retryable { "yes" } else { "no" },
+                e.http_status,
+                HtmlEscape(&e.message),
+            );
+        }
+        out.push_str("</tbody>\n</table>\n");
+        out
+    }
+
+    /// JSON 数组 `[{"code","name","category","retryable","http_status","message"}]`，不依赖 serde
+    pub fn to_json(&self) -> String {
+        let mut out = String::from("[");
+        for (i, e) in self.entries.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            let _ = write_entry_json(&mut out, e);
+        }
+        out.push(']');
+        out
+    }
+}
+
+/// `Debug` 输出中的变体名：去掉字段部分，例如 `ConfigError(Core)` 取 `ConfigError`
+fn variant_name(reason: &impl Debug) -> String {
+    let debug = format!("{reason:?}");
+    let end = debug.find(['(', ' ', '{']).unwrap_or(debug.len());
+    debug[..end].to_string()
+}
+
+fn write_entry_json(out: &mut dyn Write, e: &CatalogEntry) -> fmt::Result {
+    write!(out, "{{\"code\":{},\"name\":", e.code)?;
+    write_json_str(out, &e.name)?;
+    out.write_str(",\"category\":")?;
+    write_json_str(out, e.category)?;
+    write!(
+        out,
+        ",\"retryable\":{},\"http_status\":{},\"message\":",
+        e.retryable, e.http_status
+    )?;
+    write_json_str(out, &e.message)?;
+    out.write_char('}')
+}
+
+struct HtmlEscape<'a>(&'a str);
+
+impl Display for HtmlEscape<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for c in self.0.chars() {
+            match c {
+                '<' => f.write_str("&lt;")?,
+                '>' => f.write_str("&gt;")?,
+                '&' => f.write_str("&amp;")?,
+                '"' => f.write_str("&quot;")?,
+                c => f.write_char(c)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AsUvsReason, ErrStrategy};
+    use derive_more::From;
+    use thiserror::Error;
+
+    #[derive(Debug, Error, From)]
+    enum OrderReason {
+        #[error("order <{0}> locked")]
+        Locked(u32),
+        #[error("{0}")]
+        Uvs(UvsReason),
+    }
+
+    impl ErrorCode for OrderReason {
+        fn error_code(&self) -> i32 {
+            match self {
+                Self::Locked(_) => 1001,
+                Self::Uvs(u) => u.error_code(),
+            }
+        }
+    }
+
+    impl HttpStatus for OrderReason {
+        fn http_status(&self) -> u16 {
+            match self {
+                Self::Locked(_) => 409,
+                Self::Uvs(u) => u.http_status(),
+            }
+        }
+    }
+
+    impl AsUvsReason for OrderReason {
+        fn as_uvs(&self) -> Option<&UvsReason> {
+            match self {
+                Self::Uvs(u) => Some(u),
+                _ => None,
+            }
+        }
+    }
+
+    impl StrategyFor for OrderReason {
+        fn strategy(&self) -> ErrStrategy {
+            match self {
+                Self::Locked(_) => ErrStrategy::Retry,
+                Self::Uvs(u) => u.strategy(),
+            }
+        }
+    }
+
+    fn catalog() -> ErrorCatalog {
+        let mut catalog = ErrorCatalog::uvs();
+        catalog.register([
+            OrderReason::Locked(0),
+            OrderReason::Uvs(UvsReason::network_error()),
+        ]);
+        catalog
+    }
+
+    #[test]
+    fn test_catalog_entries() {
+        let catalog = catalog();
+        assert_eq!(catalog.entries().len(), 15);
+        assert_eq!(catalog.entries()[0].code, 100);
+        let last = catalog.entries().last().unwrap();
+        assert_eq!(last.name, "OrderReason::Locked");
+        assert_eq!(last.category, "domain");
+        assert!(last.retryable);
+        let conf = catalog.entries().iter().find(|e| e.code == 300).unwrap();
+        assert_eq!(conf.name, "UvsReason::ConfigError");
+        assert_eq!(conf.category, "config");
+        let net = catalog
+            .entries()
+            .iter()
+            .find(|e| e.name == "OrderReason::Uvs")
+            .unwrap();
+        assert_eq!(
+            (net.code, net.category, net.http_status),
+            (202, "network", 502)
+        );
+    }
+
+    #[test]
+    fn test_catalog_renderers() {
+        let catalog = catalog();
+        let md = catalog.to_markdown();
+        assert!(md.starts_with("| Code | Name |"));
+        assert!(md.contains(
+            "| 102 | `UvsReason::NotFoundError` | not_found | no | 404 | not found error |"
+        ));
+
+        let html = catalog.to_html();
+        assert!(html.contains("<td>order &lt;0&gt; locked</td>"));
+        assert_eq!(html.matches("<tr>").count(), 16);
+
+        let json = catalog.to_json();
+        assert!(json.contains(
+            r#"{"code":1001,"name":"OrderReason::Locked","category":"domain","retryable":true,"http_status":409,"message":"order <0> locked"}"#
+        ));
+    }
+}
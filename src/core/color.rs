@@ -0,0 +1,139 @@
+use std::{
+    fmt::{self, Write},
+    io::IsTerminal,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+use super::format::{write_verbose, ErrorFormatter, ErrorView, Palette};
+
+const RESET: &str = "\x1b[0m";
+const BOLD_RED: &str = "\x1b[1;31m";
+const BOLD: &str = "\x1b[1m";
+const CYAN: &str = "\x1b[36m";
+const GREEN: &str = "\x1b[32m";
+const YELLOW: &str = "\x1b[33m";
+const DIM: &str = "\x1b[2m";
+
+/// 终端着色策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorChoice {
+    /// 输出为 TTY 且未设置 `NO_COLOR` 时着色
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+static COLOR_CHOICE: AtomicU8 = AtomicU8::new(0);
+
+/// 设置 `print_error` 使用的着色策略
+pub fn set_color_choice(choice: ColorChoice) {
+    let v = match choice {
+        ColorChoice::Auto => 0,
+        ColorChoice::Always => 1,
+        ColorChoice::Never => 2,
+    };
+    COLOR_CHOICE.store(v, Ordering::Relaxed);
+}
+
+pub fn color_choice() -> ColorChoice {
+    match COLOR_CHOICE.load(Ordering::Relaxed) {
+        1 => ColorChoice::Always,
+        2 => ColorChoice::Never,
+        _ => ColorChoice::Auto,
+    }
+}
+
+impl ColorChoice {
+    /// 判断对指定输出流是否应着色
+    pub fn enabled_for<S: IsTerminal>(self, stream: &S) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => no_color_unset() && stream.is_terminal(),
+        }
+    }
+}
+
+fn no_color_unset() -> bool {
+    std::env::var_os("NO_COLOR").is_none_or(|v| v.is_empty())
+}
+
+/// 彩色多行格式：与 `VerboseFormatter` 共用同一布局（含位置轨迹、时间与构建信息），仅增加 ANSI 着色
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ColorFormatter;
+
+const PALETTE: Palette = Palette {
+    code: BOLD_RED,
+    bold: BOLD,
+    position: CYAN,
+    target: GREEN,
+    detail: YELLOW,
+    dim: DIM,
+    reset: RESET,
+};
+
+impl ErrorFormatter for ColorFormatter {
+    fn write_to(&self, view: &ErrorView<'_>, out: &mut dyn Write) -> fmt::Result {
+        write_verbose(view, out, &PALETTE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{StructError, UvsReason};
+
+    #[test]
+    fn test_color_formatter_wraps_code() {
+        let err = StructError::from(UvsReason::network_error()).with_detail("refused");
+        let out = err.format_with(&ColorFormatter);
+        assert!(out.starts_with("\x1b[1;31m[202]\x1b[0m"));
+        assert!(out.contains("\x1b[33mrefused\x1b[0m"));
+    }
+
+    #[test]
+    fn test_color_renders_position_trail() {
+        use crate::ErrorWith;
+
+        let err = StructError::from(UvsReason::network_error())
+            .with_position("src/net.rs:1:1")
+            .position("src/api.rs:2:2");
+        let out = err.format_with(&ColorFormatter);
+        assert!(out.contains("\n     \x1b[2mvia\x1b[0m \x1b[36msrc/api.rs:2:2\x1b[0m"));
+    }
+
+    fn strip_ansi(text: &str) -> String {
+        let mut out = String::new();
+        let mut chars = text.chars();
+        while let Some(c) = chars.next() {
+            if c == '\x1b' {
+                chars.by_ref().find(|&c| c == 'm');
+            } else {
+                out.push(c);
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn test_color_layout_matches_verbose() {
+        use crate::{ErrorWith, OperationContext, VerboseFormatter};
+
+        let err = StructError::from(UvsReason::network_error())
+            .with_detail("refused")
+            .with_position("src/net.rs:1:1")
+            .position("src/api.rs:2:2")
+            .with_error_id("01HX")
+            .with(OperationContext::want("fetch"));
+        let colored = err.format_with(&ColorFormatter);
+        assert_ne!(colored, err.format_with(&VerboseFormatter));
+        assert_eq!(strip_ansi(&colored), err.format_with(&VerboseFormatter));
+    }
+
+    #[test]
+    fn test_color_choice_explicit() {
+        assert!(ColorChoice::Always.enabled_for(&std::io::stdout()));
+        assert!(!ColorChoice::Never.enabled_for(&std::io::stdout()));
+    }
+}
@@ -0,0 +1,436 @@
+//! 全局错误策略：部署时一次性配置，构造错误与渲染时统一参考
+
+use std::{
+    fmt::{self, Display},
+    sync::{Arc, OnceLock, RwLock},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use super::{
+    id::set_error_id_enabled,
+    limit::{set_error_limits, ErrorLimits},
+    redact::register_sensitive_key,
+};
+
+/// `format_error` / `print_error` 使用的默认渲染格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DefaultFormat {
+    /// `[error code N]` 加上下文列表（历史格式）
+    #[default]
+    Classic,
+    Verbose,
+    Compact,
+    Json,
+}
+
+/// 时间戳渲染格式，作用于详细格式、JSON 格式与 serde 序列化
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum TimestampFormat {
+    /// Unix 毫秒（历史格式）
+    #[default]
+    UnixMillis,
+    /// UTC 的 RFC 3339，精确到毫秒，例如 `2024-05-01T08:30:00.123Z`
+    Rfc3339,
+    /// 本地时区的 RFC 3339（需 `chrono` 特性，未启用时按 UTC 渲染）
+    Local,
+}
+
+impl TimestampFormat {
+    pub fn format(&self, ts: SystemTime) -> String {
+        match self {
+            Self::UnixMillis => unix_millis(ts).to_string(),
+            #[cfg(feature = "chrono")]
+            Self::Local => chrono::DateTime::<chrono::Local>::from(ts)
+                .to_rfc3339_opts(chrono::SecondsFormat::Millis, false),
+            _ => rfc3339_utc(ts),
+        }
+    }
+}
+
+fn unix_millis(ts: SystemTime) -> u64 {
+    ts.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// 不依赖日期库的 UTC 渲染（按公历换算天数）
+fn rfc3339_utc(ts: SystemTime) -> String {
+    let ms = unix_millis(ts);
+    let secs = ms / 1000;
+    let (days, rem) = (secs / 86_400, secs % 86_400);
+    // 以 0000-03-01 为纪元换算年月日，见 Howard Hinnant 的 civil_from_days
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{:03}Z",
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60,
+        ms % 1000
+    )
+}
+
+/// 解析 `TimestampFormat` 渲染的 RFC 3339 时间（`Z` 或 `±HH:MM` 时区，秒的小数部分可选）；
+/// 早于 Unix 纪元或格式不符时返回 `None`
+#[cfg(feature = "serde")]
+pub(crate) fn parse_rfc3339(s: &str) -> Option<SystemTime> {
+    let num = |range: std::ops::Range<usize>| -> Option<i64> {
+        let part = s.get(range)?;
+        if !part.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        part.parse().ok()
+    };
+    let (year, month, day) = (num(0..4)?, num(5..7)?, num(8..10)?);
+    let (hour, min, sec) = (num(11..13)?, num(14..16)?, num(17..19)?);
+    if s.get(4..5)? != "-" || s.get(7..8)? != "-" || !matches!(s.get(10..11)?, "T" | "t" | " ") {
+        return None;
+    }
+    let mut rest = s.get(19..)?;
+    let mut millis = 0;
+    if let Some(frac) = rest.strip_prefix('.') {
+        let len = frac.bytes().take_while(u8::is_ascii_digit).count();
+        let digits = format!("{:0<3}", &frac[..len.min(3)]);
+        millis = digits.parse::<i64>().ok()?;
+        rest = &frac[len..];
+    }
+    let offset = match rest {
+        "Z" | "z" => 0,
+        _ => {
+            let sign = match rest.get(..1)? {
+                "+" => 1,
+                "-" => -1,
+                _ => return None,
+            };
+            let (h, m) = (
+                rest.get(1..3)?.parse::<i64>().ok()?,
+                rest.get(4..6)?.parse::<i64>().ok()?,
+            );
+            sign * (h * 3600 + m * 60)
+        }
+    };
+    // civil_from_days 的逆运算
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+    let secs = days * 86_400 + hour * 3600 + min * 60 + sec - offset;
+    let ms = u64::try_from(secs * 1000 + millis).ok()?;
+    Some(UNIX_EPOCH + std::time::Duration::from_millis(ms))
+}
+
+/// 应用构建信息，创建错误时记录，用于将错误归属到具体的部署版本
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AppInfo {
+    #[cfg_attr(feature = "serde", serde(rename = "name"))]
+    pub name: String,
+    #[cfg_attr(feature = "serde", serde(rename = "version"))]
+    pub version: String,
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "git_sha", skip_serializing_if = "Option::is_none")
+    )]
+    pub git_sha: Option<String>,
+}
+
+impl Display for AppInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.name, self.version)?;
+        if let Some(sha) = &self.git_sha {
+            write!(f, " ({sha})")?;
+        }
+        Ok(())
+    }
+}
+
+static APP_INFO: RwLock<Option<Arc<AppInfo>>> = RwLock::new(None);
+
+/// 当前设置的应用构建信息
+pub fn app_info() -> Option<Arc<AppInfo>> {
+    APP_INFO.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// 全局错误配置，通过 `init` 安装，进程内仅首次生效
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ErrorConfig {
+    /// 构造错误时捕获调用栈（不受 `RUST_BACKTRACE` 影响）
+    pub capture_backtrace: bool,
+    /// 构造错误时记录时间戳
+    pub capture_timestamp: bool,
+    pub timestamp_format: TimestampFormat,
+    /// 自动生成错误 ID
+    pub error_id: bool,
+    pub limits: ErrorLimits,
+    /// 追加注册的敏感键模式
+    pub sensitive_keys: Vec<String>,
+    pub format: DefaultFormat,
+}
+
+static CONFIG: OnceLock<ErrorConfig> = OnceLock::new();
+
+impl ErrorConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn with_backtrace(mut self, enabled: bool) -> Self {
+        self.capture_backtrace = enabled;
+        self
+    }
+
+    #[must_use]
+    pub fn with_timestamp(mut self, enabled: bool) -> Self {
+        self.capture_timestamp = enabled;
+        self
+    }
+
+    #[must_use]
+    pub fn with_timestamp_format(mut self, format: TimestampFormat) -> Self {
+        self.timestamp_format = format;
+        self
+    }
+
+    #[must_use]
+    pub fn with_error_id(mut self, enabled: bool) -> Self {
+        self.error_id = enabled;
+        self
+    }
+
+    #[must_use]
+    pub fn with_max_detail_len(mut self, max: usize) -> Self {
+        self.limits.max_detail_len = Some(max);
+        self
+    }
+
+    #[must_use]
+    pub fn with_max_context_depth(mut self, max: usize) -> Self {
+        self.limits.max_context_depth = Some(max);
+        self
+    }
+
+    #[must_use]
+    pub fn with_limits(mut self, limits: ErrorLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    #[must_use]
+    pub fn with_sensitive_key<S: Into<String>>(mut self, pattern: S) -> Self {
+        self.sensitive_keys.push(pattern.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_format(mut self, format: DefaultFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// 设置应用构建信息，之后创建的错误在序列化与详细格式中附带该信息；`git_sha` 为空时省略
+    ///
+    /// ```rust,ignore
+    /// ErrorConfig::set_app_info(env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"), "3f2a1c9");
+    /// ```
+    pub fn set_app_info(
+        name: impl Into<String>,
+        version: impl Into<String>,
+        git_sha: impl Into<String>,
+    ) {
+        let git_sha = git_sha.into();
+        let info = AppInfo {
+            name: name.into(),
+            version: version.into(),
+            git_sha: (!git_sha.is_empty()).then_some(git_sha),
+        };
+        *APP_INFO.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(info));
+    }
+
+    /// 从环境变量读取配置，便于按部署调整而无需改代码：
+    ///
+    /// - `ORION_ERROR_BACKTRACE` / `ORION_ERROR_TIMESTAMP` / `ORION_ERROR_ID`：`1`、`true`、`on` 为开启
+    /// - `ORION_ERROR_TIMESTAMP_FORMAT`：`unix_ms` / `rfc3339` / `local`
+    /// - `ORION_ERROR_MAX_DETAIL`：`detail` 最大字节数
+    /// - `ORION_ERROR_MAX_DEPTH`：渲染时显示的最大上下文帧数
+    /// - `ORION_ERROR_SENSITIVE_KEYS`：逗号分隔的敏感键
+    /// - `ORION_ERROR_FORMAT`：`classic` / `verbose` / `compact` / `json`
+    pub fn from_env() -> Self {
+        Self::from_lookup(|k| std::env::var(k).ok())
+    }
+
+    fn from_lookup(get: impl Fn(&str) -> Option<String>) -> Self {
+        let flag = |k: &str| {
+            get(k).is_some_and(|v| {
+                matches!(
+                    v.trim().to_ascii_lowercase().as_str(),
+                    "1" | "true" | "on" | "yes"
+                )
+            })
+        };
+        let mut cfg = Self::new()
+            .with_backtrace(flag("ORION_ERROR_BACKTRACE"))
+            .with_timestamp(flag("ORION_ERROR_TIMESTAMP"))
+            .with_error_id(flag("ORION_ERROR_ID"));
+        if let Some(max) = get("ORION_ERROR_MAX_DETAIL").and_then(|v| v.trim().parse().ok()) {
+            cfg = cfg.with_max_detail_len(max);
+        }
+        if let Some(max) = get("ORION_ERROR_MAX_DEPTH").and_then(|v| v.trim().parse().ok()) {
+            cfg = cfg.with_max_context_depth(max);
+        }
+        if let Some(keys) = get("ORION_ERROR_SENSITIVE_KEYS") {
+            cfg.sensitive_keys.extend(
+                keys.split(',')
+                    .map(str::trim)
+                    .filter(|k| !k.is_empty())
+                    .map(str::to_string),
+            );
+        }
+        cfg.timestamp_format = match get("ORION_ERROR_TIMESTAMP_FORMAT")
+            .map(|v| v.trim().to_ascii_lowercase())
+            .as_deref()
+        {
+            Some("rfc3339") => TimestampFormat::Rfc3339,
+            Some("local") => TimestampFormat::Local,
+            _ => TimestampFormat::UnixMillis,
+        };
+        cfg.format = match get("ORION_ERROR_FORMAT")
+            .map(|v| v.trim().to_ascii_lowercase())
+            .as_deref()
+        {
+            Some("verbose") => DefaultFormat::Verbose,
+            Some("compact") => DefaultFormat::Compact,
+            Some("json") => DefaultFormat::Json,
+            _ => DefaultFormat::Classic,
+        };
+        cfg
+    }
+
+    /// 安装为全局配置并同步错误 ID、体积限制与敏感键；已安装过时原样返回 `Err(self)`
+    pub fn init(self) -> Result<(), ErrorConfig> {
+        let mut installed = false;
+        let cfg = CONFIG.get_or_init(|| {
+            installed = true;
+            self.clone()
+        });
+        if !installed {
+            return Err(self);
+        }
+        set_error_id_enabled(cfg.error_id);
+        set_error_limits(cfg.limits);
+        for key in &cfg.sensitive_keys {
+            register_sensitive_key(key.as_str());
+        }
+        Ok(())
+    }
+}
+
+/// 当前生效的全局配置；未调用 `ErrorConfig::init` 时为默认值
+pub fn error_config() -> &'static ErrorConfig {
+    static DEFAULT: ErrorConfig = ErrorConfig {
+        capture_backtrace: false,
+        capture_timestamp: false,
+        timestamp_format: TimestampFormat::UnixMillis,
+        error_id: false,
+        limits: ErrorLimits {
+            max_detail_len: None,
+            max_context_items: None,
+            max_value_len: None,
+            max_context_depth: None,
+        },
+        sensitive_keys: Vec::new(),
+        format: DefaultFormat::Classic,
+    };
+    CONFIG.get().unwrap_or(&DEFAULT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_from_env_lookup() {
+        let env: HashMap<&str, &str> = [
+            ("ORION_ERROR_BACKTRACE", "true"),
+            ("ORION_ERROR_ID", "0"),
+            ("ORION_ERROR_MAX_DETAIL", "256"),
+            ("ORION_ERROR_MAX_DEPTH", "8"),
+            ("ORION_ERROR_SENSITIVE_KEYS", "token, password,,"),
+            ("ORION_ERROR_FORMAT", "JSON"),
+            ("ORION_ERROR_TIMESTAMP_FORMAT", "rfc3339"),
+        ]
+        .into_iter()
+        .collect();
+        let cfg = ErrorConfig::from_lookup(|k| env.get(k).map(|v| v.to_string()));
+        assert!(cfg.capture_backtrace);
+        assert!(!cfg.capture_timestamp);
+        assert!(!cfg.error_id);
+        assert_eq!(cfg.limits.max_detail_len, Some(256));
+        assert_eq!(cfg.limits.max_context_depth, Some(8));
+        assert_eq!(cfg.sensitive_keys, vec!["token", "password"]);
+        assert_eq!(cfg.format, DefaultFormat::Json);
+        assert_eq!(cfg.timestamp_format, TimestampFormat::Rfc3339);
+    }
+
+    #[test]
+    fn test_timestamp_formats() {
+        use std::time::Duration;
+
+        let ts = UNIX_EPOCH + Duration::from_millis(1_709_254_245_123);
+        assert_eq!(TimestampFormat::UnixMillis.format(ts), "1709254245123");
+        assert_eq!(
+            TimestampFormat::Rfc3339.format(ts),
+            "2024-03-01T00:50:45.123Z"
+        );
+        assert_eq!(
+            TimestampFormat::Rfc3339.format(UNIX_EPOCH),
+            "1970-01-01T00:00:00.000Z"
+        );
+        let end_of_leap_day = UNIX_EPOCH + Duration::from_secs(951_868_799);
+        assert_eq!(
+            TimestampFormat::Rfc3339.format(end_of_leap_day),
+            "2000-02-29T23:59:59.000Z"
+        );
+        assert!(TimestampFormat::Local.format(ts).starts_with("2024-0"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_parse_rfc3339() {
+        use std::time::Duration;
+
+        let ts = UNIX_EPOCH + Duration::from_millis(1_709_254_245_123);
+        assert_eq!(
+            parse_rfc3339(&TimestampFormat::Rfc3339.format(ts)),
+            Some(ts)
+        );
+        assert_eq!(parse_rfc3339(&TimestampFormat::Local.format(ts)), Some(ts));
+        assert_eq!(
+            parse_rfc3339("2024-03-01T08:50:45.1+08:00"),
+            Some(ts - Duration::from_millis(23))
+        );
+        assert_eq!(
+            parse_rfc3339("2000-02-29T23:59:59Z"),
+            Some(UNIX_EPOCH + Duration::from_secs(951_868_799))
+        );
+        assert_eq!(parse_rfc3339("2024-03-01"), None);
+        assert_eq!(parse_rfc3339("1969-12-31T23:59:59Z"), None);
+    }
+
+    #[test]
+    fn test_default_config_when_uninitialized() {
+        assert_eq!(ErrorConfig::from_lookup(|_| None), ErrorConfig::default());
+    }
+}
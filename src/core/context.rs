@@ -1,9 +1,27 @@
 #[cfg(all(feature = "log", not(feature = "tracing")))]
-use log::{debug, error, info, trace, warn};
+use log::{debug, error, info, warn};
 use std::{
+    borrow::Cow,
+    collections::BTreeMap,
     fmt::Display,
     ops::{Deref, DerefMut},
     path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, AtomicU8, Ordering},
+        Arc, OnceLock, RwLock,
+    },
+    time::Duration,
+};
+
+use smallvec::SmallVec;
+
+use super::{
+    limit::{error_limits, limit_value, truncated_items_marker, TRUNCATED_KEY},
+    redact::{is_sensitive_key, REDACTED},
+    severity::{Severity, SeverityOf},
+    stats,
+    universal::{AsUvsReason, UvsReason},
+    ErrorCode,
 };
 #[derive(Debug, Clone, PartialEq, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -14,6 +32,136 @@ pub enum OperationResult {
     Cancel,
 }
 
+static CONTEXT_DEDUP: AtomicBool = AtomicBool::new(false);
+
+/// 开启后，渲染上下文时同名键只显示最后一次记录的值（默认关闭，存储的条目不受影响）
+pub fn set_context_dedup(enabled: bool) {
+    CONTEXT_DEDUP.store(enabled, Ordering::Relaxed);
+}
+
+pub fn context_dedup() -> bool {
+    CONTEXT_DEDUP.load(Ordering::Relaxed)
+}
+
+/// 记录路径时的显示方式，默认原样显示
+///
+/// 临时目录等绝对路径会让错误信息冗长，并在多租户日志中泄露用户名，可按部署需要开启规范化、
+/// 相对化或将主目录缩写为 `~`。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PathDisplay {
+    /// 解析符号链接与 `..`（路径不存在时保持原样）
+    pub canonicalize: bool,
+    /// 位于该目录下的路径显示为相对路径
+    pub base: Option<PathBuf>,
+    /// 将 `$HOME` 前缀显示为 `~`
+    pub shorten_home: bool,
+}
+
+impl PathDisplay {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn with_canonicalize(mut self, enabled: bool) -> Self {
+        self.canonicalize = enabled;
+        self
+    }
+
+    #[must_use]
+    pub fn with_base<P: Into<PathBuf>>(mut self, base: P) -> Self {
+        self.base = Some(base.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_shorten_home(mut self, enabled: bool) -> Self {
+        self.shorten_home = enabled;
+        self
+    }
+
+    /// 按当前选项渲染路径
+    pub fn render(&self, path: &Path) -> String {
+        let home = self
+            .shorten_home
+            .then(|| std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE")))
+            .flatten()
+            .map(PathBuf::from);
+        self.render_with_home(path, home.as_deref())
+    }
+
+    fn render_with_home(&self, path: &Path, home: Option<&Path>) -> String {
+        let canonical = self
+            .canonicalize
+            .then(|| std::fs::canonicalize(path).ok())
+            .flatten();
+        let path = canonical.as_deref().unwrap_or(path);
+        if let Some(rel) = self.base.as_deref().and_then(|b| path.strip_prefix(b).ok()) {
+            if rel.as_os_str().is_empty() {
+                return ".".to_string();
+            }
+            return rel.display().to_string();
+        }
+        if let Some(rest) = home.and_then(|h| path.strip_prefix(h).ok()) {
+            if rest.as_os_str().is_empty() {
+                return "~".to_string();
+            }
+            return format!("~{}{}", std::path::MAIN_SEPARATOR, rest.display());
+        }
+        path.display().to_string()
+    }
+}
+
+static PATH_DISPLAY: RwLock<Option<PathDisplay>> = RwLock::new(None);
+
+/// 设置记录路径时的全局显示方式
+pub fn set_path_display(display: PathDisplay) {
+    *PATH_DISPLAY.write().unwrap_or_else(|e| e.into_inner()) = Some(display);
+}
+
+pub fn path_display() -> PathDisplay {
+    PATH_DISPLAY
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .unwrap_or_default()
+}
+
+/// 按全局 `PathDisplay` 渲染路径，未设置时等同于 `path.display()`
+pub(crate) fn render_path(path: &Path) -> String {
+    match PATH_DISPLAY
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+    {
+        Some(display) => display.render(path),
+        None => path.display().to_string(),
+    }
+}
+
+/// `ErrorWith::want` 在已有上下文时的行为
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WantMode {
+    /// 覆盖最近一帧的目标（历史行为）
+    #[default]
+    Replace,
+    /// 最近一帧已有目标时追加新帧，不丢失已记录的目标
+    Append,
+}
+
+static WANT_MODE: AtomicU8 = AtomicU8::new(0);
+
+pub fn set_want_mode(mode: WantMode) {
+    WANT_MODE.store(mode as u8, Ordering::Relaxed);
+}
+
+pub fn want_mode() -> WantMode {
+    match WANT_MODE.load(Ordering::Relaxed) {
+        1 => WantMode::Append,
+        _ => WantMode::Replace,
+    }
+}
+
 // 使用编译期模块路径作为默认日志 target，以提升可读性
 const DEFAULT_MOD_PATH: &str = module_path!();
 
@@ -28,11 +176,37 @@ macro_rules! op_context {
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OperationContext {
+    #[cfg_attr(feature = "serde", serde(rename = "context"))]
     context: CallContext,
+    #[cfg_attr(feature = "serde", serde(rename = "result"))]
     result: OperationResult,
+    #[cfg_attr(feature = "serde", serde(rename = "exit_log"))]
     exit_log: bool,
+    #[cfg_attr(feature = "serde", serde(rename = "mod_path"))]
     mod_path: String,
+    #[cfg_attr(feature = "serde", serde(rename = "target"))]
     target: Option<String>,
+    /// 失败时退出日志使用的严重级别，未设置时按 `Severity::Error` 输出
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "severity", default, skip_serializing_if = "Option::is_none")
+    )]
+    severity: Option<Severity>,
+    /// 子步骤上下文，渲染时缩进显示在父上下文之下
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "children", default, skip_serializing_if = "Vec::is_empty")
+    )]
+    children: Vec<OperationContext>,
+    /// `mark_fail_with` 记录的错误码与类别，仅用于结构化退出日志
+    #[cfg_attr(feature = "serde", serde(skip))]
+    failure: Option<(String, Option<&'static str>)>,
+    /// 附加到错误时的调用位置（`file:line:column`），由 `want` / `with` 自动记录
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "location", default, skip_serializing_if = "Option::is_none")
+    )]
+    location: Option<String>,
 }
 impl Default for OperationContext {
     fn default() -> Self {
@@ -42,6 +216,10 @@ impl Default for OperationContext {
             result: OperationResult::Fail,
             exit_log: false,
             mod_path: DEFAULT_MOD_PATH.into(),
+            severity: None,
+            children: Vec::new(),
+            failure: None,
+            location: None,
         }
     }
 }
@@ -54,10 +232,69 @@ impl From<CallContext> for OperationContext {
             target: None,
             exit_log: false,
             mod_path: DEFAULT_MOD_PATH.into(),
+            severity: None,
+            children: Vec::new(),
+            failure: None,
+            location: None,
         }
     }
 }
 
+/// 按严重级别输出日志：log 以 `mod_path` 为 target，tracing 以 `domain` 为 target 并附带 `mod_path` 字段
+#[allow(unused_variables)]
+pub(crate) fn emit_log(severity: Severity, mod_path: &str, msg: std::fmt::Arguments<'_>) {
+    #[cfg(feature = "tracing")]
+    match severity {
+        Severity::Debug => tracing::debug!(target: "domain", mod_path = %mod_path, "{msg}"),
+        Severity::Info => tracing::info!(target: "domain", mod_path = %mod_path, "{msg}"),
+        Severity::Warn => tracing::warn!(target: "domain", mod_path = %mod_path, "{msg}"),
+        Severity::Error | Severity::Critical => {
+            tracing::error!(target: "domain", mod_path = %mod_path, "{msg}")
+        }
+    }
+
+    #[cfg(all(feature = "log", not(feature = "tracing")))]
+    match severity {
+        Severity::Debug => debug!(target: mod_path, "{msg}"),
+        Severity::Info => info!(target: mod_path, "{msg}"),
+        Severity::Warn => warn!(target: mod_path, "{msg}"),
+        Severity::Error | Severity::Critical => error!(target: mod_path, "{msg}"),
+    }
+}
+
+/// 启用 `log-kv` 时以结构化键值输出，消息本身只保留简短描述
+#[cfg(all(feature = "log-kv", not(feature = "tracing")))]
+pub(crate) fn emit_log_kv(
+    severity: Severity,
+    mod_path: &str,
+    msg: std::fmt::Arguments<'_>,
+    kvs: &[(&str, &str)],
+) {
+    let level = match severity {
+        Severity::Debug => log::Level::Debug,
+        Severity::Info => log::Level::Info,
+        Severity::Warn => log::Level::Warn,
+        Severity::Error | Severity::Critical => log::Level::Error,
+    };
+    log_kv(level, mod_path, msg, kvs);
+}
+
+#[cfg(all(feature = "log-kv", not(feature = "tracing")))]
+fn log_kv(level: log::Level, mod_path: &str, msg: std::fmt::Arguments<'_>, kvs: &[(&str, &str)]) {
+    if level > log::max_level() {
+        return;
+    }
+    log::logger().log(
+        &log::Record::builder()
+            .args(msg)
+            .level(level)
+            .target(mod_path)
+            .module_path(Some(mod_path))
+            .key_values(&kvs)
+            .build(),
+    );
+}
+
 impl Drop for OperationContext {
     fn drop(&mut self) {
         if !self.exit_log {
@@ -75,13 +312,11 @@ impl Drop for OperationContext {
                         "suc! {ctx}"
                     )
                 }
-                OperationResult::Fail => {
-                    tracing::error!(
-                        target: "domain",
-                        mod_path = %self.mod_path,
-                        "fail! {ctx}"
-                    )
-                }
+                OperationResult::Fail => emit_log(
+                    self.severity.unwrap_or_default(),
+                    &self.mod_path,
+                    format_args!("fail! {ctx}"),
+                ),
                 OperationResult::Cancel => {
                     tracing::warn!(
                         target: "domain",
@@ -92,15 +327,33 @@ impl Drop for OperationContext {
             }
         }
 
-        #[cfg(all(feature = "log", not(feature = "tracing")))]
+        #[cfg(all(feature = "log-kv", not(feature = "tracing")))]
+        {
+            let (severity, tag) = match self.result() {
+                OperationResult::Suc => (Severity::Info, "suc!"),
+                OperationResult::Fail => (self.severity.unwrap_or_default(), "fail!"),
+                OperationResult::Cancel => (Severity::Warn, "cancel!"),
+            };
+            let target = self.target.as_deref().unwrap_or_default();
+            emit_log_kv(
+                severity,
+                &self.mod_path,
+                format_args!("{tag} {target}"),
+                &self.kv_items(),
+            );
+        }
+
+        #[cfg(all(feature = "log", not(feature = "log-kv"), not(feature = "tracing")))]
         {
             match self.result() {
                 OperationResult::Suc => {
                     info!(target: self.mod_path.as_str(), "suc! {}", self.format_context());
                 }
-                OperationResult::Fail => {
-                    error!(target: self.mod_path.as_str(), "fail! {}", self.format_context());
-                }
+                OperationResult::Fail => emit_log(
+                    self.severity.unwrap_or_default(),
+                    &self.mod_path,
+                    format_args!("fail! {}", self.format_context()),
+                ),
                 OperationResult::Cancel => {
                     warn!(target: self.mod_path.as_str(), "cancel! {}", self.format_context());
                 }
@@ -111,57 +364,192 @@ impl Drop for OperationContext {
 
 impl Display for OperationContext {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_indented(f, 0)
+    }
+}
+
+impl OperationContext {
+    fn fmt_indented(&self, f: &mut std::fmt::Formatter<'_>, depth: usize) -> std::fmt::Result {
+        let pad = "  ".repeat(depth);
         if let Some(target) = &self.target {
-            writeln!(f, "target: {target} ")?;
+            writeln!(f, "{pad}target: {target} ")?;
+        }
+        if let Some(location) = &self.location {
+            writeln!(f, "{pad}at: {location} ")?;
+        }
+        for (i, (k, v)) in self.context().render_items().into_iter().enumerate() {
+            writeln!(f, "{pad}{}. {k}: {v} ", i + 1)?;
         }
-        for (i, (k, v)) in self.context().items.iter().enumerate() {
-            writeln!(f, "{}. {k}: {v} ", i + 1)?;
+        for child in &self.children {
+            child.fmt_indented(f, depth + 1)?;
         }
         Ok(())
     }
 }
+/// 上下文键：字面量与常用键以借用形式保存，避免每次记录都分配
+pub type ContextKey = Cow<'static, str>;
+
+/// 常用上下文键的驻留表，命中时直接借用静态字符串
+const INTERNED_KEYS: &[&str] = &[
+    "path", "file", "key", "url", "status", "method", "host", "port", "addr", "user_id", "id",
+    "name", "step", "table", "count", "value", "type", "reason", "error",
+];
+
+fn intern_key(key: &str) -> Option<&'static str> {
+    INTERNED_KEYS.iter().copied().find(|k| *k == key)
+}
+
+/// 可转换为上下文键的类型；需要零分配的自定义键可直接传入 `Cow::Borrowed`
+pub trait IntoContextKey {
+    fn into_context_key(self) -> ContextKey;
+}
+
+impl IntoContextKey for &str {
+    fn into_context_key(self) -> ContextKey {
+        match intern_key(self) {
+            Some(k) => Cow::Borrowed(k),
+            None => Cow::Owned(self.to_string()),
+        }
+    }
+}
+
+impl IntoContextKey for String {
+    fn into_context_key(self) -> ContextKey {
+        match intern_key(&self) {
+            Some(k) => Cow::Borrowed(k),
+            None => Cow::Owned(self),
+        }
+    }
+}
+
+impl IntoContextKey for &String {
+    fn into_context_key(self) -> ContextKey {
+        self.as_str().into_context_key()
+    }
+}
+
+impl IntoContextKey for Cow<'static, str> {
+    fn into_context_key(self) -> ContextKey {
+        self
+    }
+}
+
+/// 统一的上下文记录接口，`OperationContext`、`OperationScope` 与 `StructError` 均实现，
+/// 接受相同的 `record(key, value)` 调用
 pub trait ContextRecord<S1, S2> {
     fn record(&mut self, key: S1, val: S2);
 }
 
+/// 基于 `ContextRecord` 的常用记录方式，所有记录目标自动获得
+pub trait ContextRecordExt: ContextRecord<ContextKey, String> {
+    /// 记录路径，按全局 `PathDisplay` 渲染
+    fn record_path<K: IntoContextKey, P: AsRef<Path>>(&mut self, key: K, path: P) {
+        self.record(key.into_context_key(), render_path(path.as_ref()));
+    }
+
+    /// 以 `{:?}` 格式记录，适用于未实现 `Display` 的值
+    fn record_debug<K: IntoContextKey, V: std::fmt::Debug + ?Sized>(&mut self, key: K, val: &V) {
+        self.record(key.into_context_key(), format!("{val:?}"));
+    }
+}
+
+impl<T: ContextRecord<ContextKey, String> + ?Sized> ContextRecordExt for T {}
+
 impl<S1> ContextRecord<S1, String> for OperationContext
 where
-    S1: Into<String>,
+    S1: IntoContextKey,
 {
     fn record(&mut self, key: S1, val: String) {
-        self.context.items.push((key.into(), val));
+        self.context.push_item(key.into_context_key(), val);
     }
 }
 
 impl<S1> ContextRecord<S1, &str> for OperationContext
 where
-    S1: Into<String>,
+    S1: IntoContextKey,
 {
     fn record(&mut self, key: S1, val: &str) {
-        self.context.items.push((key.into(), val.into()));
+        self.context.push_item(key.into_context_key(), val.into());
+    }
+}
+
+/// 将任意 `Display` 值记录为上下文，例如 `ctx.record("addr", DisplayValue(addr))`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DisplayValue<T: Display>(pub T);
+
+impl<S1, T> ContextRecord<S1, DisplayValue<T>> for OperationContext
+where
+    S1: IntoContextKey,
+    T: Display,
+{
+    fn record(&mut self, key: S1, val: DisplayValue<T>) {
+        self.context
+            .push_item(key.into_context_key(), val.0.to_string());
     }
 }
 
+impl<S1> ContextRecord<S1, Duration> for OperationContext
+where
+    S1: IntoContextKey,
+{
+    fn record(&mut self, key: S1, val: Duration) {
+        self.context
+            .push_item(key.into_context_key(), format!("{val:?}"));
+    }
+}
+
+/// 数值与布尔类型直接记录，无需 `.to_string()`
+macro_rules! impl_record_display {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl<S1> ContextRecord<S1, $ty> for OperationContext
+            where
+                S1: IntoContextKey,
+            {
+                fn record(&mut self, key: S1, val: $ty) {
+                    self.context.push_item(key.into_context_key(), val.to_string());
+                }
+            }
+
+            impl<K: IntoContextKey> ContextAdd<(K, $ty)> for OperationContext {
+                fn add_context(&mut self, val: (K, $ty)) {
+                    self.record(val.0, val.1);
+                }
+            }
+
+            impl<K: IntoContextKey> From<(K, $ty)> for OperationContext {
+                fn from(val: (K, $ty)) -> Self {
+                    let mut ctx = OperationContext::default();
+                    ctx.record(val.0, val.1);
+                    ctx
+                }
+            }
+        )*
+    };
+}
+
+impl_record_display!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, bool, char,
+);
+
 // Wrapper type for path values to avoid conflicts
 
 impl<S1> ContextRecord<S1, &PathBuf> for OperationContext
 where
-    S1: Into<String>,
+    S1: IntoContextKey,
 {
     fn record(&mut self, key: S1, val: &PathBuf) {
         self.context
-            .items
-            .push((key.into(), format!("{}", val.display())));
+            .push_item(key.into_context_key(), render_path(val));
     }
 }
 impl<S1> ContextRecord<S1, &Path> for OperationContext
 where
-    S1: Into<String>,
+    S1: IntoContextKey,
 {
     fn record(&mut self, key: S1, val: &Path) {
         self.context
-            .items
-            .push((key.into(), format!("{}", val.display())));
+            .push_item(key.into_context_key(), render_path(val));
     }
 }
 
@@ -186,6 +574,79 @@ impl OperationContext {
         &self.target
     }
 
+    /// 附加到错误时的调用位置
+    pub fn location(&self) -> Option<&str> {
+        self.location.as_deref()
+    }
+
+    pub(crate) fn set_location(&mut self, location: String) {
+        self.location = Some(location);
+    }
+
+    /// 稳定渲染用的副本：调用位置替换为占位符，且不触发退出日志
+    pub(crate) fn stable_copy(&self) -> OperationContext {
+        let mut copy = self.clone();
+        copy.exit_log = false;
+        if copy.location.is_some() {
+            copy.location = Some("<location>".into());
+        }
+        copy
+    }
+
+    pub fn severity(&self) -> Option<Severity> {
+        self.severity
+    }
+
+    /// 查找上下文键对应的值
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.context.get(key)
+    }
+
+    /// 合并另一个上下文：同名键以 `other` 的值为准，目标仅在自身未设置时采用，子上下文追加
+    pub fn merge(&mut self, other: &OperationContext) {
+        if self.target.is_none() {
+            self.target = other.target.clone();
+        }
+        for (k, v) in &other.context.items {
+            self.context.set(k.clone(), v.clone());
+        }
+        self.children.extend(other.children.iter().cloned());
+    }
+
+    /// 子步骤上下文
+    pub fn children(&self) -> &[OperationContext] {
+        &self.children
+    }
+
+    /// 本帧及子步骤的条目展平为映射；子步骤中与已有键重复的键加上路径前缀，如 `read file.path`
+    pub fn to_map(&self) -> BTreeMap<String, String> {
+        let mut map = BTreeMap::new();
+        flatten_frames(std::slice::from_ref(self), "", &mut map);
+        map
+    }
+
+    /// 新建子步骤上下文并返回其可变引用，用于记录多阶段操作的细节
+    pub fn child<S: Into<String>>(&mut self, target: S) -> &mut OperationContext {
+        let child = OperationContext::want(target).with_mod_path(self.mod_path.clone());
+        self.add_child(child)
+    }
+
+    /// 挂载已有的上下文作为子步骤
+    pub fn add_child(&mut self, child: OperationContext) -> &mut OperationContext {
+        self.children.push(child);
+        self.children.last_mut().expect("child just pushed")
+    }
+
+    /// 替换或插入上下文键值
+    pub fn set<K: IntoContextKey, V: Into<String>>(&mut self, key: K, val: V) {
+        self.context.set(key, val)
+    }
+
+    /// 删除上下文键，返回最后一次的值
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        self.context.remove(key)
+    }
+
     pub fn new() -> Self {
         Self {
             target: None,
@@ -193,8 +654,38 @@ impl OperationContext {
             result: OperationResult::Fail,
             exit_log: false,
             mod_path: DEFAULT_MOD_PATH.into(),
+            severity: None,
+            children: Vec::new(),
+            failure: None,
+            location: None,
         }
     }
+    /// 生成可跨线程/任务移动的上下文句柄，保留目标、条目与模块路径
+    pub fn propagate(&self) -> ContextHandle {
+        ContextHandle(Arc::new(ContextSnapshot {
+            target: self.target.clone(),
+            context: self.context.clone(),
+            mod_path: self.mod_path.clone(),
+            severity: self.severity,
+        }))
+    }
+
+    /// 由句柄还原上下文；还原出的上下文不开启自动日志，避免与源上下文重复输出
+    pub fn adopt(handle: ContextHandle) -> Self {
+        let snap = Arc::unwrap_or_clone(handle.0);
+        Self {
+            target: snap.target,
+            context: snap.context,
+            result: OperationResult::Fail,
+            exit_log: false,
+            mod_path: snap.mod_path,
+            severity: snap.severity,
+            children: Vec::new(),
+            failure: None,
+            location: None,
+        }
+    }
+
     pub fn want<S: Into<String>>(target: S) -> Self {
         Self {
             target: Some(target.into()),
@@ -202,31 +693,58 @@ impl OperationContext {
             result: OperationResult::Fail,
             exit_log: false,
             mod_path: DEFAULT_MOD_PATH.into(),
+            severity: None,
+            children: Vec::new(),
+            failure: None,
+            location: None,
         }
     }
     #[deprecated(since = "0.5.4", note = "use with_auto_log")]
+    #[must_use]
     pub fn with_exit_log(mut self) -> Self {
         self.exit_log = true;
         self
     }
+    #[must_use]
     pub fn with_auto_log(mut self) -> Self {
         self.exit_log = true;
         self
     }
+    #[must_use]
     pub fn with_mod_path<S: Into<String>>(mut self, path: S) -> Self {
         self.mod_path = path.into();
         self
     }
+    /// 记录进程信息：`pid`、`thread`（线程名，匿名线程为 ID）与 `host`
+    #[must_use]
+    pub fn with_process_info(mut self) -> Self {
+        #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+        self.record("pid", std::process::id());
+        let current = std::thread::current();
+        match current.name() {
+            Some(name) => self.record("thread", name),
+            None => self.record("thread", format!("{:?}", current.id())),
+        }
+        if let Some(host) = host_name() {
+            self.record("host", host);
+        }
+        self
+    }
     #[deprecated(since = "0.5.4", note = "use record")]
-    pub fn with<S1: Into<String>, S2: Into<String>>(&mut self, key: S1, val: S2) {
-        self.context.items.push((key.into(), val.into()));
+    pub fn with<S1: IntoContextKey, S2: Into<String>>(&mut self, key: S1, val: S2) {
+        self.context.push_item(key.into_context_key(), val.into());
     }
 
     #[deprecated(since = "0.5.4", note = "use record")]
-    pub fn with_path<S1: Into<String>, S2: Into<PathBuf>>(&mut self, key: S1, val: S2) {
+    pub fn with_path<S1: IntoContextKey, S2: Into<PathBuf>>(&mut self, key: S1, val: S2) {
         self.context
-            .items
-            .push((key.into(), format!("{}", val.into().display())));
+            .push_item(key.into_context_key(), render_path(&val.into()));
+    }
+
+    /// 记录敏感值，无论是否注册敏感键都以脱敏形式保存
+    pub fn with_secret<S1: IntoContextKey, S2: Into<String>>(&mut self, key: S1, _val: S2) {
+        self.context
+            .push_item(key.into_context_key(), REDACTED.to_string());
     }
 
     pub fn with_want<S: Into<String>>(&mut self, target: S) {
@@ -242,9 +760,60 @@ impl OperationContext {
     pub fn mark_cancel(&mut self) {
         self.result = OperationResult::Cancel;
     }
+    /// 设置失败时退出日志的严重级别
+    pub fn set_severity(&mut self, severity: Severity) {
+        self.severity = Some(severity);
+    }
+    /// 标记失败，并按错误原因的严重级别输出退出日志
+    pub fn mark_fail_by<R: SeverityOf>(&mut self, reason: &R) {
+        self.result = OperationResult::Fail;
+        self.severity = Some(reason.severity());
+    }
+
+    /// 标记失败，并记下错误码与类别；启用 `log-kv` 时退出日志以 `code` / `category` 键输出
+    pub fn mark_fail_with<R: SeverityOf + ErrorCode + AsUvsReason>(&mut self, reason: &R) {
+        self.mark_fail_by(reason);
+        self.failure = Some((
+            reason.error_code().to_string(),
+            reason.as_uvs().map(UvsReason::category_name),
+        ));
+    }
+
+    #[cfg(all(feature = "log", not(feature = "tracing")))]
+    fn log_message(&self, level: log::Level, message: &str) {
+        #[cfg(feature = "log-kv")]
+        log_kv(
+            level,
+            &self.mod_path,
+            format_args!("{message}"),
+            &self.kv_items(),
+        );
+        #[cfg(not(feature = "log-kv"))]
+        log::log!(target: self.mod_path.as_str(), level, "{}: {}", self.format_context(), message);
+    }
+
+    /// 结构化日志的键值：`target`、失败时的 `code` / `category` 与全部上下文条目
+    #[cfg(all(feature = "log-kv", not(feature = "tracing")))]
+    fn kv_items(&self) -> Vec<(&str, &str)> {
+        let mut kvs = Vec::new();
+        if let Some(target) = &self.target {
+            kvs.push(("target", target.as_str()));
+        }
+        if let (OperationResult::Fail, Some((code, category))) = (&self.result, &self.failure) {
+            kvs.push(("code", code.as_str()));
+            if let Some(category) = category {
+                kvs.push(("category", *category));
+            }
+        }
+        kvs.extend(self.context.render_items());
+        kvs
+    }
 
     /// 格式化上下文信息，用于日志输出
-    #[cfg_attr(not(any(feature = "log", feature = "tracing")), allow(dead_code))]
+    #[cfg_attr(
+        not(any(all(feature = "log", not(feature = "log-kv")), feature = "tracing")),
+        allow(dead_code)
+    )]
     fn format_context(&self) -> String {
         let target = self.target.clone().unwrap_or_default();
         if self.context.items.is_empty() {
@@ -288,7 +857,7 @@ impl OperationContext {
     }
     #[cfg(all(feature = "log", not(feature = "tracing")))]
     pub fn info<S: AsRef<str>>(&self, message: S) {
-        info!(target: self.mod_path.as_str(), "{}: {}", self.format_context(), message.as_ref());
+        self.log_message(log::Level::Info, message.as_ref());
     }
     #[cfg(not(any(feature = "log", feature = "tracing")))]
     pub fn info<S: AsRef<str>>(&self, _message: S) {}
@@ -305,7 +874,7 @@ impl OperationContext {
     }
     #[cfg(all(feature = "log", not(feature = "tracing")))]
     pub fn debug<S: AsRef<str>>(&self, message: S) {
-        debug!( target: self.mod_path.as_str(), "{}: {}", self.format_context(), message.as_ref());
+        self.log_message(log::Level::Debug, message.as_ref());
     }
     #[cfg(not(any(feature = "log", feature = "tracing")))]
     pub fn debug<S: AsRef<str>>(&self, _message: S) {}
@@ -322,7 +891,7 @@ impl OperationContext {
     }
     #[cfg(all(feature = "log", not(feature = "tracing")))]
     pub fn warn<S: AsRef<str>>(&self, message: S) {
-        warn!( target: self.mod_path.as_str(), "{}: {}", self.format_context(), message.as_ref());
+        self.log_message(log::Level::Warn, message.as_ref());
     }
     #[cfg(not(any(feature = "log", feature = "tracing")))]
     pub fn warn<S: AsRef<str>>(&self, _message: S) {}
@@ -339,7 +908,7 @@ impl OperationContext {
     }
     #[cfg(all(feature = "log", not(feature = "tracing")))]
     pub fn error<S: AsRef<str>>(&self, message: S) {
-        error!(target: self.mod_path.as_str(), "{}: {}", self.format_context(), message.as_ref());
+        self.log_message(log::Level::Error, message.as_ref());
     }
     #[cfg(not(any(feature = "log", feature = "tracing")))]
     pub fn error<S: AsRef<str>>(&self, _message: S) {}
@@ -356,7 +925,7 @@ impl OperationContext {
     }
     #[cfg(all(feature = "log", not(feature = "tracing")))]
     pub fn trace<S: AsRef<str>>(&self, message: S) {
-        trace!( target: self.mod_path.as_str(), "{}: {}", self.format_context(), message.as_ref());
+        self.log_message(log::Level::Trace, message.as_ref());
     }
     #[cfg(not(any(feature = "log", feature = "tracing")))]
     pub fn trace<S: AsRef<str>>(&self, _message: S) {}
@@ -379,6 +948,34 @@ impl OperationContext {
     }
 }
 
+/// 展平多个上下文帧：先出现的键保持原名，之后的帧中重复的键加上帧前缀（目标名，无目标时为序号）；
+/// 同一帧内重复记录的键以最后的值为准
+pub(crate) fn flatten_frames(
+    frames: &[OperationContext],
+    path: &str,
+    map: &mut BTreeMap<String, String>,
+) {
+    for (i, ctx) in frames.iter().enumerate() {
+        let label = ctx.target.clone().unwrap_or_else(|| i.to_string());
+        let prefix = if path.is_empty() {
+            label
+        } else {
+            format!("{path}.{label}")
+        };
+        let mut own: Vec<(&str, String)> = Vec::new();
+        for (k, v) in ctx.context.render_items() {
+            let key = match own.iter().find(|(ok, _)| *ok == k) {
+                Some((_, key)) => key.clone(),
+                None if map.contains_key(k) => format!("{prefix}.{k}"),
+                None => k.to_string(),
+            };
+            map.insert(key.clone(), v.to_string());
+            own.push((k, key));
+        }
+        flatten_frames(&ctx.children, &prefix, map);
+    }
+}
+
 pub struct OperationScope<'a> {
     ctx: &'a mut OperationContext,
     mark_success: bool,
@@ -402,6 +999,15 @@ impl<'a> OperationScope<'a> {
     }
 }
 
+impl<S1, S2> ContextRecord<S1, S2> for OperationScope<'_>
+where
+    OperationContext: ContextRecord<S1, S2>,
+{
+    fn record(&mut self, key: S1, val: S2) {
+        self.ctx.record(key, val);
+    }
+}
+
 impl<'a> Deref for OperationScope<'a> {
     type Target = OperationContext;
 
@@ -421,6 +1027,7 @@ impl Drop for OperationScope<'_> {
         if self.mark_success {
             self.ctx.mark_suc();
         }
+        stats::record(self.ctx.target.as_deref(), &self.ctx.result);
     }
 }
 
@@ -432,6 +1039,10 @@ impl From<String> for OperationContext {
             result: OperationResult::Fail,
             exit_log: false,
             mod_path: DEFAULT_MOD_PATH.into(),
+            severity: None,
+            children: Vec::new(),
+            failure: None,
+            location: None,
         }
     }
 }
@@ -440,10 +1051,14 @@ impl From<&PathBuf> for OperationContext {
     fn from(value: &PathBuf) -> Self {
         Self {
             target: None,
-            context: CallContext::from(("path", format!("{}", value.display()))),
+            context: CallContext::from(("path", render_path(value))),
             result: OperationResult::Fail,
             exit_log: false,
             mod_path: DEFAULT_MOD_PATH.into(),
+            severity: None,
+            children: Vec::new(),
+            failure: None,
+            location: None,
         }
     }
 }
@@ -452,10 +1067,14 @@ impl From<&Path> for OperationContext {
     fn from(value: &Path) -> Self {
         Self {
             target: None,
-            context: CallContext::from(("path", format!("{}", value.display()))),
+            context: CallContext::from(("path", render_path(value))),
             result: OperationResult::Fail,
             exit_log: false,
             mod_path: DEFAULT_MOD_PATH.into(),
+            severity: None,
+            children: Vec::new(),
+            failure: None,
+            location: None,
         }
     }
 }
@@ -468,6 +1087,10 @@ impl From<&str> for OperationContext {
             result: OperationResult::Fail,
             exit_log: false,
             mod_path: DEFAULT_MOD_PATH.into(),
+            severity: None,
+            children: Vec::new(),
+            failure: None,
+            location: None,
         }
     }
 }
@@ -480,6 +1103,10 @@ impl From<(&str, &str)> for OperationContext {
             result: OperationResult::Fail,
             exit_log: false,
             mod_path: DEFAULT_MOD_PATH.into(),
+            severity: None,
+            children: Vec::new(),
+            failure: None,
+            location: None,
         }
     }
 }
@@ -492,6 +1119,10 @@ impl From<(&str, String)> for OperationContext {
             result: OperationResult::Fail,
             exit_log: false,
             mod_path: DEFAULT_MOD_PATH.into(),
+            severity: None,
+            children: Vec::new(),
+            failure: None,
+            location: None,
         }
     }
 }
@@ -510,15 +1141,14 @@ where
     fn from(value: (&str, V)) -> Self {
         Self {
             target: None,
-            context: CallContext {
-                items: vec![(
-                    value.0.to_string(),
-                    format!("{}", value.1.as_ref().display()),
-                )],
-            },
+            context: CallContext::from((value.0, render_path(value.1.as_ref()))),
             result: OperationResult::Fail,
             exit_log: false,
             mod_path: DEFAULT_MOD_PATH.into(),
+            severity: None,
+            children: Vec::new(),
+            failure: None,
+            location: None,
         }
     }
 }
@@ -531,6 +1161,10 @@ impl From<(String, String)> for OperationContext {
             result: OperationResult::Fail,
             exit_log: false,
             mod_path: DEFAULT_MOD_PATH.into(),
+            severity: None,
+            children: Vec::new(),
+            failure: None,
+            location: None,
         }
     }
 }
@@ -541,48 +1175,221 @@ impl From<&OperationContext> for OperationContext {
     }
 }
 
+/// 主机名：优先取环境变量，其次读取 `/etc/hostname`，结果进程内缓存
+fn host_name() -> Option<&'static str> {
+    static HOST: OnceLock<Option<String>> = OnceLock::new();
+    HOST.get_or_init(|| {
+        ["HOSTNAME", "COMPUTERNAME"]
+            .iter()
+            .find_map(|k| std::env::var(k).ok())
+            .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+            .map(|h| h.trim().to_string())
+            .filter(|h| !h.is_empty())
+    })
+    .as_deref()
+}
+
+/// `OperationContext::propagate` 生成的只读快照，克隆仅增加引用计数
+#[derive(Debug, Clone)]
+pub struct ContextHandle(Arc<ContextSnapshot>);
+
+#[derive(Debug, Clone)]
+struct ContextSnapshot {
+    target: Option<String>,
+    context: CallContext,
+    mod_path: String,
+    severity: Option<Severity>,
+}
+
+impl ContextHandle {
+    pub fn target(&self) -> Option<&str> {
+        self.0.target.as_deref()
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.0.context.get(key)
+    }
+}
+
+/// 上下文条目；多数帧只有 0–3 个条目，内联存放，超出后才分配堆内存
+pub type ContextItems = SmallVec<[(ContextKey, String); 3]>;
+
 #[derive(Default, Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CallContext {
-    pub items: Vec<(String, String)>,
+    #[cfg_attr(feature = "serde", serde(rename = "items"))]
+    pub items: ContextItems,
 }
 
 impl<K: AsRef<str>, V: AsRef<str>> From<(K, V)> for CallContext {
     fn from(value: (K, V)) -> Self {
-        Self {
-            items: vec![(value.0.as_ref().to_string(), value.1.as_ref().to_string())],
+        let mut ctx = Self::default();
+        ctx.push_item(
+            value.0.as_ref().into_context_key(),
+            value.1.as_ref().to_string(),
+        );
+        ctx
+    }
+}
+
+impl CallContext {
+    /// 统一的写入入口：命中敏感键时脱敏，并应用全局体积限制
+    pub(crate) fn push_item(&mut self, key: ContextKey, val: String) {
+        let val = if is_sensitive_key(&key) {
+            REDACTED.to_string()
+        } else {
+            limit_value(val)
+        };
+        if let Some(max) = error_limits().max_context_items {
+            let real = self
+                .items
+                .iter()
+                .filter(|(k, _)| k != TRUNCATED_KEY)
+                .count();
+            if real >= max {
+                self.bump_truncated();
+                return;
+            }
+        }
+        self.items.push((key, val));
+    }
+
+    /// 追加另一个上下文的全部条目（保留重复键）
+    pub fn extend_from(&mut self, other: &CallContext) {
+        for (k, v) in &other.items {
+            self.push_item(k.clone(), v.clone());
         }
     }
+
+    /// 查找键对应的值；重复记录时返回最后一次的值
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.items
+            .iter()
+            .rev()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// 替换已有键的值（重复键合并为一条），不存在时追加
+    pub fn set<K: IntoContextKey, V: Into<String>>(&mut self, key: K, val: V) {
+        let key = key.into_context_key();
+        match self.items.iter().position(|(k, _)| *k == key) {
+            Some(idx) => {
+                let val = if is_sensitive_key(&key) {
+                    REDACTED.to_string()
+                } else {
+                    limit_value(val.into())
+                };
+                self.items[idx].1 = val;
+                let mut i = 0;
+                self.items.retain(|(k, _)| {
+                    i += 1;
+                    i - 1 == idx || *k != key
+                });
+            }
+            None => self.push_item(key, val.into()),
+        }
+    }
+
+    /// 删除键的所有记录，返回最后一次的值
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        let last = self.get(key).map(str::to_string);
+        self.items.retain(|(k, _)| k != key);
+        last
+    }
+
+    /// 渲染用条目：开启 `set_context_dedup` 时同名键保留首次出现的位置、显示最后的值
+    pub(crate) fn render_items(&self) -> Vec<(&str, &str)> {
+        if !context_dedup() {
+            return self
+                .items
+                .iter()
+                .map(|(k, v)| (k.as_ref(), v.as_str()))
+                .collect();
+        }
+        let mut out: Vec<(&str, &str)> = Vec::with_capacity(self.items.len());
+        for (k, v) in &self.items {
+            match out.iter_mut().find(|(ok, _)| *ok == k.as_ref()) {
+                Some(slot) => slot.1 = v.as_str(),
+                None => out.push((k.as_ref(), v.as_str())),
+            }
+        }
+        out
+    }
+
+    fn bump_truncated(&mut self) {
+        if let Some((k, v)) = self.items.last_mut() {
+            if k == TRUNCATED_KEY {
+                let count = v
+                    .strip_prefix("(truncated, ")
+                    .and_then(|x| x.strip_suffix(" items)"))
+                    .and_then(|x| x.parse::<usize>().ok())
+                    .unwrap_or(0);
+                *v = truncated_items_marker(count + 1);
+                return;
+            }
+        }
+        self.items
+            .push((Cow::Borrowed(TRUNCATED_KEY), truncated_items_marker(1)));
+    }
 }
 
 pub trait ContextAdd<T> {
     fn add_context(&mut self, val: T);
 }
 
-impl<K: Into<String>> ContextAdd<(K, String)> for OperationContext {
+impl<K: IntoContextKey> ContextAdd<(K, String)> for OperationContext {
     fn add_context(&mut self, val: (K, String)) {
-        self.record(val.0.into(), val.1);
+        self.record(val.0, val.1);
     }
 }
-impl<K: Into<String>> ContextAdd<(K, &String)> for OperationContext {
+impl<K: IntoContextKey> ContextAdd<(K, &String)> for OperationContext {
     fn add_context(&mut self, val: (K, &String)) {
-        self.record(val.0.into(), val.1.clone());
+        self.record(val.0, val.1.clone());
     }
 }
-impl<K: Into<String>> ContextAdd<(K, &str)> for OperationContext {
+impl<K: IntoContextKey> ContextAdd<(K, &str)> for OperationContext {
     fn add_context(&mut self, val: (K, &str)) {
-        self.record(val.0.into(), val.1.to_string());
+        self.record(val.0, val.1.to_string());
     }
 }
 
-impl<K: Into<String>> ContextAdd<(K, &PathBuf)> for OperationContext {
+impl<K: IntoContextKey> ContextAdd<(K, &PathBuf)> for OperationContext {
     fn add_context(&mut self, val: (K, &PathBuf)) {
-        self.record(val.0.into(), format!("{}", val.1.display()));
+        self.record(val.0, render_path(val.1));
+    }
+}
+impl<K: IntoContextKey> ContextAdd<(K, Duration)> for OperationContext {
+    fn add_context(&mut self, val: (K, Duration)) {
+        self.record(val.0, val.1);
     }
 }
-impl<K: Into<String>> ContextAdd<(K, &Path)> for OperationContext {
+
+impl<K: IntoContextKey, T: Display> ContextAdd<(K, DisplayValue<T>)> for OperationContext {
+    fn add_context(&mut self, val: (K, DisplayValue<T>)) {
+        self.record(val.0, val.1);
+    }
+}
+
+impl<K: IntoContextKey> From<(K, Duration)> for OperationContext {
+    fn from(val: (K, Duration)) -> Self {
+        let mut ctx = OperationContext::default();
+        ctx.record(val.0, val.1);
+        ctx
+    }
+}
+
+impl<K: IntoContextKey, T: Display> From<(K, DisplayValue<T>)> for OperationContext {
+    fn from(val: (K, DisplayValue<T>)) -> Self {
+        let mut ctx = OperationContext::default();
+        ctx.record(val.0, val.1);
+        ctx
+    }
+}
+
+impl<K: IntoContextKey> ContextAdd<(K, &Path)> for OperationContext {
     fn add_context(&mut self, val: (K, &Path)) {
-        self.record(val.0.into(), format!("{}", val.1.display()));
+        self.record(val.0, render_path(val.1));
     }
 }
 
@@ -591,7 +1398,7 @@ impl Display for CallContext {
         if !self.items.is_empty() {
             writeln!(f, "\ncall context:")?;
         }
-        for (k, v) in &self.items {
+        for (k, v) in self.render_items() {
             writeln!(f, "\t{k} : {v}")?;
         }
         Ok(())
@@ -617,6 +1424,178 @@ mod tests {
         assert_eq!(ctx.context().items.len(), 0);
     }
 
+    #[test]
+    fn test_context_get_set_remove() {
+        let mut ctx = OperationContext::want("upload");
+        ctx.record("file", "a.txt");
+        ctx.record("size", "1");
+        ctx.record("file", "b.txt");
+        assert_eq!(ctx.get("file"), Some("b.txt"));
+
+        ctx.set("file", "c.txt");
+        assert_eq!(
+            ctx.context().items.as_slice(),
+            [
+                ("file".into(), "c.txt".to_string()),
+                ("size".into(), "1".to_string())
+            ]
+        );
+        ctx.set("user", "alice");
+        assert_eq!(ctx.get("user"), Some("alice"));
+
+        assert_eq!(ctx.remove("size"), Some("1".to_string()));
+        assert_eq!(ctx.remove("size"), None);
+        assert_eq!(ctx.context().items.len(), 2);
+    }
+
+    #[test]
+    fn test_child_context_renders_indented() {
+        let mut ctx = OperationContext::want("deploy");
+        ctx.record("env", "prod");
+        ctx.child("build").record("step", "compile");
+        ctx.child("upload").child("retry").record("attempt", "2");
+        assert_eq!(ctx.children().len(), 2);
+        assert_eq!(
+            ctx.to_string(),
+            "target: deploy \n1. env: prod \n  target: build \n  1. step: compile \n  target: upload \n    target: retry \n    1. attempt: 2 \n"
+        );
+    }
+
+    #[test]
+    fn test_record_numeric_and_display_values() {
+        let mut ctx = OperationContext::new();
+        ctx.record("count", 3_u32);
+        ctx.record("ratio", 0.5_f64);
+        ctx.record("ok", true);
+        ctx.record("elapsed", Duration::from_millis(1500));
+        ctx.record("addr", DisplayValue(std::net::Ipv4Addr::LOCALHOST));
+        ctx.add_context(("retries", -1_i64));
+        assert_eq!(ctx.get("count"), Some("3"));
+        assert_eq!(ctx.get("ratio"), Some("0.5"));
+        assert_eq!(ctx.get("ok"), Some("true"));
+        assert_eq!(ctx.get("elapsed"), Some("1.5s"));
+        assert_eq!(ctx.get("addr"), Some("127.0.0.1"));
+        assert_eq!(ctx.get("retries"), Some("-1"));
+    }
+
+    #[test]
+    fn test_error_with_numeric_and_display_values() {
+        use crate::{ErrorWith, StructError};
+
+        let err = StructError::from(UvsReason::data_error())
+            .with(("count", 3))
+            .with(("ok", false))
+            .with(("elapsed", Duration::from_millis(1500)))
+            .with(("addr", DisplayValue(std::net::Ipv4Addr::LOCALHOST)));
+        let values: Vec<_> = err
+            .contexts()
+            .iter()
+            .map(|ctx| ctx.context().items[0].1.as_str())
+            .collect();
+        assert_eq!(values, ["3", "false", "1.5s", "127.0.0.1"]);
+    }
+
+    #[test]
+    fn test_context_merge() {
+        let mut request = OperationContext::new();
+        request.record("request_id", "r-1");
+        request.record("user", "alice");
+
+        let mut op = OperationContext::want("create_order");
+        op.record("user", "bob");
+        op.merge(&request);
+        assert_eq!(op.target().as_deref(), Some("create_order"));
+        assert_eq!(op.get("user"), Some("alice"));
+        assert_eq!(op.get("request_id"), Some("r-1"));
+        assert_eq!(op.context().items.len(), 2);
+
+        let mut call = CallContext::from(("a", "1"));
+        call.extend_from(&CallContext::from(("a", "2")));
+        assert_eq!(call.items.len(), 2);
+    }
+
+    #[test]
+    fn test_propagate_and_adopt_across_thread() {
+        let mut ctx = OperationContext::want("handle request").with_auto_log();
+        ctx.record("request_id", "r-42");
+        let handle = ctx.propagate();
+        assert_eq!(handle.target(), Some("handle request"));
+
+        let adopted = std::thread::spawn(move || {
+            let mut bg = OperationContext::adopt(handle);
+            bg.record("job", "reindex");
+            bg
+        })
+        .join()
+        .unwrap();
+
+        assert_eq!(adopted.target().as_deref(), Some("handle request"));
+        assert_eq!(adopted.get("request_id"), Some("r-42"));
+        assert_eq!(adopted.get("job"), Some("reindex"));
+        assert!(!adopted.exit_log());
+        assert_eq!(ctx.get("job"), None);
+        ctx.mark_suc();
+    }
+
+    #[test]
+    fn test_with_process_info() {
+        let ctx = std::thread::Builder::new()
+            .name("worker-1".into())
+            .spawn(|| OperationContext::want("sync").with_process_info())
+            .unwrap()
+            .join()
+            .unwrap();
+        assert_eq!(
+            ctx.get("pid"),
+            Some(std::process::id().to_string().as_str())
+        );
+        assert_eq!(ctx.get("thread"), Some("worker-1"));
+
+        let anon = std::thread::spawn(|| OperationContext::new().with_process_info())
+            .join()
+            .unwrap();
+        assert!(anon.get("thread").unwrap().starts_with("ThreadId("));
+    }
+
+    #[test]
+    fn test_context_key_interning() {
+        assert!(matches!("path".into_context_key(), Cow::Borrowed("path")));
+        assert!(matches!(
+            "user_id".to_string().into_context_key(),
+            Cow::Borrowed(_)
+        ));
+        assert!(matches!("custom".into_context_key(), Cow::Owned(_)));
+
+        let mut ctx = OperationContext::new();
+        ctx.record(Cow::Borrowed("tenant"), "t1");
+        ctx.record("url", "http://x");
+        assert!(ctx
+            .context()
+            .items
+            .iter()
+            .all(|(k, _)| matches!(k, Cow::Borrowed(_))));
+        assert_eq!(ctx.get("tenant"), Some("t1"));
+    }
+
+    #[test]
+    fn test_mark_fail_by_sets_severity() {
+        let mut ctx = OperationContext::want("sync");
+        assert_eq!(ctx.severity(), None);
+        ctx.mark_fail_by(&crate::UvsReason::network_error());
+        assert_eq!(ctx.result(), &OperationResult::Fail);
+        assert_eq!(ctx.severity(), Some(Severity::Warn));
+        ctx.set_severity(Severity::Critical);
+        assert_eq!(ctx.severity(), Some(Severity::Critical));
+    }
+
+    #[test]
+    fn test_mark_fail_with_records_code() {
+        let mut ctx = OperationContext::want("sync");
+        ctx.mark_fail_with(&crate::UvsReason::timeout_error());
+        assert_eq!(ctx.severity(), Some(Severity::Warn));
+        assert_eq!(ctx.failure, Some(("204".to_string(), Some("timeout"))));
+    }
+
     #[test]
     fn test_withcontext_want() {
         let ctx = OperationContext::want("test_target");
@@ -633,11 +1612,11 @@ mod tests {
         assert_eq!(ctx.context().items.len(), 2);
         assert_eq!(
             ctx.context().items[0],
-            ("key1".to_string(), "value1".to_string())
+            ("key1".into(), "value1".to_string())
         );
         assert_eq!(
             ctx.context().items[1],
-            ("key2".to_string(), "value2".to_string())
+            ("key2".into(), "value2".to_string())
         );
     }
 
@@ -663,35 +1642,35 @@ mod tests {
     fn test_errcontext_from_string() {
         let ctx = CallContext::from(("key".to_string(), "test_string".to_string()));
         assert_eq!(ctx.items.len(), 1);
-        assert_eq!(ctx.items[0], ("key".to_string(), "test_string".to_string()));
+        assert_eq!(ctx.items[0], ("key".into(), "test_string".to_string()));
     }
 
     #[test]
     fn test_errcontext_from_str() {
         let ctx = CallContext::from(("key", "test_str"));
         assert_eq!(ctx.items.len(), 1);
-        assert_eq!(ctx.items[0], ("key".to_string(), "test_str".to_string()));
+        assert_eq!(ctx.items[0], ("key".into(), "test_str".to_string()));
     }
 
     #[test]
     fn test_errcontext_from_string_pair() {
         let ctx = CallContext::from(("key1".to_string(), "value1".to_string()));
         assert_eq!(ctx.items.len(), 1);
-        assert_eq!(ctx.items[0], ("key1".to_string(), "value1".to_string()));
+        assert_eq!(ctx.items[0], ("key1".into(), "value1".to_string()));
     }
 
     #[test]
     fn test_errcontext_from_str_pair() {
         let ctx = CallContext::from(("key1", "value1"));
         assert_eq!(ctx.items.len(), 1);
-        assert_eq!(ctx.items[0], ("key1".to_string(), "value1".to_string()));
+        assert_eq!(ctx.items[0], ("key1".into(), "value1".to_string()));
     }
 
     #[test]
     fn test_errcontext_from_mixed_pair() {
         let ctx = CallContext::from(("key1", "value1".to_string()));
         assert_eq!(ctx.items.len(), 1);
-        assert_eq!(ctx.items[0], ("key1".to_string(), "value1".to_string()));
+        assert_eq!(ctx.items[0], ("key1".into(), "value1".to_string()));
     }
 
     #[test]
@@ -711,8 +1690,8 @@ mod tests {
     #[test]
     fn test_errcontext_display_multiple() {
         let mut ctx = CallContext::default();
-        ctx.items.push(("key1".to_string(), "value1".to_string()));
-        ctx.items.push(("key2".to_string(), "value2".to_string()));
+        ctx.items.push(("key1".into(), "value1".to_string()));
+        ctx.items.push(("key2".into(), "value2".to_string()));
         let display = format!("{ctx}");
         assert!(display.contains("call context:"));
         assert!(display.contains("key1 : value1"));
@@ -733,7 +1712,7 @@ mod tests {
         assert_eq!(ctx.context().items.len(), 1);
         assert_eq!(
             ctx.context().items[0],
-            ("key".to_string(), "test_string".to_string())
+            ("key".into(), "test_string".to_string())
         );
     }
 
@@ -744,7 +1723,7 @@ mod tests {
         assert_eq!(ctx.context().items.len(), 1);
         assert_eq!(
             ctx.context().items[0],
-            ("key".to_string(), "test_str".to_string())
+            ("key".into(), "test_str".to_string())
         );
     }
 
@@ -773,7 +1752,7 @@ mod tests {
         assert_eq!(ctx.context().items.len(), 1);
         assert_eq!(
             ctx.context().items[0],
-            ("key1".to_string(), "value1".to_string())
+            ("key1".into(), "value1".to_string())
         );
     }
 
@@ -784,7 +1763,7 @@ mod tests {
         assert_eq!(ctx.context().items.len(), 1);
         assert_eq!(
             ctx.context().items[0],
-            ("key1".to_string(), "value1".to_string())
+            ("key1".into(), "value1".to_string())
         );
     }
 
@@ -795,7 +1774,7 @@ mod tests {
         assert_eq!(ctx.context().items.len(), 1);
         assert_eq!(
             ctx.context().items[0],
-            ("key1".to_string(), "value1".to_string())
+            ("key1".into(), "value1".to_string())
         );
     }
 
@@ -835,7 +1814,7 @@ mod tests {
         assert_eq!(ctx.context().items.len(), 1);
         assert_eq!(
             ctx.context().items[0],
-            ("key1".to_string(), "value1".to_string())
+            ("key1".into(), "value1".to_string())
         );
     }
 
@@ -848,7 +1827,7 @@ mod tests {
         assert_eq!(ctx2.context().items.len(), 1);
         assert_eq!(
             ctx2.context().items[0],
-            ("key1".to_string(), "value1".to_string())
+            ("key1".into(), "value1".to_string())
         );
     }
 
@@ -877,11 +1856,11 @@ mod tests {
     fn test_withcontext_edge_cases() {
         let ctx1 = OperationContext::from("".to_string());
         assert_eq!(ctx1.context().items.len(), 1);
-        assert_eq!(ctx1.context().items[0], ("key".to_string(), "".to_string()));
+        assert_eq!(ctx1.context().items[0], ("key".into(), "".to_string()));
 
         let ctx2 = OperationContext::from(("".to_string(), "".to_string()));
         assert_eq!(ctx2.context().items.len(), 1);
-        assert_eq!(ctx2.context().items[0], ("".to_string(), "".to_string()));
+        assert_eq!(ctx2.context().items[0], ("".into(), "".to_string()));
     }
 
     #[test]
@@ -929,7 +1908,7 @@ mod tests {
         // 验证最后一个添加的值
         assert_eq!(
             ctx.context().items[2],
-            ("bool_key".to_string(), "true".to_string())
+            ("bool_key".into(), "true".to_string())
         );
     }
 
@@ -1048,11 +2027,11 @@ mod tests {
         assert_eq!(ctx.context().items.len(), 2);
         assert_eq!(
             ctx.context().items[0],
-            ("key1".to_string(), "value1".to_string())
+            ("key1".into(), "value1".to_string())
         );
         assert_eq!(
             ctx.context().items[1],
-            ("key2".to_string(), "value2".to_string())
+            ("key2".into(), "value2".to_string())
         );
     }
 
@@ -1156,18 +2135,15 @@ mod tests {
         assert_eq!(ctx.context().items.len(), 3);
         assert_eq!(
             ctx.context().items[0],
-            (
-                "key_with_spaces".to_string(),
-                "value with spaces".to_string()
-            )
+            ("key_with_spaces".into(), "value with spaces".to_string())
         );
         assert_eq!(
             ctx.context().items[1],
-            ("key_with_unicode".to_string(), "值包含中文".to_string())
+            ("key_with_unicode".into(), "值包含中文".to_string())
         );
         assert_eq!(
             ctx.context().items[2],
-            ("key_with_symbols".to_string(), "value@#$%^&*()".to_string())
+            ("key_with_symbols".into(), "value@#$%^&*()".to_string())
         );
 
         // 测试显示
@@ -1200,11 +2176,11 @@ mod tests {
         assert_eq!(ctx.context().items.len(), 4);
         assert_eq!(
             ctx.context().items[0],
-            ("key1".to_string(), "value1".to_string())
+            ("key1".into(), "value1".to_string())
         );
         assert_eq!(
             ctx.context().items[3],
-            ("key1".to_string(), "new_value1".to_string())
+            ("key1".into(), "new_value1".to_string())
         );
     }
 
@@ -1214,13 +2190,13 @@ mod tests {
         let ctx1 = OperationContext::from("simple_string");
         assert_eq!(
             ctx1.context().items[0],
-            ("key".to_string(), "simple_string".to_string())
+            ("key".into(), "simple_string".to_string())
         );
 
         let ctx2 = OperationContext::from(("custom_key", "custom_value"));
         assert_eq!(
             ctx2.context().items[0],
-            ("custom_key".to_string(), "custom_value".to_string())
+            ("custom_key".into(), "custom_value".to_string())
         );
 
         let path = PathBuf::from("/test/path/file.txt");
@@ -1243,19 +2219,19 @@ mod tests {
         assert_eq!(ctx.context().items.len(), 4);
         assert_eq!(
             ctx.context().items[0],
-            ("string_key".to_string(), "string_value".to_string())
+            ("string_key".into(), "string_value".to_string())
         );
         assert_eq!(
             ctx.context().items[1],
-            ("string_key2".to_string(), "string_value2".to_string())
+            ("string_key2".into(), "string_value2".to_string())
         );
         assert_eq!(
             ctx.context().items[2],
-            ("string_key3".to_string(), "string_value3".to_string())
+            ("string_key3".into(), "string_value3".to_string())
         );
         assert_eq!(
             ctx.context().items[3],
-            ("string_key4".to_string(), "string_value4".to_string())
+            ("string_key4".into(), "string_value4".to_string())
         );
     }
 
@@ -1269,17 +2245,14 @@ mod tests {
         ctx.record("bool_key", true.to_string());
 
         assert_eq!(ctx.context().items.len(), 3);
-        assert_eq!(
-            ctx.context().items[0],
-            ("int_key".to_string(), "42".to_string())
-        );
+        assert_eq!(ctx.context().items[0], ("int_key".into(), "42".to_string()));
         assert_eq!(
             ctx.context().items[1],
-            ("float_key".to_string(), "3.24".to_string())
+            ("float_key".into(), "3.24".to_string())
         );
         assert_eq!(
             ctx.context().items[2],
-            ("bool_key".to_string(), "true".to_string())
+            ("bool_key".into(), "true".to_string())
         );
     }
 
@@ -1314,17 +2287,14 @@ mod tests {
         assert_eq!(ctx.context().items.len(), 4);
         assert_eq!(
             ctx.context().items[0],
-            ("name".to_string(), "test_user".to_string())
-        );
-        assert_eq!(
-            ctx.context().items[1],
-            ("age".to_string(), "25".to_string())
+            ("name".into(), "test_user".to_string())
         );
+        assert_eq!(ctx.context().items[1], ("age".into(), "25".to_string()));
         assert_eq!(ctx.context().items[2].0, "config_file");
         assert!(ctx.context().items[2].1.contains("/etc/config.toml"));
         assert_eq!(
             ctx.context().items[3],
-            ("status".to_string(), "active".to_string())
+            ("status".into(), "active".to_string())
         );
     }
 
@@ -1340,22 +2310,19 @@ mod tests {
         ctx.record("unicode", "测试中文字符"); // Unicode字符
 
         assert_eq!(ctx.context().items.len(), 5);
-        assert_eq!(ctx.context().items[0], ("".to_string(), "".to_string()));
+        assert_eq!(ctx.context().items[0], ("".into(), "".to_string()));
         assert_eq!(
             ctx.context().items[1],
-            ("empty_value".to_string(), "".to_string())
-        );
-        assert_eq!(
-            ctx.context().items[2],
-            ("".to_string(), "empty_key".to_string())
+            ("empty_value".into(), "".to_string())
         );
+        assert_eq!(ctx.context().items[2], ("".into(), "empty_key".to_string()));
         assert_eq!(
             ctx.context().items[3],
-            ("special_chars".to_string(), "@#$%^&*()".to_string())
+            ("special_chars".into(), "@#$%^&*()".to_string())
         );
         assert_eq!(
             ctx.context().items[4],
-            ("unicode".to_string(), "测试中文字符".to_string())
+            ("unicode".into(), "测试中文字符".to_string())
         );
     }
 
@@ -1374,15 +2341,15 @@ mod tests {
         assert_eq!(ctx.context().items.len(), 5);
         assert_eq!(
             ctx.context().items[0],
-            ("key1".to_string(), "value1".to_string())
+            ("key1".into(), "value1".to_string())
         );
         assert_eq!(
             ctx.context().items[1],
-            ("key2".to_string(), "value2".to_string())
+            ("key2".into(), "value2".to_string())
         );
         assert_eq!(
             ctx.context().items[2],
-            ("key1".to_string(), "new_value1".to_string())
+            ("key1".into(), "new_value1".to_string())
         );
         assert_eq!(ctx.context().items[3].0, "key3");
         assert!(ctx.context().items[3].1.contains("/path/file.txt"));
@@ -1402,13 +2369,122 @@ mod tests {
         assert_eq!(ctx.context().items.len(), 3);
         assert_eq!(
             ctx.context().items[0],
-            ("existing_key".to_string(), "existing_value".to_string())
+            ("existing_key".into(), "existing_value".to_string())
         );
         assert_eq!(
             ctx.context().items[1],
-            ("new_key1".to_string(), "new_value1".to_string())
+            ("new_key1".into(), "new_value1".to_string())
         );
         assert_eq!(ctx.context().items[2].0, "new_key2");
         assert!(ctx.context().items[2].1.contains("/new/path.txt"));
     }
+
+    #[test]
+    fn test_with_secret_masks_value() {
+        let mut ctx = OperationContext::want("login");
+        ctx.with_secret("password", "hunter2");
+        ctx.record("user", "alice");
+
+        assert_eq!(
+            ctx.context().items[0],
+            ("password".into(), REDACTED.to_string())
+        );
+        let display = format!("{ctx}");
+        assert!(!display.contains("hunter2"));
+        assert!(display.contains("user: alice"));
+    }
+
+    #[test]
+    fn test_registered_sensitive_key_masks_record() {
+        crate::core::register_sensitive_key("ut_ctx_api_token");
+        let mut ctx = OperationContext::new();
+        ctx.record("UT_CTX_API_TOKEN", "abc123");
+        ctx.record("ut_ctx_plain", "visible");
+        let ctx2 = OperationContext::from(("ut_ctx_api_token", "abc123"));
+
+        assert_eq!(ctx.context().items[0].1, REDACTED);
+        assert_eq!(ctx.context().items[1].1, "visible");
+        assert_eq!(ctx2.context().items[0].1, REDACTED);
+        assert!(!ctx.format_context().contains("abc123"));
+    }
+
+    #[test]
+    fn test_to_map_includes_children() {
+        let mut ctx = OperationContext::want("import");
+        ctx.record("file", "orders.csv");
+        let child = ctx.child("parse row");
+        child.record("line", "42");
+        child.record("file", "orders.csv#42");
+
+        let map = ctx.to_map();
+        assert_eq!(map["file"], "orders.csv");
+        assert_eq!(map["line"], "42");
+        assert_eq!(map["import.parse row.file"], "orders.csv#42");
+    }
+
+    #[test]
+    fn test_path_display_options() {
+        let path = Path::new("/home/alice/work/tmp/a.txt");
+        assert_eq!(
+            PathDisplay::new().render(path),
+            "/home/alice/work/tmp/a.txt"
+        );
+
+        let home = Some(Path::new("/home/alice"));
+        let opts = PathDisplay::new().with_shorten_home(true);
+        assert_eq!(opts.render_with_home(path, home), "~/work/tmp/a.txt");
+        assert_eq!(
+            opts.render_with_home(Path::new("/etc/hosts"), home),
+            "/etc/hosts"
+        );
+
+        // 相对化优先于主目录缩写
+        let opts = opts.with_base("/home/alice/work");
+        assert_eq!(opts.render_with_home(path, home), "tmp/a.txt");
+        assert_eq!(
+            opts.render_with_home(Path::new("/home/alice/work"), home),
+            "."
+        );
+
+        // 不存在的路径无法规范化，保持原样
+        let opts = PathDisplay::new().with_canonicalize(true);
+        assert_eq!(
+            opts.render(Path::new("/no/such/../file")),
+            "/no/such/../file"
+        );
+        let dir = std::env::temp_dir();
+        let canonical = std::fs::canonicalize(&dir).unwrap();
+        assert_eq!(opts.render(&dir.join(".")), canonical.display().to_string());
+    }
+
+    #[test]
+    fn test_context_record_is_uniform() {
+        fn fill<C>(target: &mut C)
+        where
+            C: ContextRecordExt + ContextRecord<&'static str, &'static str>,
+        {
+            target.record("user", "alice");
+            target.record_path("file", Path::new("/tmp/o.json"));
+            target.record_debug("shards", &[1, 2]);
+        }
+
+        let check = |ctx: &OperationContext| {
+            assert_eq!(ctx.get("user"), Some("alice"));
+            assert_eq!(ctx.get("file"), Some("/tmp/o.json"));
+            assert_eq!(ctx.get("shards"), Some("[1, 2]"));
+        };
+
+        let mut ctx = OperationContext::want("import");
+        fill(&mut ctx);
+        check(&ctx);
+
+        let mut ctx = OperationContext::want("import");
+        fill(&mut ctx.scope());
+        check(&ctx);
+
+        let mut err = crate::StructError::from(UvsReason::data_error());
+        fill(&mut err);
+        assert_eq!(err.contexts().len(), 1);
+        check(&err.contexts()[0]);
+    }
 }
@@ -0,0 +1,47 @@
+use std::fmt::{Debug, Display};
+
+use super::{domain::DomainReason, ErrorCode, StructError};
+
+/// 按错误码分层给出处理建议（与 `UvsReason` 的错误码区间一致）
+fn layer_help(code: i32) -> Option<&'static str> {
+    match code {
+        100..=199 => Some("business error: check the request input and business rules"),
+        200..=299 => {
+            Some("infrastructure error: check system resources; the operation may succeed on retry")
+        }
+        300..=399 => Some(
+            "configuration or external error: check configuration and third-party dependencies",
+        ),
+        _ => None,
+    }
+}
+
+impl<R> miette::Diagnostic for StructError<R>
+where
+    R: DomainReason + ErrorCode + Debug,
+{
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        Some(Box::new(self.error_code()))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        layer_help(self.error_code()).map(|h| Box::new(h) as Box<dyn Display>)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::Diagnostic;
+
+    use crate::{StructError, UvsReason};
+
+    #[test]
+    fn test_diagnostic_code_and_help() {
+        let err = StructError::from(UvsReason::core_conf()).with_detail("missing key");
+        assert_eq!(err.code().unwrap().to_string(), "300");
+        assert!(err.help().unwrap().to_string().starts_with("configuration"));
+
+        let report = miette::Report::new(err);
+        assert!(format!("{report:?}").contains("300"));
+    }
+}
@@ -11,7 +11,7 @@ impl<T> DomainReason for T where T: From<UvsReason> + Display + PartialEq {}
 
 #[allow(dead_code)]
 #[derive(Debug, PartialEq, Error, From)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum NullReason {
     #[allow(dead_code)]
     #[error("null")]
@@ -19,3 +19,80 @@ pub enum NullReason {
     #[error("{0}")]
     Uvs(UvsReason),
 }
+
+/// 生成领域错误的样板代码：错误与 `Result` 别名、`Uvs` 变体的 `From<UvsReason>` /
+/// `AsUvsReason` 以及 `ErrorCode` 实现（`code()` 以变体名作为错误码名称）
+///
+/// reason 枚举需包含 `Uvs(UvsReason)` 变体，且不再派生 `From`（否则 `From<UvsReason>` 重复实现）。
+/// 错误码按列出的变体映射，`Uvs` 变体取通用错误码，其余为 500。
+///
+/// ```rust
+/// use orion_error::{define_domain_error, ErrorCode, UvsReason};
+///
+/// #[derive(Debug, PartialEq, thiserror::Error)]
+/// pub enum OrderReason {
+///     #[error("insufficient funds")]
+///     InsufficientFunds,
+///     #[error("storage full: {0}")]
+///     StorageFull(String),
+///     #[error("{0}")]
+///     Uvs(UvsReason),
+/// }
+///
+/// define_domain_error!(OrderReason => OrderError, OrderResult {
+///     InsufficientFunds => 1001,
+///     StorageFull => 1002,
+/// });
+///
+/// fn place() -> OrderResult<()> {
+///     Err(OrderError::from(OrderReason::InsufficientFunds))
+/// }
+/// assert_eq!(place().unwrap_err().error_code(), 1001);
+/// assert_eq!(OrderReason::from(UvsReason::network_error()).error_code(), 202);
+/// assert_eq!(OrderReason::InsufficientFunds.code().to_string(), "1001:InsufficientFunds");
+/// ```
+#[macro_export]
+macro_rules! define_domain_error {
+    ($reason:ident => $error:ident, $result:ident $({ $($variant:ident => $code:expr),* $(,)? })?) => {
+        pub type $error = $crate::StructError<$reason>;
+        pub type $result<T> = ::std::result::Result<T, $error>;
+
+        impl ::std::convert::From<$crate::UvsReason> for $reason {
+            fn from(value: $crate::UvsReason) -> Self {
+                Self::Uvs(value)
+            }
+        }
+
+        impl $crate::AsUvsReason for $reason {
+            fn as_uvs(&self) -> ::std::option::Option<&$crate::UvsReason> {
+                match self {
+                    Self::Uvs(uvs) => ::std::option::Option::Some(uvs),
+                    #[allow(unreachable_patterns)]
+                    _ => ::std::option::Option::None,
+                }
+            }
+        }
+
+        impl $crate::ErrorCode for $reason {
+            fn error_code(&self) -> i32 {
+                match self {
+                    Self::Uvs(uvs) => $crate::ErrorCode::error_code(uvs),
+                    $($(Self::$variant { .. } => $code,)*)?
+                    #[allow(unreachable_patterns)]
+                    _ => 500,
+                }
+            }
+
+            fn code(&self) -> $crate::Code {
+                match self {
+                    Self::Uvs(uvs) => $crate::ErrorCode::code(uvs),
+                    $($(Self::$variant { .. } => {
+                        $crate::Code::new($code).with_name(stringify!($variant))
+                    })*)?
+                    #[allow(unreachable_patterns)]
+                    _ => $crate::Code::new($crate::ErrorCode::error_code(self)),
+                }
+            }
+        }
+    };
+}
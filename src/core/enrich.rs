@@ -0,0 +1,54 @@
+//! 全局上下文增强：错误创建时统一注入请求 ID、租户、构建版本等信息
+
+use std::sync::{Arc, RwLock};
+
+use super::context::OperationContext;
+
+/// 错误创建时调用的增强器，向新错误的基础上下文帧写入公共字段
+pub trait ContextEnricher: Send + Sync {
+    fn enrich(&self, ctx: &mut OperationContext);
+}
+
+impl<F> ContextEnricher for F
+where
+    F: Fn(&mut OperationContext) + Send + Sync,
+{
+    fn enrich(&self, ctx: &mut OperationContext) {
+        self(ctx)
+    }
+}
+
+static ENRICHERS: RwLock<Vec<Arc<dyn ContextEnricher>>> = RwLock::new(Vec::new());
+
+/// 注册全局增强器，按注册顺序执行
+///
+/// 对所有经 `StructError::new` 创建的错误生效（`owe_*`、`ToStructError`、`From<R>`、构建器），
+/// 写入的字段放在最靠近出错位置的第一个上下文帧；未写入任何字段时不追加帧。
+pub fn register_context_enricher<E: ContextEnricher + 'static>(enricher: E) {
+    ENRICHERS
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .push(Arc::new(enricher));
+}
+
+/// 清空全局增强器
+pub fn clear_context_enrichers() {
+    ENRICHERS.write().unwrap_or_else(|e| e.into_inner()).clear();
+}
+
+/// 依次执行已注册的增强器，得到待插入的基础帧
+pub(crate) fn enrich_context() -> Option<OperationContext> {
+    // 先复制列表再执行，增强器内部再创建错误时不会重入锁
+    let enrichers = {
+        let guard = ENRICHERS.read().unwrap_or_else(|e| e.into_inner());
+        if guard.is_empty() {
+            return None;
+        }
+        guard.clone()
+    };
+    let mut ctx = OperationContext::new();
+    for enricher in &enrichers {
+        enricher.enrich(&mut ctx);
+    }
+    (!ctx.context().items.is_empty()).then_some(ctx)
+}
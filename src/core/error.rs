@@ -1,12 +1,38 @@
-use std::{fmt::Display, ops::Deref, sync::Arc};
+use std::{
+    any::Any,
+    backtrace::Backtrace,
+    collections::BTreeMap,
+    fmt::Display,
+    ops::Deref,
+    sync::{Arc, OnceLock},
+    time::SystemTime,
+};
+
+#[cfg(feature = "serde")]
+use super::config::{parse_rfc3339, TimestampFormat};
+#[cfg(feature = "serde")]
+use serde::Deserialize as _;
+#[cfg(feature = "serde")]
+use std::time::UNIX_EPOCH;
 
-use crate::ErrorWith;
+use crate::{ContextRecord, ErrorWith, IntoContextKey};
 
 use super::{
-    context::{CallContext, OperationContext},
+    config::{app_info, error_config, AppInfo},
+    context::{flatten_frames, want_mode, CallContext, OperationContext, WantMode},
     domain::DomainReason,
-    ContextAdd, ErrorCode,
+    enrich::enrich_context,
+    extensions::Extensions,
+    format::{CompactFormatter, ErrorFormatter, VerboseFormatter, WrappedFormatter},
+    id::maybe_error_id,
+    limit::limit_detail,
+    reason::Code,
+    redact::redact_owned,
+    severity::{Severity, SeverityOf},
+    universal::{AsUvsReason, FaultOf, UvsReason},
+    ContextAdd, ErrorCode, ErrorExitCode, HttpStatus,
 };
+use smallvec::SmallVec;
 use thiserror::Error;
 
 #[macro_export]
@@ -26,15 +52,186 @@ impl<T: DomainReason + ErrorCode> ErrorCode for StructError<T> {
     fn error_code(&self) -> i32 {
         self.reason.error_code()
     }
+
+    fn error_code_base(&self) -> i32 {
+        self.reason.error_code_base()
+    }
+
+    fn code(&self) -> Code {
+        self.reason.code()
+    }
+}
+
+// 以下映射在 `classify` 设置了通用分类时以分类为准，否则委托给 reason
+
+impl<T: DomainReason + ErrorExitCode> ErrorExitCode for StructError<T> {
+    fn exit_code(&self) -> i32 {
+        match self.uvs_class() {
+            Some(uvs) => uvs.exit_code(),
+            None => self.reason.exit_code(),
+        }
+    }
+}
+
+impl<T: DomainReason + SeverityOf> SeverityOf for StructError<T> {
+    fn severity(&self) -> Severity {
+        match self.uvs_class() {
+            Some(uvs) => uvs.severity(),
+            None => self.reason.severity(),
+        }
+    }
+}
+
+impl<T: DomainReason + AsUvsReason> AsUvsReason for StructError<T> {
+    fn as_uvs(&self) -> Option<&UvsReason> {
+        self.uvs_class().or_else(|| self.reason.as_uvs())
+    }
+}
+
+impl<T: DomainReason + FaultOf> FaultOf for StructError<T> {
+    fn is_client_fault(&self) -> bool {
+        match self.uvs_class() {
+            Some(uvs) => uvs.is_client_fault(),
+            None => self.reason.is_client_fault(),
+        }
+    }
+
+    fn is_server_fault(&self) -> bool {
+        match self.uvs_class() {
+            Some(uvs) => uvs.is_server_fault(),
+            None => self.reason.is_server_fault(),
+        }
+    }
+}
+
+impl<T: DomainReason + HttpStatus> HttpStatus for StructError<T> {
+    fn http_status(&self) -> u16 {
+        match self.uvs_class() {
+            Some(uvs) => uvs.http_status(),
+            None => self.reason.http_status(),
+        }
+    }
 }
 
 /// Structured error type containing detailed error information
 /// including error source, contextual data, and debugging information.
-#[derive(Error, Debug, Clone, PartialEq)]
+///
+/// `T: Send + Sync + 'static` 时错误本身同样满足，可跨线程与异步任务传递
+/// （来源错误与保留的原始错误均要求 `Send + Sync`，见 `tests/test_send_sync.rs`）。
+#[derive(Debug, Clone, PartialEq)]
 pub struct StructError<T: DomainReason> {
     imp: Box<StructErrorImpl<T>>,
 }
 
+/// 直接使用通用原因的错误类型，适合不需要自定义领域原因的小型项目
+pub type UvsError = StructError<UvsReason>;
+
+impl<T: DomainReason> std::error::Error for StructError<T>
+where
+    Self: std::fmt::Debug + Display,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.imp
+            .body
+            .source
+            .as_ref()
+            .map(|s| s.inner() as &(dyn std::error::Error + 'static))
+    }
+}
+
+/// 按 reason + detail 比较的包装，便于测试比较与去重（忽略上下文、位置与 ID）
+#[derive(Debug, Clone)]
+pub struct ReasonEq<T: DomainReason>(pub StructError<T>);
+
+impl<T: DomainReason> ReasonEq<T> {
+    pub fn into_inner(self) -> StructError<T> {
+        self.0
+    }
+}
+
+impl<T: DomainReason> PartialEq for ReasonEq<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_reason(&other.0)
+    }
+}
+
+impl<T: DomainReason + Eq> Eq for ReasonEq<T> {}
+
+impl<T: DomainReason + std::hash::Hash> std::hash::Hash for ReasonEq<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.imp.reason.hash(state);
+        self.0.imp.body.detail.hash(state);
+    }
+}
+
+/// 底层错误源：可克隆，按显示文本比较相等
+#[derive(Clone)]
+pub struct ErrorSource(Arc<dyn std::error::Error + Send + Sync + 'static>);
+
+impl ErrorSource {
+    pub fn new<E: std::error::Error + Send + Sync + 'static>(err: E) -> Self {
+        Self(Arc::new(err))
+    }
+
+    pub fn inner(&self) -> &(dyn std::error::Error + Send + Sync + 'static) {
+        self.0.as_ref()
+    }
+}
+
+impl std::fmt::Debug for ErrorSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl Display for ErrorSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl PartialEq for ErrorSource {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.to_string() == other.0.to_string()
+    }
+}
+
+impl std::error::Error for ErrorSource {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.0.source()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ErrorSource {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ErrorSource {
+    /// 序列化时只保留显示文本，反序列化得到的错误源没有下层错误链
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(|text| Self::new(RemoteSource(text)))
+    }
+}
+
+/// 反序列化得到的错误源文本
+#[cfg(feature = "serde")]
+#[derive(Debug)]
+struct RemoteSource(String);
+
+#[cfg(feature = "serde")]
+impl Display for RemoteSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl std::error::Error for RemoteSource {}
+
 #[cfg(feature = "serde")]
 impl<T: DomainReason> serde::Serialize for StructError<T>
 where
@@ -48,10 +245,81 @@ where
     }
 }
 
+/// 兼容任意不高于 `ERROR_SCHEMA_VERSION` 的载荷：新增字段缺失时取默认值
+#[cfg(feature = "serde")]
+impl<'de, T: DomainReason> serde::Deserialize<'de> for StructError<T>
+where
+    StructErrorImpl<T>: serde::Deserialize<'de>,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        StructErrorImpl::deserialize(deserializer).map(|imp| StructError { imp: Box::new(imp) })
+    }
+}
+
+/// 序列化格式版本，写入 `schema_version` 字段；1 为引入版本号之前的格式
+/// （仅 `reason`、`detail`、`position`、`context`）
+pub const ERROR_SCHEMA_VERSION: u32 = 2;
+
+/// `schema_version` 字段：序列化时总是写入当前版本，反序列化时拒绝更新的版本
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+struct SchemaVersion;
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for SchemaVersion {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u32(ERROR_SCHEMA_VERSION)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SchemaVersion {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let version = u32::deserialize(deserializer)?;
+        if version > ERROR_SCHEMA_VERSION {
+            return Err(serde::de::Error::custom(format_args!(
+                "unsupported error schema_version {version} (newest known: {ERROR_SCHEMA_VERSION})"
+            )));
+        }
+        Ok(Self)
+    }
+}
+
+#[cfg(all(feature = "serde", feature = "json"))]
+impl<T> StructError<T>
+where
+    T: DomainReason + serde::de::DeserializeOwned,
+{
+    /// 解析任意版本的 JSON 错误载荷，先按 `schema_version`（缺失视为 1）逐级升级再反序列化
+    pub fn deserialize_any_version(mut value: serde_json::Value) -> serde_json::Result<Self> {
+        let version = value
+            .get("schema_version")
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or(1);
+        if version < 2 {
+            upgrade_v1(&mut value);
+        }
+        serde_json::from_value(value)
+    }
+}
+
+/// 版本 1 的载荷允许 `context` 为 `null`，此后总是数组
+#[cfg(all(feature = "serde", feature = "json"))]
+fn upgrade_v1(value: &mut serde_json::Value) {
+    if let Some(obj) = value.as_object_mut() {
+        if obj.get("context").is_none_or(serde_json::Value::is_null) {
+            obj.insert("context".into(), serde_json::Value::Array(Vec::new()));
+        }
+    }
+}
+
 impl<T: DomainReason> StructError<T> {
     pub fn imp(&self) -> &StructErrorImpl<T> {
         &self.imp
     }
+
+    fn body_mut(&mut self) -> &mut ErrorBody {
+        Arc::make_mut(&mut self.imp.body)
+    }
 }
 
 impl<T: DomainReason> Deref for StructError<T> {
@@ -66,19 +334,52 @@ impl<T: DomainReason> StructError<T> {
         reason: T,
         detail: Option<String>,
         position: Option<String>,
-        context: Vec<OperationContext>,
+        mut context: Vec<OperationContext>,
     ) -> Self {
+        if let Some(base) = enrich_context() {
+            context.insert(0, base);
+        }
         StructError {
             imp: Box::new(StructErrorImpl {
                 reason,
-                detail,
-                position,
-                context: Arc::new(context),
+                body: Arc::new(ErrorBody {
+                    schema_version: SchemaVersion,
+                    detail: detail.map(|d| limit_detail(redact_owned(d))),
+                    position,
+                    position_trail: Vec::new(),
+                    context: shared_context(context),
+                    id: maybe_error_id(),
+                    correlation_id: None,
+                    source: None,
+                    timestamp: capture_timestamp(),
+                    backtrace: capture_backtrace(),
+                    converted_from: Vec::new(),
+                    origin: None,
+                    app: app_info(),
+                    target: None,
+                    uvs_class: None,
+                    extensions: Extensions::default(),
+                    #[cfg(feature = "json")]
+                    payload: None,
+                }),
             }),
         }
     }
 }
 
+/// 错误的上下文帧栈；多数错误只有 0–2 帧，内联存放在共享存储中，不再单独分配
+pub type ContextStack = SmallVec<[OperationContext; 2]>;
+
+/// 无上下文的错误共享同一个空栈，避免每次构造都分配；首次写入时写时复制
+fn shared_context(context: Vec<OperationContext>) -> Arc<ContextStack> {
+    static EMPTY: OnceLock<Arc<ContextStack>> = OnceLock::new();
+    if context.is_empty() {
+        EMPTY.get_or_init(|| Arc::new(ContextStack::new())).clone()
+    } else {
+        Arc::new(context.into_iter().collect())
+    }
+}
+
 impl<T> From<T> for StructError<T>
 where
     T: DomainReason,
@@ -88,13 +389,207 @@ where
     }
 }
 
+/// 序列化字段名是稳定的存储格式，均以 `rename` 显式声明，重命名 Rust 字段不影响载荷：
+///
+/// | 字段 | 说明 |
+/// |------|------|
+/// | `reason` | 原因（按 reason 类型的 serde 表示） |
+/// | `schema_version` | 格式版本，见 `ERROR_SCHEMA_VERSION` |
+/// | `detail` / `position` | 可为 `null` |
+/// | `context` | 上下文帧数组：`context.items`、`result`、`exit_log`、`mod_path`、`target`，可选 `severity`、`children`、`location` |
+/// | `position_trail` / `converted_from` | 为空时省略 |
+/// | `id` / `correlation_id` / `source` / `timestamp` / `app` / `target` / `uvs_class` / `payload` | 缺省时省略 |
+///
+/// 只允许新增可省略的字段；删除或改名需提升 `ERROR_SCHEMA_VERSION` 并在
+/// `deserialize_any_version` 中升级旧载荷。样例载荷见 `tests/compat/`。
 #[derive(Error, Debug, Clone, PartialEq)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StructErrorImpl<T: DomainReason> {
+    #[cfg_attr(feature = "serde", serde(rename = "reason"))]
     reason: T,
+    /// reason 以外的部分共享存储，克隆错误时只增加引用计数，修改时写时复制
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    body: Arc<ErrorBody>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+struct ErrorBody {
+    #[cfg_attr(feature = "serde", serde(rename = "schema_version"))]
+    schema_version: SchemaVersion,
+    #[cfg_attr(feature = "serde", serde(rename = "detail"))]
     detail: Option<String>,
+    #[cfg_attr(feature = "serde", serde(rename = "position"))]
     position: Option<String>,
-    context: Arc<Vec<OperationContext>>,
+    /// `position` 之后各层 `want` / `position` 的标注点，按调用顺序记录，最多保留最近 `MAX_POSITION_TRAIL` 个
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "position_trail", skip_serializing_if = "Vec::is_empty")
+    )]
+    position_trail: Vec<String>,
+    #[cfg_attr(feature = "serde", serde(rename = "context"))]
+    context: Arc<ContextStack>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "id", skip_serializing_if = "Option::is_none")
+    )]
+    id: Option<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "correlation_id", skip_serializing_if = "Option::is_none")
+    )]
+    correlation_id: Option<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "source", skip_serializing_if = "Option::is_none")
+    )]
+    source: Option<ErrorSource>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            rename = "timestamp",
+            skip_serializing_if = "Option::is_none",
+            serialize_with = "serialize_timestamp",
+            deserialize_with = "deserialize_timestamp"
+        )
+    )]
+    timestamp: Option<SystemTime>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    backtrace: Option<CapturedBacktrace>,
+    /// `convert_error` 经过的原因，按转换顺序记录，例如 `StoreReason: storage full`
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "converted_from", skip_serializing_if = "Vec::is_empty")
+    )]
+    converted_from: Vec<String>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    origin: Option<ErrorOrigin>,
+    /// 创建时的应用构建信息，见 `ErrorConfig::set_app_info`
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "app", skip_serializing_if = "Option::is_none")
+    )]
+    app: Option<Arc<AppInfo>>,
+    /// `with_target` 显式设置的操作对象，优先于上下文帧中的目标
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "target", skip_serializing_if = "Option::is_none")
+    )]
+    target: Option<String>,
+    /// `classify` 附加的通用分类，供重试、HTTP 映射与指标使用
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "uvs_class", skip_serializing_if = "Option::is_none")
+    )]
+    uvs_class: Option<UvsReason>,
+    /// 中间件附加的类型化数据，见 `with_extension`
+    #[cfg_attr(feature = "serde", serde(skip))]
+    extensions: Extensions,
+    /// `with_payload` 附加的机器可读数据，例如字段列表或关联 ID
+    #[cfg(feature = "json")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "payload", skip_serializing_if = "Option::is_none")
+    )]
+    payload: Option<serde_json::Value>,
+}
+
+/// 构造时捕获的调用栈；不参与相等比较
+#[derive(Debug, Clone)]
+struct CapturedBacktrace(Arc<Backtrace>);
+
+impl PartialEq for CapturedBacktrace {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+/// `convert_error_keep` 保留的转换前错误（类型擦除）；不参与相等比较
+#[derive(Clone)]
+struct ErrorOrigin(Arc<dyn AnyOrigin>);
+
+trait AnyOrigin: Any + Send + Sync {
+    fn as_any(&self) -> &dyn Any;
+    fn origin(&self) -> Option<&ErrorOrigin>;
+    fn type_label(&self) -> &'static str;
+}
+
+impl<T: DomainReason + Send + Sync + 'static> AnyOrigin for StructError<T> {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn origin(&self) -> Option<&ErrorOrigin> {
+        self.imp.body.origin.as_ref()
+    }
+
+    fn type_label(&self) -> &'static str {
+        short_type_name::<T>()
+    }
+}
+
+impl std::fmt::Debug for ErrorOrigin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ErrorOrigin(StructError<{}>)", self.0.type_label())
+    }
+}
+
+impl PartialEq for ErrorOrigin {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+/// 时间戳按 `ErrorConfig::timestamp_format` 序列化：Unix 毫秒为数字，其余为字符串
+#[cfg(feature = "serde")]
+fn serialize_timestamp<S: serde::Serializer>(
+    ts: &Option<SystemTime>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let format = error_config().timestamp_format;
+    match ts {
+        Some(t) if format == TimestampFormat::UnixMillis => {
+            let ms = t.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_millis());
+            serializer.serialize_u64(ms as u64)
+        }
+        Some(t) => serializer.collect_str(&format.format(*t)),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// 接受 Unix 毫秒或 RFC 3339 字符串，与 `TimestampFormat` 的各种输出对应
+#[cfg(feature = "serde")]
+fn deserialize_timestamp<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<SystemTime>, D::Error> {
+    #[derive(serde::Deserialize)]
+    #[serde(untagged)]
+    enum Raw {
+        Millis(u64),
+        Text(String),
+    }
+    match Option::<Raw>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(Raw::Millis(ms)) => Ok(Some(UNIX_EPOCH + std::time::Duration::from_millis(ms))),
+        Some(Raw::Text(text)) => parse_rfc3339(&text)
+            .map(Some)
+            .ok_or_else(|| serde::de::Error::custom(format_args!("invalid timestamp `{text}`"))),
+    }
+}
+
+/// 按全局配置记录时间戳；wasm32-unknown-unknown 上 `SystemTime::now` 不可用
+fn capture_timestamp() -> Option<SystemTime> {
+    #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+    return None;
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    error_config().capture_timestamp.then(SystemTime::now)
+}
+
+fn capture_backtrace() -> Option<CapturedBacktrace> {
+    error_config()
+        .capture_backtrace
+        .then(|| CapturedBacktrace(Arc::new(Backtrace::force_capture())))
 }
 
 impl<T: DomainReason> StructErrorImpl<T> {
@@ -103,16 +598,110 @@ impl<T: DomainReason> StructErrorImpl<T> {
     }
 
     pub fn detail(&self) -> &Option<String> {
-        &self.detail
+        &self.body.detail
     }
 
     pub fn position(&self) -> &Option<String> {
-        &self.position
+        &self.body.position
+    }
+
+    /// `classify` 附加的通用分类
+    pub fn uvs_class(&self) -> Option<&UvsReason> {
+        self.body.uvs_class.as_ref()
+    }
+
+    pub fn extensions(&self) -> &Extensions {
+        &self.body.extensions
+    }
+
+    /// 按类型取附加数据，例如 `err.extension::<RetryAfter>()`
+    pub fn extension<V: 'static>(&self) -> Option<&V> {
+        self.body.extensions.get()
+    }
+
+    /// 首个位置之后逐层追加的标注点，最早的在前
+    pub fn position_trail(&self) -> &[String] {
+        &self.body.position_trail
+    }
+
+    /// 完整的传播轨迹：首个位置加上后续各层的标注点
+    pub fn positions(&self) -> Vec<&str> {
+        self.body
+            .position
+            .iter()
+            .chain(&self.body.position_trail)
+            .map(String::as_str)
+            .collect()
+    }
+
+    pub fn context(&self) -> &Arc<ContextStack> {
+        &self.body.context
+    }
+
+    /// 唯一错误 ID（需 `set_error_id_enabled(true)` 或显式 `with_error_id`）
+    pub fn id(&self) -> &Option<String> {
+        &self.body.id
+    }
+
+    /// 关联 / 请求 ID
+    pub fn correlation_id(&self) -> &Option<String> {
+        &self.body.correlation_id
+    }
+
+    /// 底层错误源（通过 `with_source` 或构建器 `source` 设置）
+    pub fn error_source(&self) -> Option<&ErrorSource> {
+        self.body.source.as_ref()
     }
 
-    pub fn context(&self) -> &Arc<Vec<OperationContext>> {
-        &self.context
+    /// 从底层错误源开始，沿 `Error::source` 逐层展开的错误链
+    pub fn source_chain(&self) -> impl Iterator<Item = &(dyn std::error::Error + 'static)> {
+        std::iter::successors(
+            self.body
+                .source
+                .as_ref()
+                .map(|s| s.inner() as &(dyn std::error::Error + 'static)),
+            |e| e.source(),
+        )
     }
+
+    /// 创建时间（需 `ErrorConfig::capture_timestamp`）
+    pub fn timestamp(&self) -> Option<SystemTime> {
+        self.body.timestamp
+    }
+
+    /// 创建时捕获的调用栈（需 `ErrorConfig::capture_backtrace`）
+    pub fn backtrace(&self) -> Option<&Backtrace> {
+        self.body.backtrace.as_ref().map(|b| b.0.as_ref())
+    }
+
+    /// 创建错误时的应用构建信息
+    pub fn app_info(&self) -> Option<&AppInfo> {
+        self.body.app.as_deref()
+    }
+
+    /// 转换轨迹：每次 `convert_error` 前的原因，最早的在前
+    pub fn conversion_trail(&self) -> &[String] {
+        &self.body.converted_from
+    }
+
+    /// 经 `convert_error_keep` 保留的原始错误，沿多层转换向内查找第一个 reason 类型为 `R` 的错误
+    pub fn origin<R: DomainReason + 'static>(&self) -> Option<&StructError<R>> {
+        let mut cur = self.body.origin.as_ref();
+        while let Some(origin) = cur {
+            if let Some(err) = origin.0.as_any().downcast_ref() {
+                return Some(err);
+            }
+            cur = origin.0.origin();
+        }
+        None
+    }
+}
+
+/// 类型名去掉模块路径与泛型参数，例如 `app::store::StoreReason` -> `StoreReason`
+pub(crate) fn short_type_name<T: ?Sized>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    let name = name.split('<').next().unwrap_or(name);
+    name.rsplit("::").next().unwrap_or(name)
 }
 
 pub fn convert_error<R1, R2>(other: StructError<R1>) -> StructError<R2>
@@ -120,52 +709,237 @@ where
     R1: DomainReason,
     R2: DomainReason + From<R1>,
 {
-    StructError::new(
-        other.imp.reason.into(),
-        other.imp.detail,
-        other.imp.position,
-        Arc::try_unwrap(other.imp.context).unwrap_or_else(|arc| (*arc).clone()),
-    )
+    wrap_error(other, R2::from)
+}
+
+/// 以 `f` 得到的新原因包装错误，detail、位置、上下文与 ID 原样保留，原 reason 记入转换轨迹
+pub(crate) fn wrap_error<R1, R2, F>(other: StructError<R1>, f: F) -> StructError<R2>
+where
+    R1: DomainReason,
+    R2: DomainReason,
+    F: FnOnce(R1) -> R2,
+{
+    let StructErrorImpl { reason, mut body } = *other.imp;
+    let crumb = format!("{}: {reason}", short_type_name::<R1>());
+    Arc::make_mut(&mut body).converted_from.push(crumb);
+    StructError {
+        imp: Box::new(StructErrorImpl {
+            reason: f(reason),
+            body,
+        }),
+    }
+}
+
+impl<T: DomainReason> StructError<T> {
+    /// 就地转换 reason，保留 detail、位置与上下文；适合无需定义 `From` 的临时映射
+    pub fn map_reason<R2, F>(self, f: F) -> StructError<R2>
+    where
+        R2: DomainReason,
+        F: FnOnce(T) -> R2,
+    {
+        let StructErrorImpl { reason, body } = *self.imp;
+        StructError {
+            imp: Box::new(StructErrorImpl {
+                reason: f(reason),
+                body,
+            }),
+        }
+    }
+}
+
+/// 与 `convert_error` 相同，但保留转换前的错误，边界处可通过 `origin::<R1>()` 按底层原因分支
+pub fn convert_error_keep<R1, R2>(other: StructError<R1>) -> StructError<R2>
+where
+    R1: DomainReason + Clone + Send + Sync + 'static,
+    R2: DomainReason + From<R1>,
+{
+    let origin = ErrorOrigin(Arc::new(other.clone()));
+    let mut err = convert_error::<R1, R2>(other);
+    err.body_mut().origin = Some(origin);
+    err
 }
 
 impl<T: DomainReason> StructError<T> {
     pub fn builder(reason: T) -> StructErrorBuilder<T> {
-        StructErrorBuilder {
-            reason,
-            detail: None,
-            position: None,
-            contexts: Vec::new(),
+        StructErrorBuilder::new(reason)
+    }
+
+    /// 仅比较 reason 与 detail，忽略上下文、位置与 ID
+    pub fn eq_reason(&self, other: &Self) -> bool {
+        self.imp.reason == other.imp.reason && self.imp.body.detail == other.imp.body.detail
+    }
+
+    /// 折叠重复的上下文帧：目标相同的帧合并到首次出现处，完全相同的帧只保留一个
+    #[must_use]
+    pub fn merge_context(mut self) -> Self {
+        let frames = Arc::make_mut(&mut self.body_mut().context);
+        let mut folded = ContextStack::with_capacity(frames.len());
+        for frame in frames.drain(..) {
+            let same = folded
+                .iter_mut()
+                .find(|f| (f.target().is_some() && f.target() == frame.target()) || **f == frame);
+            match same {
+                Some(f) => f.merge(&frame),
+                None => folded.push(frame),
+            }
         }
+        *frames = folded;
+        self
+    }
+
+    /// 压缩上下文栈：相邻且目标相同的帧（循环、递归调用产生）合并为一帧，保持各层顺序不变
+    #[must_use]
+    pub fn compact_contexts(mut self) -> Self {
+        let frames = Arc::make_mut(&mut self.body_mut().context);
+        let mut compacted = ContextStack::with_capacity(frames.len());
+        for frame in frames.drain(..) {
+            match compacted.last_mut() {
+                Some(last) if last.target().is_some() && last.target() == frame.target() => {
+                    last.merge(&frame)
+                }
+                _ => compacted.push(frame),
+            }
+        }
+        *frames = compacted;
+        self
+    }
+
+    /// 附加底层错误源，可通过 `std::error::Error::source` 访问
+    #[must_use]
+    pub fn with_source<E: std::error::Error + Send + Sync + 'static>(mut self, err: E) -> Self {
+        self.body_mut().source = Some(ErrorSource::new(err));
+        self
     }
 
     /// 使用示例
     ///self.with_position(location!());
     #[must_use]
     pub fn with_position(mut self, position: impl Into<String>) -> Self {
-        self.imp.position = Some(position.into());
+        self.body_mut().position = Some(position.into());
         self
     }
     #[must_use]
     pub fn with_context(mut self, context: CallContext) -> Self {
-        Arc::make_mut(&mut self.imp.context).push(OperationContext::from(context));
+        Arc::make_mut(&mut self.body_mut().context).push(OperationContext::from(context));
         self
     }
 
     pub fn contexts(&self) -> &[OperationContext] {
-        self.imp.context.as_ref()
+        self.imp.body.context.as_ref()
+    }
+
+    /// 所有上下文帧的条目展平为映射，最靠近出错位置的帧保持原键名，其余重复键加上帧前缀
+    pub fn context_map(&self) -> BTreeMap<String, String> {
+        let mut map = BTreeMap::new();
+        flatten_frames(self.contexts(), "", &mut map);
+        map
+    }
+
+    /// 附加结构化载荷（覆盖已有值），供调用方读取机器可读的细节而无需解析消息文本
+    #[cfg(feature = "json")]
+    #[must_use]
+    pub fn with_payload(mut self, payload: impl Into<serde_json::Value>) -> Self {
+        self.body_mut().payload = Some(payload.into());
+        self
+    }
+
+    #[cfg(feature = "json")]
+    pub fn payload(&self) -> Option<&serde_json::Value> {
+        self.imp.body.payload.as_ref()
+    }
+
+    /// `context_map` 的 JSON 对象形式，便于直接嵌入日志或 HTTP 错误响应体
+    #[cfg(feature = "json")]
+    pub fn context_json(&self) -> serde_json::Value {
+        serde_json::Value::Object(
+            self.context_map()
+                .into_iter()
+                .map(|(k, v)| (k, serde_json::Value::String(v)))
+                .collect(),
+        )
     }
 
     // 提供修改方法
     #[must_use]
     pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
-        self.imp.detail = Some(detail.into());
+        self.body_mut().detail = Some(limit_detail(redact_owned(detail.into())));
+        self
+    }
+    /// 就地修改 reason，供中间件改写错误
+    pub fn reason_mut(&mut self) -> &mut T {
+        &mut self.imp.reason
+    }
+
+    /// 就地修改 `detail`；直接写入，不经过脱敏与长度限制
+    pub fn detail_mut(&mut self) -> &mut Option<String> {
+        &mut self.body_mut().detail
+    }
+
+    /// 拆分为 (reason, detail, position, contexts)，与 `new` 的参数一一对应
+    pub fn into_parts(self) -> (T, Option<String>, Option<String>, Vec<OperationContext>) {
+        let StructErrorImpl { reason, body } = *self.imp;
+        let body = Arc::unwrap_or_clone(body);
+        (
+            reason,
+            body.detail,
+            body.position,
+            Arc::unwrap_or_clone(body.context).into_vec(),
+        )
+    }
+
+    /// 设置（或覆盖）错误的操作对象，适用于没有上下文帧的错误，例如 `to_err()` 直接创建的错误
+    #[must_use]
+    pub fn with_target(mut self, target: impl Into<String>) -> Self {
+        self.body_mut().target = Some(target.into());
+        self
+    }
+
+    /// 显式指定错误 ID
+    #[must_use]
+    pub fn with_error_id(mut self, id: impl Into<String>) -> Self {
+        self.body_mut().id = Some(id.into());
+        self
+    }
+
+    /// 附加关联 / 请求 ID，便于与服务端日志对应
+    #[must_use]
+    pub fn with_correlation_id(mut self, id: impl Into<String>) -> Self {
+        self.body_mut().correlation_id = Some(id.into());
+        self
+    }
+
+    /// 附加通用分类，领域原因无需包装 `Uvs(UvsReason)` 也能参与重试判断、HTTP / 退出码映射、
+    /// 严重级别与指标统计；设置后这些映射以分类为准，错误码仍取自 reason
+    #[must_use]
+    pub fn classify(mut self, uvs: UvsReason) -> Self {
+        self.body_mut().uvs_class = Some(uvs);
+        self
+    }
+
+    /// 附加类型化数据，同类型的旧值被替换；原因转换时随错误保留
+    #[must_use]
+    pub fn with_extension<V: Clone + Send + Sync + 'static>(mut self, val: V) -> Self {
+        self.body_mut().extensions.insert(val);
         self
     }
+
+    pub fn extensions_mut(&mut self) -> &mut Extensions {
+        &mut self.body_mut().extensions
+    }
+
     pub fn err<V>(self) -> Result<V, Self> {
         Err(self)
     }
+    /// 操作对象：`with_target` 显式设置的值，否则取第一个上下文帧的目标
     pub fn target(&self) -> Option<String> {
-        self.context.first().and_then(|x| x.target().clone())
+        if let Some(target) = &self.imp.body.target {
+            return Some(target.clone());
+        }
+        self.imp
+            .body
+            .context
+            .first()
+            .and_then(|x| x.target().clone())
     }
 }
 
@@ -175,7 +949,7 @@ impl<T: DomainReason> StructErrorTrait<T> for StructError<T> {
     }
 
     fn get_detail(&self) -> Option<&String> {
-        self.detail.as_ref()
+        self.imp.body.detail.as_ref()
     }
 
     fn get_target(&self) -> Option<String> {
@@ -193,46 +967,53 @@ impl<S1: Into<String>, S2: Into<String>, T: DomainReason> ContextAdd<(S1, S2)> f
 
 impl<T: DomainReason> ContextAdd<&OperationContext> for StructError<T> {
     fn add_context(&mut self, ctx: &OperationContext) {
-        Arc::make_mut(&mut self.imp.context).push(ctx.clone());
+        Arc::make_mut(&mut self.body_mut().context).push(ctx.clone());
     }
 }
 impl<T: DomainReason> ContextAdd<OperationContext> for StructError<T> {
     fn add_context(&mut self, ctx: OperationContext) {
-        Arc::make_mut(&mut self.imp.context).push(ctx);
+        Arc::make_mut(&mut self.body_mut().context).push(ctx);
     }
 }
 
-impl<T: std::fmt::Display + DomainReason + ErrorCode> Display for StructError<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        // 核心错误信息
-        write!(f, "[{}] {reason}", self.error_code(), reason = self.reason)?;
-
-        // 位置信息优先显示
-        if let Some(pos) = &self.position {
-            write!(f, "\n  -> At: {pos}")?;
-        }
-
-        // 目标资源信息
-        if let Some(target) = &self.target() {
-            write!(f, "\n  -> Want: {target}")?;
+/// 写入最近的上下文帧；尚无上下文时新建一帧
+impl<T, S1, S2> ContextRecord<S1, S2> for StructError<T>
+where
+    T: DomainReason,
+    OperationContext: ContextRecord<S1, S2>,
+{
+    fn record(&mut self, key: S1, val: S2) {
+        let ctx_stack = Arc::make_mut(&mut self.body_mut().context);
+        if ctx_stack.is_empty() {
+            ctx_stack.push(OperationContext::new());
         }
-
-        // 技术细节
-        if let Some(detail) = &self.detail {
-            write!(f, "\n  -> Details: {detail}")?;
+        if let Some(ctx) = ctx_stack.last_mut() {
+            ctx.record(key, val);
         }
+    }
+}
 
-        // 上下文信息
-        if !self.context.is_empty() {
-            writeln!(f, "\n  -> Context stack:")?;
+impl<T> StructError<T>
+where
+    T: DomainReason + ErrorCode + std::fmt::Debug + Display + Send + Sync + 'static,
+{
+    /// 转换为 `Box<dyn Error + Send + Sync>`，与接收装箱错误的库互通；
+    /// 之后可用 `downcast_ref::<StructError<T>>()` 取回结构化信息
+    pub fn into_boxed(self) -> Box<dyn std::error::Error + Send + Sync> {
+        Box::new(self)
+    }
+}
 
-            for (i, c) in self.context.iter().enumerate() {
-                writeln!(f, "context {i}: ")?;
-                writeln!(f, "{c}")?;
-            }
+/// `{}` 为多行详细格式，`{:#}` 为单行紧凑格式，`{:80}` 按 80 列折行
+impl<T: std::fmt::Display + DomainReason + ErrorCode> Display for StructError<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            CompactFormatter.write_to(&self.view(), f)
+        } else if let Some(width) = f.width() {
+            WrappedFormatter::new(width).write_to(&self.view(), f)
+        } else {
+            VerboseFormatter.write_to(&self.view(), f)
         }
-
-        Ok(())
     }
 }
 
@@ -241,55 +1022,146 @@ pub struct StructErrorBuilder<T: DomainReason> {
     detail: Option<String>,
     position: Option<String>,
     contexts: Vec<OperationContext>,
+    source: Option<ErrorSource>,
 }
 
 impl<T: DomainReason> StructErrorBuilder<T> {
+    pub fn new(reason: T) -> Self {
+        StructErrorBuilder {
+            reason,
+            detail: None,
+            position: None,
+            contexts: Vec::new(),
+            source: None,
+        }
+    }
+
+    #[must_use]
     pub fn detail(mut self, detail: impl Into<String>) -> Self {
         self.detail = Some(detail.into());
         self
     }
 
+    #[must_use]
     pub fn position(mut self, position: impl Into<String>) -> Self {
         self.position = Some(position.into());
         self
     }
 
+    #[must_use]
     pub fn context(mut self, ctx: OperationContext) -> Self {
         self.contexts.push(ctx);
         self
     }
 
+    #[must_use]
     pub fn context_ref(mut self, ctx: &OperationContext) -> Self {
         self.contexts.push(ctx.clone());
         self
     }
 
+    #[must_use]
+    pub fn source<E: std::error::Error + Send + Sync + 'static>(mut self, err: E) -> Self {
+        self.source = Some(ErrorSource::new(err));
+        self
+    }
+
+    #[must_use]
+    pub fn build(self) -> StructError<T> {
+        let mut err = StructError::new(self.reason, self.detail, self.position, self.contexts);
+        err.body_mut().source = self.source;
+        err
+    }
+
+    #[must_use]
     pub fn finish(self) -> StructError<T> {
-        StructError::new(self.reason, self.detail, self.position, self.contexts)
+        self.build()
+    }
+}
+
+/// 位置轨迹最多保留的标注点数，超出时丢弃最早的
+const MAX_POSITION_TRAIL: usize = 8;
+
+impl ErrorBody {
+    /// 记录一个标注点：尚无位置时作为首个位置，否则追加到轨迹（跳过与上一个相同的位置）
+    fn mark_position(&mut self, pos: String) {
+        let Some(first) = &self.position else {
+            self.position = Some(pos);
+            return;
+        };
+        if self.position_trail.last().unwrap_or(first) == &pos {
+            return;
+        }
+        if self.position_trail.len() >= MAX_POSITION_TRAIL {
+            self.position_trail.remove(0);
+        }
+        self.position_trail.push(pos);
     }
 }
 
+/// `#[track_caller]` 捕获的调用位置，格式与 `location!()` 一致
+#[track_caller]
+fn caller_location() -> String {
+    let loc = std::panic::Location::caller();
+    format!("{}:{}:{}", loc.file(), loc.line(), loc.column())
+}
+
 impl<T: DomainReason> ErrorWith for StructError<T> {
+    #[track_caller]
     fn want<S: Into<String>>(mut self, desc: S) -> Self {
         let desc = desc.into();
-        let ctx_stack = Arc::make_mut(&mut self.imp.context);
-        if ctx_stack.is_empty() {
-            ctx_stack.push(OperationContext::want(desc));
-        } else if let Some(x) = ctx_stack.last_mut() {
-            x.with_want(desc);
+        let here = caller_location();
+        let body = self.body_mut();
+        body.mark_position(here.clone());
+        let ctx_stack = Arc::make_mut(&mut body.context);
+        match ctx_stack.last_mut() {
+            Some(x) if want_mode() == WantMode::Replace || x.target().is_none() => {
+                x.with_want(desc);
+                x.set_location(here);
+            }
+            _ => {
+                let mut ctx = OperationContext::want(desc);
+                ctx.set_location(here);
+                ctx_stack.push(ctx);
+            }
         }
         self
     }
     fn position<S: Into<String>>(mut self, pos: S) -> Self {
-        self.imp.position = Some(pos.into());
+        self.body_mut().mark_position(pos.into());
         self
     }
 
+    #[track_caller]
     fn with<C: Into<OperationContext>>(mut self, ctx: C) -> Self {
-        let ctx = ctx.into();
+        let mut ctx = ctx.into();
+        let here = caller_location();
+        if ctx.location().is_none() {
+            ctx.set_location(here.clone());
+        }
+        self.body_mut().mark_position(here);
         self.add_context(ctx);
         self
     }
+
+    /// 键值写入最近的上下文帧；尚无上下文时新建一帧
+    fn with_all<I, K, V>(mut self, items: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: IntoContextKey,
+        V: Display,
+    {
+        let ctx_stack = Arc::make_mut(&mut self.body_mut().context);
+        if ctx_stack.is_empty() {
+            ctx_stack.push(OperationContext::new());
+        }
+        if let Some(ctx) = ctx_stack.last_mut() {
+            for (k, v) in items {
+                ctx.record(k, v.to_string());
+            }
+        }
+        self
+    }
 }
 
 #[cfg(all(test, feature = "serde"))]
@@ -302,7 +1174,7 @@ mod tests {
 
     // Define a simple DomainReason for testing
     #[derive(Debug, Clone, PartialEq, Error, From)]
-    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     enum TestDomainReason {
         #[error("test error")]
         TestError,
@@ -323,12 +1195,8 @@ mod tests {
     fn test_struct_error_serialization() {
         // Create a context
         let mut context = CallContext::default();
-        context
-            .items
-            .push(("key1".to_string(), "value1".to_string()));
-        context
-            .items
-            .push(("key2".to_string(), "value2".to_string()));
+        context.items.push(("key1".into(), "value1".to_string()));
+        context.items.push(("key2".into(), "value2".to_string()));
 
         // Create a StructError
         let error = StructError::new(
@@ -342,4 +1210,82 @@ mod tests {
         let json_value = serde_json::to_value(&error).unwrap();
         println!("{json_value:#}");
     }
+
+    #[test]
+    fn test_target_serialized_separately() {
+        let err = StructError::from(TestDomainReason::TestError).with_target("inventory");
+        let json = serde_json::to_value(&err).unwrap();
+        assert_eq!(json["target"], "inventory");
+        let json = serde_json::to_value(StructError::from(TestDomainReason::TestError)).unwrap();
+        assert!(json.get("target").is_none());
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_payload_round_trip() {
+        let payload = serde_json::json!({"fields": ["name", "email"], "order_id": 42});
+        let err = StructError::from(TestDomainReason::TestError).with_payload(payload.clone());
+        assert_eq!(err.payload(), Some(&payload));
+
+        let json = serde_json::to_value(&err).unwrap();
+        assert_eq!(json["payload"], payload);
+        let back: StructError<TestDomainReason> = serde_json::from_value(json).unwrap();
+        assert_eq!(back.payload(), Some(&payload));
+
+        let json = serde_json::to_value(StructError::from(TestDomainReason::TestError)).unwrap();
+        assert!(json.get("payload").is_none());
+    }
+
+    #[test]
+    fn test_schema_version_round_trip() {
+        let err = StructError::from(TestDomainReason::from(UvsReason::not_found_resource(
+            "order", "A-1",
+        )))
+        .with_detail("missing")
+        .with_position("src/order.rs:1:1")
+        .with_source(std::io::Error::other("disk gone"))
+        .with_target("load order");
+        let json = serde_json::to_value(&err).unwrap();
+        assert_eq!(json["schema_version"], ERROR_SCHEMA_VERSION);
+
+        let back: StructError<TestDomainReason> = serde_json::from_value(json).unwrap();
+        assert_eq!(back.reason(), err.reason());
+        assert_eq!(back.detail(), err.detail());
+        assert_eq!(back.target().as_deref(), Some("load order"));
+        assert_eq!(back.error_source().unwrap().to_string(), "disk gone");
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_deserialize_any_version() {
+        // 引入版本号之前的载荷
+        let v1 = serde_json::json!({
+            "reason": "TestError",
+            "detail": "legacy",
+            "position": null,
+            "context": null,
+        });
+        let err = StructError::<TestDomainReason>::deserialize_any_version(v1).unwrap();
+        assert_eq!(err.reason(), &TestDomainReason::TestError);
+        assert_eq!(err.detail().as_deref(), Some("legacy"));
+        assert!(err.contexts().is_empty());
+
+        let v2 = serde_json::json!({
+            "reason": {"Uvs": "NetworkError"},
+            "schema_version": 2,
+            "timestamp": "2024-03-01T00:50:45.123Z",
+            "context": [],
+        });
+        let err = StructError::<TestDomainReason>::deserialize_any_version(v2).unwrap();
+        assert_eq!(
+            err.timestamp(),
+            Some(UNIX_EPOCH + std::time::Duration::from_millis(1_709_254_245_123))
+        );
+
+        let future = serde_json::json!({"reason": "TestError", "schema_version": 99});
+        let msg = StructError::<TestDomainReason>::deserialize_any_version(future)
+            .unwrap_err()
+            .to_string();
+        assert!(msg.contains("unsupported error schema_version 99"));
+    }
 }
@@ -0,0 +1,67 @@
+//! 以 YAML / TOML 输出错误，便于配置类 CLI 以用户熟悉的格式回显
+
+use super::{domain::DomainReason, StructError, UvsReason};
+
+#[cfg(feature = "yaml")]
+impl<T: DomainReason + serde::Serialize> StructError<T> {
+    /// 序列化为 YAML 文档，字段与 JSON 序列化结果一致
+    ///
+    /// serde_yaml 以标签表示枚举且不支持嵌套枚举（如 `ConfigError(MissingKey {..})`），
+    /// 因此先转为 JSON 值，枚举统一输出为 `{Variant: ..}` 映射。
+    pub fn to_yaml(&self) -> Result<String, StructError<UvsReason>> {
+        let value = serde_json::to_value(self).map_err(export_error)?;
+        serde_yaml::to_string(&value).map_err(export_error)
+    }
+}
+
+#[cfg(feature = "toml")]
+impl<T: DomainReason + serde::Serialize> StructError<T> {
+    /// 序列化为 TOML 文档；值为空的字段省略
+    pub fn to_toml(&self) -> Result<String, StructError<UvsReason>> {
+        toml::to_string(self).map_err(export_error)
+    }
+}
+
+fn export_error(e: impl std::fmt::Display) -> StructError<UvsReason> {
+    StructError::from(UvsReason::data_error()).with_detail(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ConfErrReason, ContextRecord, ErrorWith, OperationContext, StructError, UvsReason,
+    };
+
+    fn sample() -> StructError<UvsReason> {
+        let mut ctx = OperationContext::want("load config");
+        ctx.record("file", "app.toml");
+        StructError::from(UvsReason::ConfigError(ConfErrReason::missing_key(
+            "app.toml", "db.url",
+        )))
+        .with_detail("db.url is required")
+        .with(ctx)
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_to_yaml() {
+        let yaml = sample().to_yaml().unwrap();
+        assert!(yaml.contains("detail: db.url is required"));
+        assert!(yaml.contains("target: load config"));
+        let back: serde_yaml::Value = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(back["reason"]["ConfigError"]["MissingKey"]["key"], "db.url");
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_to_toml() {
+        let text = sample().to_toml().unwrap();
+        assert!(text.contains("detail = \"db.url is required\""));
+        let back: toml::Value = toml::from_str(&text).unwrap();
+        assert_eq!(back["context"][0]["target"].as_str(), Some("load config"));
+        // `with` 未显式设置位置时记录调用点
+        assert!(back["position"]
+            .as_str()
+            .is_some_and(|p| p.starts_with("src/core/export.rs:")));
+    }
+}
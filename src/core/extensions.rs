@@ -0,0 +1,209 @@
+//! 按类型存取的附加数据，供中间件在不修改原因类型的情况下跨层传递提示
+
+use std::{
+    any::{type_name, Any, TypeId},
+    collections::HashMap,
+    fmt,
+    time::Duration,
+};
+
+use super::{domain::DomainReason, StructError};
+
+/// 以类型为键的附加数据表（类似 `http::Extensions`），每种类型最多一个值；
+/// 不参与序列化与相等比较
+#[derive(Clone, Default)]
+pub struct Extensions {
+    map: HashMap<TypeId, Box<dyn AnyClone>>,
+}
+
+impl Extensions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 插入值，返回同类型的旧值
+    pub fn insert<V: Clone + Send + Sync + 'static>(&mut self, val: V) -> Option<V> {
+        self.map
+            .insert(TypeId::of::<V>(), Box::new(val))
+            .and_then(|old| old.into_any().downcast().ok().map(|b| *b))
+    }
+
+    pub fn get<V: 'static>(&self) -> Option<&V> {
+        self.map
+            .get(&TypeId::of::<V>())
+            .and_then(|v| (**v).as_any().downcast_ref())
+    }
+
+    pub fn get_mut<V: 'static>(&mut self) -> Option<&mut V> {
+        self.map
+            .get_mut(&TypeId::of::<V>())
+            .and_then(|v| (**v).as_any_mut().downcast_mut())
+    }
+
+    pub fn remove<V: 'static>(&mut self) -> Option<V> {
+        self.map
+            .remove(&TypeId::of::<V>())
+            .and_then(|old| old.into_any().downcast().ok().map(|b| *b))
+    }
+
+    pub fn contains<V: 'static>(&self) -> bool {
+        self.map.contains_key(&TypeId::of::<V>())
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+}
+
+impl PartialEq for Extensions {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl fmt::Debug for Extensions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set()
+            .entries(self.map.values().map(|v| (**v).type_label()))
+            .finish()
+    }
+}
+
+/// 服务端建议的重试间隔，通常由限流、资源不足或外部服务错误携带；
+/// 重试执行器至少等待该时间，HTTP 响应输出为 `Retry-After` 头
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryAfter(pub Duration);
+
+impl<T: DomainReason> StructError<T> {
+    #[must_use]
+    pub fn with_retry_after(self, after: Duration) -> Self {
+        self.with_extension(RetryAfter(after))
+    }
+
+    pub fn retry_after(&self) -> Option<Duration> {
+        self.extension::<RetryAfter>().map(|r| r.0)
+    }
+
+    /// `Retry-After` 头的取值：秒数，不足一秒向上取整
+    pub fn retry_after_header(&self) -> Option<String> {
+        self.retry_after().map(|d| {
+            let secs = d.as_secs() + u64::from(d.subsec_nanos() > 0);
+            secs.to_string()
+        })
+    }
+}
+
+trait AnyClone: Any + Send + Sync {
+    fn clone_box(&self) -> Box<dyn AnyClone>;
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+    fn type_label(&self) -> &'static str;
+}
+
+impl<V: Clone + Send + Sync + 'static> AnyClone for V {
+    fn clone_box(&self) -> Box<dyn AnyClone> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+
+    fn type_label(&self) -> &'static str {
+        type_name::<V>()
+    }
+}
+
+impl Clone for Box<dyn AnyClone> {
+    fn clone(&self) -> Self {
+        (**self).clone_box()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct TraceId(String);
+
+    #[test]
+    fn test_typed_insert_get_remove() {
+        let mut ext = Extensions::new();
+        assert!(ext.is_empty());
+        assert_eq!(ext.insert(TraceId("t-1".into())), None);
+        assert_eq!(ext.insert(3u16), None);
+        assert_eq!(
+            ext.insert(TraceId("t-2".into())),
+            Some(TraceId("t-1".into()))
+        );
+        assert_eq!(ext.len(), 2);
+
+        *ext.get_mut::<u16>().unwrap() += 1;
+        assert_eq!(ext.get::<u16>(), Some(&4));
+        assert!(!ext.contains::<u32>());
+
+        let copy = ext.clone();
+        assert_eq!(ext.remove::<TraceId>(), Some(TraceId("t-2".into())));
+        assert_eq!(ext.get::<TraceId>(), None);
+        assert_eq!(copy.get::<TraceId>(), Some(&TraceId("t-2".into())));
+    }
+
+    #[test]
+    fn test_retry_after_header() {
+        use crate::{StructError, UvsReason};
+
+        let err = StructError::from(UvsReason::resource_error())
+            .with_retry_after(Duration::from_millis(1500));
+        assert_eq!(err.retry_after(), Some(Duration::from_millis(1500)));
+        assert_eq!(err.retry_after_header().as_deref(), Some("2"));
+        let err = err.with_retry_after(Duration::from_secs(30));
+        assert_eq!(err.retry_after_header().as_deref(), Some("30"));
+        assert_eq!(
+            StructError::from(UvsReason::external_error()).retry_after(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_error_extensions_cross_layers() {
+        use crate::{StructError, UvsReason};
+        use std::time::Duration;
+
+        #[derive(Debug, Clone, PartialEq, thiserror::Error, derive_more::From)]
+        enum ApiReason {
+            #[error("{0}")]
+            Uvs(UvsReason),
+        }
+
+        let mut err = StructError::from(UvsReason::network_error())
+            .with_extension(Duration::from_secs(3))
+            .with_extension(TraceId("t-9".into()));
+        err.extensions_mut().insert(503u16);
+        let plain = StructError::from(UvsReason::network_error());
+        assert_eq!(err, plain);
+
+        let err: StructError<ApiReason> = err.map_reason(ApiReason::Uvs);
+        assert_eq!(err.extension::<Duration>(), Some(&Duration::from_secs(3)));
+        assert_eq!(err.extension::<u16>(), Some(&503));
+        assert_eq!(err.extensions().len(), 3);
+        assert!(plain.extensions().is_empty());
+    }
+}
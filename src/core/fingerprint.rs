@@ -0,0 +1,150 @@
+//! 错误指纹：将同类错误归为一组，供 Sentry 类系统与限流上报使用
+
+use std::fmt::Display;
+
+use super::{domain::DomainReason, format::ErrorView, ErrorCode, StructError};
+
+/// 指纹算法；与 `ErrorFormatter` 一样基于 `ErrorView`，可按部署需要替换
+pub trait Fingerprint {
+    fn fingerprint(&self, view: &ErrorView<'_>) -> u64;
+}
+
+/// 默认指纹：错误码、原因与 `detail`，其中数字、十六进制值与 UUID 被归一化，跨进程与版本稳定
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultFingerprint;
+
+impl Fingerprint for DefaultFingerprint {
+    fn fingerprint(&self, view: &ErrorView<'_>) -> u64 {
+        let mut h = Fnv64::new();
+        h.write(&view.code.to_le_bytes());
+        h.write(normalize_text(&view.reason).as_bytes());
+        h.write(&[0xff]);
+        if let Some(detail) = view.detail {
+            h.write(normalize_text(detail).as_bytes());
+        }
+        h.finish()
+    }
+}
+
+impl<T: DomainReason + ErrorCode + Display> StructError<T> {
+    /// 默认指纹，可格式化为 `{:016x}` 作为分组键
+    pub fn fingerprint(&self) -> u64 {
+        self.fingerprint_with(&DefaultFingerprint)
+    }
+
+    pub fn fingerprint_with(&self, fp: &dyn Fingerprint) -> u64 {
+        fp.fingerprint(&self.view())
+    }
+}
+
+/// FNV-1a：实现简单且结果不随 Rust 版本变化（`DefaultHasher` 不保证这一点）
+struct Fnv64(u64);
+
+impl Fnv64 {
+    fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for b in bytes {
+            self.0 ^= u64::from(*b);
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// 归一化易变片段：UUID 替换为 `<uuid>`，十进制与 `0x` 十六进制数字串替换为 `#`
+pub fn normalize_text(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut out = String::with_capacity(text.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if is_uuid_at(bytes, i) {
+            out.push_str("<uuid>");
+            i += 36;
+        } else if bytes[i].is_ascii_digit() {
+            if bytes[i] == b'0' && matches!(bytes.get(i + 1), Some(b'x' | b'X')) {
+                i += 2;
+                while i < bytes.len() && bytes[i].is_ascii_hexdigit() {
+                    i += 1;
+                }
+            } else {
+                while i < bytes.len() && bytes[i].is_ascii_digit() {
+                    i += 1;
+                }
+            }
+            out.push('#');
+        } else {
+            let ch = text[i..].chars().next().unwrap_or_default();
+            out.push(ch);
+            i += ch.len_utf8().max(1);
+        }
+    }
+    out
+}
+
+fn is_uuid_at(bytes: &[u8], at: usize) -> bool {
+    let Some(s) = bytes.get(at..at + 36) else {
+        return false;
+    };
+    let boundary = at == 0 || !bytes[at - 1].is_ascii_alphanumeric();
+    boundary
+        && s.iter().enumerate().all(|(i, b)| match i {
+            8 | 13 | 18 | 23 => *b == b'-',
+            _ => b.is_ascii_hexdigit(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ErrorWith, UvsReason};
+
+    #[test]
+    fn test_normalize_text() {
+        assert_eq!(
+            normalize_text("user 42 at 0x7ffe12 req 550e8400-e29b-41d4-a716-446655440000 done"),
+            "user # at # req <uuid> done"
+        );
+        assert_eq!(normalize_text("订单 1001 不存在"), "订单 # 不存在");
+    }
+
+    #[test]
+    fn test_fingerprint_groups_variable_detail() {
+        let a = StructError::from(UvsReason::not_found_error()).with_detail("order 1001 missing");
+        let b = StructError::from(UvsReason::not_found_error())
+            .with_detail("order 2002 missing")
+            .want("lookup");
+        let c = StructError::from(UvsReason::not_found_error()).with_detail("user 1 missing");
+        assert_eq!(a.fingerprint(), b.fingerprint());
+        assert_ne!(a.fingerprint(), c.fingerprint());
+        assert_ne!(
+            a.fingerprint(),
+            StructError::from(UvsReason::validation_error())
+                .with_detail("order 1001 missing")
+                .fingerprint()
+        );
+    }
+
+    #[test]
+    fn test_fingerprint_is_stable() {
+        let err = StructError::from(UvsReason::network_error()).with_detail("peer 10.0.0.1 reset");
+        assert_eq!(format!("{:016x}", err.fingerprint()), "58d01aea1d8b4e93");
+    }
+
+    #[test]
+    fn test_custom_fingerprint() {
+        struct ByCode;
+        impl Fingerprint for ByCode {
+            fn fingerprint(&self, view: &ErrorView<'_>) -> u64 {
+                view.code as u64
+            }
+        }
+        let err = StructError::from(UvsReason::network_error());
+        assert_eq!(err.fingerprint_with(&ByCode), 202);
+    }
+}
@@ -0,0 +1,810 @@
+use std::{
+    fmt::{self, Display, Write},
+    time::SystemTime,
+};
+
+use super::{
+    config::{error_config, AppInfo, TimestampFormat},
+    context::OperationContext,
+    domain::DomainReason,
+    error::short_type_name,
+    limit::error_limits,
+    reason::DomainCode,
+    ErrorCode, StructError,
+};
+
+/// 错误渲染视图：与具体 reason 类型解耦，供 `ErrorFormatter` 使用
+#[derive(Debug, Clone)]
+pub struct ErrorView<'a> {
+    pub code: i32,
+    /// 错误码命名空间，设置后错误码渲染为 `NS-0101`
+    pub domain: Option<String>,
+    pub reason: String,
+    pub detail: Option<&'a str>,
+    pub position: Option<&'a str>,
+    /// `position` 之后各层的标注点，最早的在前
+    pub position_trail: &'a [String],
+    pub target: Option<String>,
+    pub contexts: &'a [OperationContext],
+    pub id: Option<&'a str>,
+    pub correlation_id: Option<&'a str>,
+    /// reason 的类型名（不含模块路径）
+    pub reason_type: &'static str,
+    /// 转换前的原因，最早的在前，见 `conversion_trail`
+    pub conversions: &'a [String],
+    /// 最多显示的上下文帧数，默认取全局 `ErrorLimits::max_context_depth`
+    pub max_depth: Option<usize>,
+    /// 应用构建信息，见 `ErrorConfig::set_app_info`
+    pub app: Option<&'a AppInfo>,
+    /// 创建时间，见 `ErrorConfig::capture_timestamp`
+    pub timestamp: Option<SystemTime>,
+    /// 时间戳渲染格式，默认取全局 `ErrorConfig::timestamp_format`
+    pub timestamp_format: TimestampFormat,
+}
+
+impl<T: DomainReason + ErrorCode + Display> StructError<T> {
+    /// 构建渲染视图
+    pub fn view(&self) -> ErrorView<'_> {
+        ErrorView {
+            code: self.error_code(),
+            domain: None,
+            reason: self.reason().to_string(),
+            detail: self.detail().as_deref(),
+            position: self.position().as_deref(),
+            position_trail: self.position_trail(),
+            target: self.target(),
+            contexts: self.contexts(),
+            id: self.id().as_deref(),
+            correlation_id: self.correlation_id().as_deref(),
+            reason_type: short_type_name::<T>(),
+            conversions: self.conversion_trail(),
+            max_depth: error_limits().max_context_depth,
+            app: self.app_info(),
+            timestamp: self.timestamp(),
+            timestamp_format: error_config().timestamp_format,
+        }
+    }
+
+    /// 使用指定的格式化器渲染错误
+    pub fn format_with(&self, formatter: &dyn ErrorFormatter) -> String {
+        formatter.format(&self.view())
+    }
+
+    /// 单行紧凑格式，等同于 `format!("{err:#}")`，适用于会拆分多行消息的日志系统
+    pub fn to_compact_string(&self) -> String {
+        self.format_with(&CompactFormatter)
+    }
+
+    /// 按 `cols` 列折行的详细格式，等同于 `format!("{err:cols$}")`
+    pub fn format_width(&self, cols: usize) -> String {
+        self.format_with(&WrappedFormatter::new(cols))
+    }
+
+    /// 适用于快照测试的稳定渲染：位置、错误 ID 等随运行变化的字段替换为占位符，时间与构建信息省略
+    pub fn to_stable_string(&self) -> String {
+        let contexts: Vec<OperationContext> = self
+            .contexts()
+            .iter()
+            .map(OperationContext::stable_copy)
+            .collect();
+        let trail = vec!["<position>".to_string(); self.position_trail().len()];
+        let mut view = self.view();
+        view.contexts = &contexts;
+        view.position = view.position.map(|_| "<position>");
+        view.position_trail = &trail;
+        view.id = view.id.map(|_| "<id>");
+        view.correlation_id = view.correlation_id.map(|_| "<correlation-id>");
+        view.timestamp = None;
+        view.app = None;
+        VerboseFormatter.format(&view)
+    }
+}
+
+impl<T: DomainReason + DomainCode + Display> StructError<T> {
+    /// 组合错误码，例如 `ORD-0101`
+    pub fn domain_code(&self) -> String {
+        self.reason().domain_code()
+    }
+
+    /// 带命名空间的渲染视图
+    pub fn domain_view(&self) -> ErrorView<'_> {
+        let mut view = self.view();
+        view.domain = Some(self.reason().domain().to_string());
+        view
+    }
+}
+
+/// 渲染用的上下文帧：超出 `max_depth` 的中间帧折叠为一条省略标记
+pub(crate) enum ContextFrame<'a> {
+    Frame(usize, &'a OperationContext),
+    Omitted(usize),
+}
+
+impl<'a> ErrorView<'a> {
+    /// 按 `max_depth` 折叠后的上下文帧，保留最外层与最内层，下标为原始位置
+    pub(crate) fn context_frames(&self) -> Vec<ContextFrame<'a>> {
+        let contexts = self.contexts;
+        let mut frames: Vec<ContextFrame<'a>> = Vec::new();
+        match self.max_depth {
+            Some(max) if contexts.len() > max => {
+                let head = max.div_ceil(2);
+                let tail = max / 2;
+                frames.extend(
+                    contexts[..head]
+                        .iter()
+                        .enumerate()
+                        .map(|(i, c)| ContextFrame::Frame(i, c)),
+                );
+                frames.push(ContextFrame::Omitted(contexts.len() - head - tail));
+                let start = contexts.len() - tail;
+                frames.extend(
+                    contexts[start..]
+                        .iter()
+                        .enumerate()
+                        .map(|(i, c)| ContextFrame::Frame(start + i, c)),
+                );
+            }
+            _ => frames.extend(
+                contexts
+                    .iter()
+                    .enumerate()
+                    .map(|(i, c)| ContextFrame::Frame(i, c)),
+            ),
+        }
+        frames
+    }
+}
+
+impl ErrorView<'_> {
+    /// 渲染用的错误码文本：有命名空间时为 `NS-0101`，否则为数字
+    pub fn code_label(&self) -> String {
+        match &self.domain {
+            Some(domain) => format!("{domain}-{:04}", self.code),
+            None => self.code.to_string(),
+        }
+    }
+}
+
+/// 错误格式化器：不同部署目标（终端、journald、k8s 日志）可选择不同的渲染方式
+pub trait ErrorFormatter {
+    fn write_to(&self, view: &ErrorView<'_>, out: &mut dyn Write) -> fmt::Result;
+
+    fn format(&self, view: &ErrorView<'_>) -> String {
+        let mut out = String::new();
+        let _ = self.write_to(view, &mut out);
+        out
+    }
+}
+
+/// 多行详细格式，与 `Display` 输出一致
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VerboseFormatter;
+
+impl ErrorFormatter for VerboseFormatter {
+    fn write_to(&self, view: &ErrorView<'_>, out: &mut dyn Write) -> fmt::Result {
+        write_verbose(view, out, &Palette::default())
+    }
+}
+
+/// 详细格式各部分的 ANSI 样式；全空即为纯文本。`VerboseFormatter` 与 `ColorFormatter`
+/// 共用 `write_verbose` 的布局，只在样式上不同
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Palette {
+    pub code: &'static str,
+    pub bold: &'static str,
+    pub position: &'static str,
+    pub target: &'static str,
+    pub detail: &'static str,
+    pub dim: &'static str,
+    pub reset: &'static str,
+}
+
+pub(crate) fn write_verbose(view: &ErrorView<'_>, out: &mut dyn Write, p: &Palette) -> fmt::Result {
+    let Palette {
+        code,
+        bold,
+        position,
+        target: target_style,
+        detail: detail_style,
+        dim,
+        reset,
+    } = *p;
+
+    // 核心错误信息
+    write!(
+        out,
+        "{code}[{}]{reset} {bold}{}{reset}",
+        view.code_label(),
+        view.reason
+    )?;
+
+    // 位置信息优先显示，随后是逐层传播的标注点
+    if let Some(pos) = view.position {
+        write!(out, "\n  -> At: {position}{pos}{reset}")?;
+    }
+    for pos in view.position_trail {
+        write!(out, "\n     {dim}via{reset} {position}{pos}{reset}")?;
+    }
+
+    // 目标资源信息
+    if let Some(target) = &view.target {
+        write!(out, "\n  -> Want: {target_style}{target}{reset}")?;
+    }
+
+    // 技术细节
+    if let Some(detail) = view.detail {
+        write!(out, "\n  -> Details: {detail_style}{detail}{reset}")?;
+    }
+
+    // 跨层转换轨迹，自外向内
+    if !view.conversions.is_empty() {
+        write!(out, "\n  -> Converted:")?;
+        write!(
+            out,
+            "\n     {bold}{}{reset}: {}",
+            view.reason_type, view.reason
+        )?;
+        for crumb in view.conversions.iter().rev() {
+            write!(out, "\n     {dim}<-{reset} {crumb}")?;
+        }
+    }
+
+    // 错误 ID 与关联 ID
+    if let Some(id) = view.id {
+        write!(out, "\n  -> Error ID: {bold}{id}{reset}")?;
+    }
+    if let Some(cid) = view.correlation_id {
+        write!(out, "\n  -> Correlation ID: {cid}")?;
+    }
+    if let Some(ts) = view.timestamp {
+        write!(out, "\n  -> Time: {}", view.timestamp_format.format(ts))?;
+    }
+
+    // 上下文信息
+    if !view.contexts.is_empty() {
+        writeln!(out, "\n  -> {dim}Context stack:{reset}")?;
+
+        for frame in view.context_frames() {
+            match frame {
+                ContextFrame::Frame(i, c) => {
+                    writeln!(out, "{dim}context {i}: {reset}")?;
+                    writeln!(out, "{c}")?;
+                }
+                ContextFrame::Omitted(n) => writeln!(out, "{dim}… {n} frames omitted …{reset}\n")?,
+            }
+        }
+    }
+
+    // 构建信息作为页脚
+    if let Some(app) = view.app {
+        if view.contexts.is_empty() {
+            out.write_char('\n')?;
+        }
+        write!(out, "  -> {dim}Build:{reset} {app}")?;
+    }
+
+    Ok(())
+}
+
+/// 按列宽折行的详细格式：长 `detail`、路径与 JSON 续行对齐到值所在列，上下文键对齐；
+/// 等同于 `format!("{err:80}")`。宽度按字符数计算
+#[derive(Debug, Clone, Copy)]
+pub struct WrappedFormatter {
+    pub width: usize,
+}
+
+impl WrappedFormatter {
+    pub fn new(width: usize) -> Self {
+        Self { width }
+    }
+
+    fn field(&self, out: &mut dyn Write, label: &str, value: &str) -> fmt::Result {
+        let prefix = format!("\n  -> {label}: ");
+        write_wrapped(out, &prefix, value, self.width)
+    }
+
+    fn frame(&self, out: &mut dyn Write, ctx: &OperationContext, depth: usize) -> fmt::Result {
+        let pad = "  ".repeat(depth);
+        if let Some(target) = ctx.target() {
+            write_wrapped(out, &format!("{pad}target: "), target, self.width)?;
+            out.write_char('\n')?;
+        }
+        if let Some(location) = ctx.location() {
+            write_wrapped(out, &format!("{pad}at: "), location, self.width)?;
+            out.write_char('\n')?;
+        }
+        let items = ctx.context().render_items();
+        let key_width = items
+            .iter()
+            .map(|(k, _)| k.chars().count())
+            .max()
+            .unwrap_or(0);
+        let num_width = items.len().to_string().len();
+        for (i, (k, v)) in items.into_iter().enumerate() {
+            let prefix = format!("{pad}{:>num_width$}. {k:<key_width$} : ", i + 1);
+            write_wrapped(out, &prefix, v, self.width)?;
+            out.write_char('\n')?;
+        }
+        for child in ctx.children() {
+            self.frame(out, child, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl ErrorFormatter for WrappedFormatter {
+    fn write_to(&self, view: &ErrorView<'_>, out: &mut dyn Write) -> fmt::Result {
+        let head = format!("[{}] ", view.code_label());
+        write_wrapped(out, &head, &view.reason, self.width)?;
+        if let Some(pos) = view.position {
+            self.field(out, "At", pos)?;
+        }
+        for pos in view.position_trail {
+            write_wrapped(out, "\n     via ", pos, self.width)?;
+        }
+        if let Some(target) = &view.target {
+            self.field(out, "Want", target)?;
+        }
+        if let Some(detail) = view.detail {
+            self.field(out, "Details", detail)?;
+        }
+        if !view.conversions.is_empty() {
+            write!(out, "\n  -> Converted:")?;
+            let current = format!("{}: {}", view.reason_type, view.reason);
+            write_wrapped(out, "\n     ", &current, self.width)?;
+            for crumb in view.conversions.iter().rev() {
+                write_wrapped(out, "\n     <- ", crumb, self.width)?;
+            }
+        }
+        if let Some(id) = view.id {
+            self.field(out, "Error ID", id)?;
+        }
+        if let Some(cid) = view.correlation_id {
+            self.field(out, "Correlation ID", cid)?;
+        }
+        if let Some(ts) = view.timestamp {
+            self.field(out, "Time", &view.timestamp_format.format(ts))?;
+        }
+        if !view.contexts.is_empty() {
+            writeln!(out, "\n  -> Context stack:")?;
+            for frame in view.context_frames() {
+                match frame {
+                    ContextFrame::Frame(i, c) => {
+                        writeln!(out, "context {i}: ")?;
+                        self.frame(out, c, 0)?;
+                        out.write_char('\n')?;
+                    }
+                    ContextFrame::Omitted(n) => writeln!(out, "… {n} frames omitted …\n")?,
+                }
+            }
+        }
+        if let Some(app) = view.app {
+            if view.contexts.is_empty() {
+                out.write_char('\n')?;
+            }
+            write_wrapped(out, "  -> Build: ", &app.to_string(), self.width)?;
+        }
+        Ok(())
+    }
+}
+
+/// 写出 `prefix` 与折行后的 `text`，续行缩进到 `prefix` 末尾所在列；
+/// 过窄时至少保留 20 列，过长且无空白的片段（路径、JSON）按字符硬切
+fn write_wrapped(out: &mut dyn Write, prefix: &str, text: &str, width: usize) -> fmt::Result {
+    let indent = prefix.rsplit('\n').next().unwrap_or(prefix).chars().count();
+    let avail = width.saturating_sub(indent).max(20);
+    out.write_str(prefix)?;
+    for (i, line) in wrap_lines(text, avail).iter().enumerate() {
+        if i > 0 {
+            write!(out, "\n{:indent$}", "")?;
+        }
+        out.write_str(line)?;
+    }
+    Ok(())
+}
+
+fn wrap_lines(text: &str, avail: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for para in text.split('\n') {
+        let mut line = String::new();
+        let mut len = 0;
+        for mut word in para.split_whitespace() {
+            let word_len = word.chars().count();
+            if len > 0 && len + 1 + word_len <= avail {
+                line.push(' ');
+                line.push_str(word);
+                len += 1 + word_len;
+                continue;
+            }
+            if len > 0 {
+                lines.push(std::mem::take(&mut line));
+            }
+            while let Some((cut, _)) = word.char_indices().nth(avail) {
+                lines.push(word[..cut].to_string());
+                word = &word[cut..];
+            }
+            line.push_str(word);
+            len = word.chars().count();
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// 单行紧凑格式，适用于会拆分多行消息的日志系统
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CompactFormatter;
+
+impl ErrorFormatter for CompactFormatter {
+    fn write_to(&self, view: &ErrorView<'_>, out: &mut dyn Write) -> fmt::Result {
+        write!(out, "[{}] {}", view.code_label(), one_line(&view.reason))?;
+        if let Some(detail) = view.detail {
+            write!(out, " > {}", one_line(detail))?;
+        }
+        if let Some(target) = &view.target {
+            write!(out, " | want={}", one_line(target))?;
+        }
+        if let Some(pos) = view.position {
+            write!(out, " | at={}", one_line(pos))?;
+        }
+        if let Some(id) = view.id {
+            write!(out, " | id={id}")?;
+        }
+        if let Some(cid) = view.correlation_id {
+            write!(out, " | cid={}", one_line(cid))?;
+        }
+        for ctx in view.contexts {
+            for (k, v) in ctx.context().items.iter() {
+                write!(out, " | {}={}", one_line(k), one_line(v))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// JSON 格式（单行），不依赖 serde
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonFormatter;
+
+impl ErrorFormatter for JsonFormatter {
+    fn write_to(&self, view: &ErrorView<'_>, out: &mut dyn Write) -> fmt::Result {
+        write!(out, "{{\"code\":{},", view.code)?;
+        if let Some(domain) = &view.domain {
+            out.write_str("\"domain\":")?;
+            write_json_str(out, domain)?;
+            out.write_char(',')?;
+        }
+        out.write_str("\"reason\":")?;
+        write_json_str(out, &view.reason)?;
+        out.write_str(",\"detail\":")?;
+        write_json_opt(out, view.detail)?;
+        out.write_str(",\"position\":")?;
+        write_json_opt(out, view.position)?;
+        if !view.position_trail.is_empty() {
+            out.write_str(",\"position_trail\":[")?;
+            for (i, pos) in view.position_trail.iter().enumerate() {
+                if i > 0 {
+                    out.write_char(',')?;
+                }
+                write_json_str(out, pos)?;
+            }
+            out.write_char(']')?;
+        }
+        out.write_str(",\"target\":")?;
+        write_json_opt(out, view.target.as_deref())?;
+        if let Some(id) = view.id {
+            out.write_str(",\"id\":")?;
+            write_json_str(out, id)?;
+        }
+        if let Some(cid) = view.correlation_id {
+            out.write_str(",\"correlation_id\":")?;
+            write_json_str(out, cid)?;
+        }
+        if let Some(ts) = view.timestamp {
+            out.write_str(",\"timestamp\":")?;
+            let ts = view.timestamp_format.format(ts);
+            match view.timestamp_format {
+                TimestampFormat::UnixMillis => out.write_str(&ts)?,
+                _ => write_json_str(out, &ts)?,
+            }
+        }
+        out.write_str(",\"context\":")?;
+        write_json_contexts(out, view.contexts)?;
+        if let Some(app) = view.app {
+            out.write_str(",\"app\":{\"name\":")?;
+            write_json_str(out, &app.name)?;
+            out.write_str(",\"version\":")?;
+            write_json_str(out, &app.version)?;
+            if let Some(sha) = &app.git_sha {
+                out.write_str(",\"git_sha\":")?;
+                write_json_str(out, sha)?;
+            }
+            out.write_char('}')?;
+        }
+        out.write_char('}')
+    }
+}
+
+/// 上下文数组：`[{"target":..,"items":[[k,v]],"children":[..]}]`，无子上下文时省略 `children`
+pub(crate) fn write_json_contexts(
+    out: &mut dyn Write,
+    contexts: &[OperationContext],
+) -> fmt::Result {
+    out.write_char('[')?;
+    for (i, ctx) in contexts.iter().enumerate() {
+        if i > 0 {
+            out.write_char(',')?;
+        }
+        out.write_str("{\"target\":")?;
+        write_json_opt(out, ctx.target().as_deref())?;
+        out.write_str(",\"items\":[")?;
+        for (j, (k, v)) in ctx.context().items.iter().enumerate() {
+            if j > 0 {
+                out.write_char(',')?;
+            }
+            out.write_char('[')?;
+            write_json_str(out, k)?;
+            out.write_char(',')?;
+            write_json_str(out, v)?;
+            out.write_char(']')?;
+        }
+        out.write_char(']')?;
+        if !ctx.children().is_empty() {
+            out.write_str(",\"children\":")?;
+            write_json_contexts(out, ctx.children())?;
+        }
+        out.write_char('}')?;
+    }
+    out.write_char(']')
+}
+
+fn one_line(s: &str) -> String {
+    s.trim_end().replace(['\n', '\r'], " ")
+}
+
+pub(crate) fn write_json_opt(out: &mut dyn Write, value: Option<&str>) -> fmt::Result {
+    match value {
+        Some(v) => write_json_str(out, v),
+        None => out.write_str("null"),
+    }
+}
+
+pub(crate) fn write_json_str(out: &mut dyn Write, value: &str) -> fmt::Result {
+    out.write_char('"')?;
+    for c in value.chars() {
+        match c {
+            '"' => out.write_str("\\\"")?,
+            '\\' => out.write_str("\\\\")?,
+            '\n' => out.write_str("\\n")?,
+            '\r' => out.write_str("\\r")?,
+            '\t' => out.write_str("\\t")?,
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32)?,
+            c => out.write_char(c)?,
+        }
+    }
+    out.write_char('"')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ContextRecord, ErrorWith, UvsReason};
+
+    fn sample() -> StructError<UvsReason> {
+        let mut ctx = OperationContext::want("database_config");
+        ctx.record("file", "app\"1\".toml");
+        StructError::from(UvsReason::core_conf())
+            .with_detail("missing db\nconfig")
+            .with(ctx)
+            .with_position("src/config.rs:42")
+    }
+
+    #[test]
+    fn test_verbose_formatter_matches_display() {
+        let err = sample();
+        assert_eq!(err.format_with(&VerboseFormatter), err.to_string());
+    }
+
+    #[test]
+    fn test_context_depth_folding() {
+        let mut err = StructError::from(UvsReason::network_error());
+        for i in 0..10 {
+            err = err.with(OperationContext::want(format!("layer{i}")));
+        }
+        let mut view = err.view();
+        view.max_depth = Some(3);
+        let out = VerboseFormatter.format(&view);
+        assert!(out.contains("context 0: \ntarget: layer0"));
+        assert!(out.contains("context 1: \ntarget: layer1"));
+        assert!(out.contains("… 7 frames omitted …"));
+        assert!(out.contains("context 9: \ntarget: layer9"));
+        assert!(!out.contains("layer5"));
+
+        view.max_depth = Some(10);
+        assert!(!VerboseFormatter.format(&view).contains("omitted"));
+    }
+
+    #[test]
+    fn test_wrapped_formatter_aligns_long_values() {
+        let mut ctx = OperationContext::want("load");
+        ctx.record(
+            "path",
+            "/srv/data/orders/2024/archive/very/long/path/order.json",
+        );
+        ctx.record("id", "A-1");
+        let err = StructError::from(UvsReason::core_conf())
+            .with_detail(
+                "unexpected token in payload {\"order\":{\"id\":1,\"items\":[]}} near line 3",
+            )
+            .with(ctx);
+        let out = err.format_width(40);
+        assert_eq!(out, format!("{err:40}"));
+        for line in out.lines() {
+            assert!(line.chars().count() <= 40, "{line}");
+        }
+        // 续行与值所在列对齐，键按最长键对齐
+        assert!(out.contains(
+            "  -> Details: unexpected token in\n              payload\n              {\"order\""
+        ));
+        assert!(out.contains("1. path : /srv/data/orders/2024/archive/\n          very/long"));
+        assert!(out.contains("2. id   : A-1"));
+        // 不指定宽度时保持原有布局
+        assert_eq!(format!("{err}"), err.format_with(&VerboseFormatter));
+    }
+
+    #[test]
+    fn test_ids_rendered() {
+        let err = StructError::from(UvsReason::network_error())
+            .with_error_id("01J0000000000000000000000")
+            .with_correlation_id("req-42");
+        let verbose = err.to_string();
+        assert!(verbose.contains("-> Error ID: 01J0000000000000000000000"));
+        assert!(verbose.contains("-> Correlation ID: req-42"));
+        assert!(err
+            .format_with(&CompactFormatter)
+            .ends_with("| id=01J0000000000000000000000 | cid=req-42"));
+        assert!(err
+            .format_with(&JsonFormatter)
+            .contains(r#""id":"01J0000000000000000000000","correlation_id":"req-42""#));
+    }
+
+    #[derive(Debug, Clone, PartialEq, derive_more::From)]
+    enum OrderReason {
+        Uvs(UvsReason),
+    }
+
+    impl Display for OrderReason {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                OrderReason::Uvs(r) => r.fmt(f),
+            }
+        }
+    }
+
+    impl ErrorCode for OrderReason {
+        fn error_code(&self) -> i32 {
+            match self {
+                OrderReason::Uvs(r) => r.error_code(),
+            }
+        }
+    }
+
+    impl DomainCode for OrderReason {
+        fn domain(&self) -> crate::Namespace {
+            crate::Namespace::Name("ORD")
+        }
+    }
+
+    #[test]
+    fn test_domain_code_rendering() {
+        let err = StructError::from(OrderReason::from(UvsReason::business_error()));
+        assert_eq!(err.domain_code(), "ORD-0101");
+        let view = err.domain_view();
+        assert!(VerboseFormatter
+            .format(&view)
+            .starts_with("[ORD-0101] business logic error"));
+        assert!(JsonFormatter
+            .format(&view)
+            .starts_with(r#"{"code":101,"domain":"ORD","reason":"business logic error""#));
+        // 未指定命名空间时保持纯数字
+        assert!(err.to_string().starts_with("[101]"));
+    }
+
+    #[test]
+    fn test_timestamp_rendering_follows_format() {
+        use std::time::{Duration, UNIX_EPOCH};
+
+        let err = sample();
+        let mut view = err.view();
+        view.timestamp = Some(UNIX_EPOCH + Duration::from_millis(1_709_254_245_123));
+        assert!(VerboseFormatter
+            .format(&view)
+            .contains("-> Time: 1709254245123"));
+        assert!(JsonFormatter
+            .format(&view)
+            .contains(r#""timestamp":1709254245123"#));
+
+        view.timestamp_format = TimestampFormat::Rfc3339;
+        assert!(VerboseFormatter
+            .format(&view)
+            .contains("-> Time: 2024-03-01T00:50:45.123Z"));
+        assert!(JsonFormatter
+            .format(&view)
+            .contains(r#""timestamp":"2024-03-01T00:50:45.123Z""#));
+        // 其余多行格式化器同样遵循时间戳配置
+        assert!(WrappedFormatter::new(80)
+            .format(&view)
+            .contains("-> Time: 2024-03-01T00:50:45.123Z"));
+        let localized = crate::LocalizedFormatter {
+            locale: crate::Locale::Zh,
+        };
+        assert!(localized
+            .format(&view)
+            .contains("-> 时间: 2024-03-01T00:50:45.123Z"));
+        #[cfg(feature = "color")]
+        assert!(crate::ColorFormatter
+            .format(&view)
+            .contains("-> Time: 2024-03-01T00:50:45.123Z"));
+    }
+
+    #[test]
+    fn test_stable_string_normalizes_run_dependent_fields() {
+        let a = sample()
+            .with_error_id("01AAAA")
+            .with_position("src/a.rs:1:1");
+        let b = sample()
+            .with_error_id("01BBBB")
+            .with_position("src/a.rs:9:9");
+        assert_eq!(a.to_stable_string(), b.to_stable_string());
+        assert!(a.to_stable_string().contains("-> At: <position>"));
+        assert!(a.to_stable_string().contains("-> Error ID: <id>"));
+    }
+
+    #[test]
+    fn test_position_trail_rendering() {
+        let err = StructError::from(UvsReason::network_error())
+            .position("src/net.rs:10:5")
+            .position("src/service.rs:20:9")
+            .position("src/handler.rs:30:13");
+        let out = err.format_with(&VerboseFormatter);
+        assert!(out.contains(
+            "-> At: src/net.rs:10:5\n     via src/service.rs:20:9\n     via src/handler.rs:30:13"
+        ));
+        assert!(err
+            .format_with(&JsonFormatter)
+            .contains(r#""position_trail":["src/service.rs:20:9","src/handler.rs:30:13"]"#));
+        let stable = err.to_stable_string();
+        assert_eq!(stable.matches("via <position>").count(), 2);
+        assert!(!stable.contains("handler.rs"));
+    }
+
+    #[test]
+    fn test_compact_formatter_single_line() {
+        let out = sample().format_with(&CompactFormatter);
+        assert_eq!(
+            out,
+            "[300] configuration error << core config > missing db config | want=database_config | at=src/config.rs:42 | file=app\"1\".toml"
+        );
+        assert_eq!(sample().to_compact_string(), out);
+        assert_eq!(format!("{:#}", sample()), out);
+    }
+
+    #[test]
+    fn test_json_formatter_child_contexts() {
+        let mut ctx = OperationContext::want("deploy");
+        ctx.child("build").record("step", "compile");
+        let err = StructError::from(UvsReason::system_error()).with(ctx);
+        assert!(err.format_with(&JsonFormatter).ends_with(
+            r#""context":[{"target":"deploy","items":[],"children":[{"target":"build","items":[["step","compile"]]}]}]}"#
+        ));
+    }
+
+    #[test]
+    fn test_json_formatter_escapes() {
+        let out = sample().format_with(&JsonFormatter);
+        assert_eq!(
+            out,
+            r#"{"code":300,"reason":"configuration error << core config","detail":"missing db\nconfig","position":"src/config.rs:42","target":"database_config","context":[{"target":"database_config","items":[["file","app\"1\".toml"]]}]}"#
+        );
+    }
+}
@@ -0,0 +1,188 @@
+//! `arbitrary::Arbitrary` 实现，供下游用 cargo-fuzz / 属性测试覆盖错误处理与序列化往返
+
+use std::borrow::Cow;
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use super::{
+    AuthErrReason, CallContext, ConfErrReason, ContextRecord, FieldError, OperationContext,
+    ResourceKind, StructError, Upstream, UvsReason,
+};
+
+impl<'a> Arbitrary<'a> for ConfErrReason {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.choose_index(5)? {
+            0 => Self::Core,
+            1 => Self::Feature,
+            2 => Self::Dynamic,
+            3 => Self::MissingKey {
+                file: u.arbitrary()?,
+                key: u.arbitrary()?,
+            },
+            _ => Self::InvalidType {
+                file: u.arbitrary()?,
+                key: u.arbitrary()?,
+                expected: u.arbitrary()?,
+            },
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for AuthErrReason {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.choose_index(2)? {
+            0 => Self::Unauthenticated,
+            _ => Self::Forbidden {
+                required: u.arbitrary()?,
+            },
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for ResourceKind {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(*u.choose(&[
+            Self::Memory,
+            Self::Disk,
+            Self::FileHandles,
+            Self::ConnectionPool,
+        ])?)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Upstream {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            service: u.arbitrary()?,
+            endpoint: u.arbitrary()?,
+            status: u.arbitrary()?,
+            code: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for FieldError {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self::new(
+            u.arbitrary::<String>()?,
+            u.arbitrary::<String>()?,
+            u.arbitrary::<String>()?,
+        ))
+    }
+}
+
+impl<'a> Arbitrary<'a> for UvsReason {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.choose_index(19)? {
+            0 => Self::ValidationError,
+            1 => Self::ValidationFields(u.arbitrary()?),
+            2 => Self::BusinessError,
+            3 => Self::RunRuleError,
+            4 => Self::NotFoundError,
+            5 => Self::NotFoundResource {
+                kind: u.arbitrary()?,
+                id: u.arbitrary()?,
+            },
+            6 => Self::PermissionError,
+            7 => Self::AuthError(u.arbitrary()?),
+            8 => Self::DataError,
+            9 => Self::SystemError,
+            10 => Self::NetworkError,
+            11 => Self::ResourceError,
+            12 => Self::ResourceExhausted {
+                kind: u.arbitrary()?,
+                current: u.arbitrary()?,
+                limit: u.arbitrary()?,
+            },
+            13 => Self::TimeoutError,
+            14 => Self::TimeoutAfter {
+                op: u.arbitrary()?,
+                elapsed: u.arbitrary()?,
+                limit: u.arbitrary()?,
+            },
+            15 => Self::ConfigError(u.arbitrary()?),
+            16 => Self::ExternalError,
+            17 => Self::UpstreamError(u.arbitrary()?),
+            _ => Self::LogicError,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for CallContext {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let items = u.arbitrary::<Vec<(String, String)>>()?;
+        Ok(Self {
+            items: items.into_iter().map(|(k, v)| (Cow::Owned(k), v)).collect(),
+        })
+    }
+}
+
+/// 随机的原因、detail、位置与上下文帧；构造时的 ID、时间戳等仍按全局配置生成
+impl<'a> Arbitrary<'a> for StructError<UvsReason> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let reason = u.arbitrary()?;
+        let detail = u.arbitrary()?;
+        let position = u.arbitrary()?;
+        let mut frames = Vec::new();
+        for _ in 0..u.int_in_range(0..=3)? {
+            let mut frame = OperationContext::want(u.arbitrary::<String>()?);
+            for (key, val) in u.arbitrary::<CallContext>()?.items {
+                frame.record(key, val);
+            }
+            frames.push(frame);
+        }
+        Ok(StructError::new(reason, detail, position, frames))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 固定种子生成的伪随机字节，保证测试可复现
+    fn bytes(seed: u64) -> Vec<u8> {
+        let mut x = seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1;
+        (0..512)
+            .map(|_| {
+                x ^= x << 13;
+                x ^= x >> 7;
+                x ^= x << 17;
+                x as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_arbitrary_reasons_cover_variants() {
+        let mut categories = std::collections::BTreeSet::new();
+        for seed in 0..200 {
+            let data = bytes(seed);
+            let reason = UvsReason::arbitrary(&mut Unstructured::new(&data)).unwrap();
+            let _ = reason.to_string();
+            categories.insert(reason.category_name());
+        }
+        assert_eq!(categories.len(), 13);
+    }
+
+    #[test]
+    fn test_arbitrary_struct_error_renders() {
+        for seed in 0..50 {
+            let data = bytes(seed);
+            let err = StructError::<UvsReason>::arbitrary(&mut Unstructured::new(&data)).unwrap();
+            let _ = err.to_string();
+            let _ = err.to_compact_string();
+        }
+    }
+
+    #[cfg(all(feature = "serde", feature = "json"))]
+    #[test]
+    fn test_arbitrary_reason_serde_round_trip() {
+        for seed in 0..200 {
+            let data = bytes(seed);
+            let reason = UvsReason::arbitrary(&mut Unstructured::new(&data)).unwrap();
+            let json = serde_json::to_string(&reason).unwrap();
+            let back: UvsReason = serde_json::from_str(&json).unwrap();
+            assert_eq!(back, reason);
+        }
+    }
+}
@@ -0,0 +1,90 @@
+//! reqwest 错误适配：按超时/连接/状态码归类，并记录 URL 与状态码
+
+use super::{context::OperationContext, domain::DomainReason, universal::UvsReason, StructError};
+use crate::{traits::ErrorWith, ContextRecord, UvsFrom};
+
+impl UvsReason {
+    /// 超时 → TimeoutError，连接失败 → NetworkError，对端返回错误状态 → ExternalError，其余 → NetworkError
+    pub fn from_reqwest(err: &reqwest::Error) -> Self {
+        if err.is_timeout() {
+            Self::TimeoutError
+        } else if err.is_connect() {
+            Self::NetworkError
+        } else if err.status().is_some() {
+            Self::ExternalError
+        } else {
+            Self::NetworkError
+        }
+    }
+}
+
+/// HTTP 客户端错误转换为结构错误，附带 `url` 与 `status` 上下文
+pub trait ErrorOweHttp<T, R>
+where
+    R: DomainReason + UvsFrom,
+{
+    fn owe_http(self) -> Result<T, StructError<R>>;
+}
+
+impl<T, R> ErrorOweHttp<T, R> for Result<T, reqwest::Error>
+where
+    R: DomainReason + UvsFrom,
+{
+    #[track_caller]
+    fn owe_http(self) -> Result<T, StructError<R>> {
+        // 不经闭包，使上下文帧与位置记录调用者的代码行
+        let e = match self {
+            Ok(v) => return Ok(v),
+            Err(e) => e,
+        };
+        let mut ctx = OperationContext::new();
+        if let Some(url) = e.url() {
+            ctx.record("url", url.as_str());
+        }
+        if let Some(status) = e.status() {
+            ctx.record("status", status.as_u16().to_string());
+        }
+        let err =
+            StructError::from(R::from(UvsReason::from_reqwest(&e))).with_detail(e.to_string());
+        if ctx.context().items.is_empty() {
+            Err(err)
+        } else {
+            Err(err.with(ctx))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ErrorCode;
+
+    #[test]
+    fn test_owe_http_status_error() {
+        let resp = http::Response::builder()
+            .status(503)
+            .body("unavailable")
+            .unwrap();
+        let res: Result<_, StructError<UvsReason>> =
+            reqwest::Response::from(resp).error_for_status().owe_http();
+        let err = res.unwrap_err();
+        assert_eq!(err.reason(), &UvsReason::ExternalError);
+        assert!(err.contexts()[0]
+            .context()
+            .items
+            .contains(&("status".into(), "503".to_string())));
+        assert!(err
+            .imp()
+            .position()
+            .as_deref()
+            .unwrap()
+            .starts_with(file!()));
+    }
+
+    #[test]
+    fn test_owe_http_other_error() {
+        let res: Result<_, StructError<UvsReason>> =
+            reqwest::Client::new().get("http://[::1").build().owe_http();
+        assert_eq!(res.unwrap_err().error_code(), 202);
+    }
+}
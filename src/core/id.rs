@@ -0,0 +1,81 @@
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+};
+
+const CROCKFORD: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+static ID_ENABLED: AtomicBool = AtomicBool::new(false);
+static ID_SEQ: AtomicU64 = AtomicU64::new(0);
+
+/// 开启后，每个新建的 `StructError` 自动生成唯一错误 ID（默认关闭）
+pub fn set_error_id_enabled(enabled: bool) {
+    ID_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn error_id_enabled() -> bool {
+    ID_ENABLED.load(Ordering::Relaxed)
+}
+
+/// 生成 26 位 ULID 风格的错误 ID：48 位毫秒时间戳 + 80 位随机数，按时间有序
+pub fn new_error_id() -> String {
+    let millis = now_millis();
+    let seq = ID_SEQ.fetch_add(1, Ordering::Relaxed);
+    let state = RandomState::new();
+    let mut h1 = state.build_hasher();
+    h1.write_u64(seq);
+    h1.write_u64(millis);
+    let mut h2 = state.build_hasher();
+    h2.write_u64(!seq);
+
+    let value: u128 = ((millis as u128 & 0xFFFF_FFFF_FFFF) << 80)
+        | ((h1.finish() as u128) << 16)
+        | (h2.finish() as u128 & 0xFFFF);
+    let mut out = [0u8; 26];
+    for (i, slot) in out.iter_mut().enumerate() {
+        let shift = 125 - i * 5;
+        *slot = CROCKFORD[((value >> shift) & 0x1F) as usize];
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+fn now_millis() -> u64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// wasm32-unknown-unknown 上 `SystemTime::now` 会 panic：启用 `wasm` 时取 JS 时钟，否则仅依赖随机部分
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+fn now_millis() -> u64 {
+    #[cfg(feature = "wasm")]
+    {
+        js_sys::Date::now() as u64
+    }
+    #[cfg(not(feature = "wasm"))]
+    {
+        0
+    }
+}
+
+pub(crate) fn maybe_error_id() -> Option<String> {
+    error_id_enabled().then(new_error_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_error_id_shape() {
+        let a = new_error_id();
+        let b = new_error_id();
+        assert_eq!(a.len(), 26);
+        assert!(a.bytes().all(|c| CROCKFORD.contains(&c)));
+        assert_ne!(a, b);
+    }
+}
@@ -0,0 +1,85 @@
+use std::sync::RwLock;
+
+/// 错误体积限制；`None` 表示不限制（默认）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ErrorLimits {
+    /// `detail` 最大字节数
+    pub max_detail_len: Option<usize>,
+    /// 单个上下文帧的最大条目数，超出部分以一条截断标记汇总
+    pub max_context_items: Option<usize>,
+    /// 上下文值的最大字节数
+    pub max_value_len: Option<usize>,
+    /// 渲染时显示的最大上下文帧数，超出时保留首尾、折叠中间帧（不影响错误内容）
+    pub max_context_depth: Option<usize>,
+}
+
+/// 上下文条目被截断时使用的标记键
+pub const TRUNCATED_KEY: &str = "…";
+
+static LIMITS: RwLock<ErrorLimits> = RwLock::new(ErrorLimits {
+    max_detail_len: None,
+    max_context_items: None,
+    max_value_len: None,
+    max_context_depth: None,
+});
+
+/// 设置全局体积限制，在错误构造与上下文记录时生效
+pub fn set_error_limits(limits: ErrorLimits) {
+    *LIMITS.write().unwrap_or_else(|e| e.into_inner()) = limits;
+}
+
+pub fn error_limits() -> ErrorLimits {
+    *LIMITS.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// 超过 `max` 字节时截断（按字符边界），并追加 `…(truncated, N bytes)` 标记
+pub fn truncate_text(mut text: String, max: usize) -> String {
+    if text.len() <= max {
+        return text;
+    }
+    let mut cut = max;
+    while !text.is_char_boundary(cut) {
+        cut -= 1;
+    }
+    let removed = text.len() - cut;
+    text.truncate(cut);
+    text.push_str(&format!("…(truncated, {removed} bytes)"));
+    text
+}
+
+pub(crate) fn limit_detail(detail: String) -> String {
+    match error_limits().max_detail_len {
+        Some(max) => truncate_text(detail, max),
+        None => detail,
+    }
+}
+
+pub(crate) fn limit_value(value: String) -> String {
+    match error_limits().max_value_len {
+        Some(max) => truncate_text(value, max),
+        None => value,
+    }
+}
+
+pub(crate) fn truncated_items_marker(count: usize) -> String {
+    format!("(truncated, {count} items)")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_text_marks_removed_bytes() {
+        assert_eq!(truncate_text("short".into(), 10), "short");
+        assert_eq!(
+            truncate_text("0123456789".into(), 4),
+            "0123…(truncated, 6 bytes)"
+        );
+        // 不在多字节字符中间截断
+        assert_eq!(
+            truncate_text("中文字符".into(), 4),
+            "中…(truncated, 9 bytes)"
+        );
+    }
+}
@@ -0,0 +1,276 @@
+use std::fmt::{self, Display, Write};
+
+use super::{
+    domain::DomainReason,
+    format::{ErrorFormatter, ErrorView},
+    universal::{join_fields, AuthErrReason, ConfErrReason, UvsReason},
+    ErrorCode, StructError,
+};
+
+/// 错误信息的显示语言
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    #[default]
+    En,
+    Zh,
+}
+
+/// 本地化的 reason 文本；默认回退到 `Display`
+///
+/// 领域 reason 包装 `UvsReason` 时可委托给 `UvsReason::localize`。
+pub trait Localize: Display {
+    fn localize(&self, _locale: Locale) -> String {
+        self.to_string()
+    }
+}
+
+impl Localize for ConfErrReason {
+    fn localize(&self, locale: Locale) -> String {
+        match locale {
+            Locale::En => self.to_string(),
+            Locale::Zh => match self {
+                ConfErrReason::Core => "核心配置".into(),
+                ConfErrReason::Feature => "特性配置错误".into(),
+                ConfErrReason::Dynamic => "动态配置错误".into(),
+                ConfErrReason::MissingKey { file, key } => {
+                    format!("缺少配置项 `{key}`（{file}）")
+                }
+                ConfErrReason::InvalidType {
+                    file,
+                    key,
+                    expected,
+                } => format!("配置项 `{key}` 类型错误（{file}），期望 {expected}"),
+            },
+        }
+    }
+}
+
+impl Localize for AuthErrReason {
+    fn localize(&self, locale: Locale) -> String {
+        match locale {
+            Locale::En => self.to_string(),
+            Locale::Zh => match self {
+                AuthErrReason::Unauthenticated => "需要认证".into(),
+                AuthErrReason::Forbidden { required } => format!("权限不足，需要 `{required}`"),
+            },
+        }
+    }
+}
+
+impl Localize for UvsReason {
+    fn localize(&self, locale: Locale) -> String {
+        match locale {
+            Locale::En => self.to_string(),
+            Locale::Zh => match self {
+                UvsReason::ValidationError => "校验错误".into(),
+                UvsReason::ValidationFields(fields) => format!("校验错误: {}", join_fields(fields)),
+                UvsReason::BusinessError => "业务逻辑错误".into(),
+                UvsReason::RunRuleError => "运行规则错误".into(),
+                UvsReason::NotFoundError => "资源不存在".into(),
+                UvsReason::NotFoundResource { kind, id } => format!("{kind} `{id}` 不存在"),
+                UvsReason::PermissionError => "权限错误".into(),
+                UvsReason::AuthError(reason) => reason.localize(locale),
+                UvsReason::DataError => "数据错误".into(),
+                UvsReason::SystemError => "系统错误".into(),
+                UvsReason::NetworkError => "网络错误".into(),
+                UvsReason::ResourceError => "资源错误".into(),
+                UvsReason::ResourceExhausted {
+                    kind,
+                    current,
+                    limit,
+                } => format!("资源 {kind} 耗尽：{current}/{limit}"),
+                UvsReason::TimeoutError => "超时错误".into(),
+                UvsReason::TimeoutAfter { op, elapsed, limit } => {
+                    format!("{op} 超时：耗时 {elapsed:?}，时限 {limit:?}")
+                }
+                UvsReason::ConfigError(reason) => {
+                    format!("配置错误 << {}", reason.localize(locale))
+                }
+                UvsReason::ExternalError => "外部服务错误".into(),
+                UvsReason::UpstreamError(upstream) => format!("上游服务错误: {upstream}"),
+                UvsReason::LogicError => "BUG :逻辑错误".into(),
+            },
+        }
+    }
+}
+
+struct Labels {
+    error_code: &'static str,
+    at: &'static str,
+    via: &'static str,
+    want: &'static str,
+    details: &'static str,
+    error_id: &'static str,
+    correlation_id: &'static str,
+    time: &'static str,
+    context_stack: &'static str,
+    context: &'static str,
+    build: &'static str,
+}
+
+impl Locale {
+    fn labels(self) -> Labels {
+        match self {
+            Locale::En => Labels {
+                error_code: "error code",
+                at: "At",
+                via: "via",
+                want: "Want",
+                details: "Details",
+                error_id: "Error ID",
+                correlation_id: "Correlation ID",
+                time: "Time",
+                context_stack: "Context stack",
+                context: "context",
+                build: "Build",
+            },
+            Locale::Zh => Labels {
+                error_code: "错误代码",
+                at: "位置",
+                via: "经由",
+                want: "目标",
+                details: "详情",
+                error_id: "错误 ID",
+                correlation_id: "关联 ID",
+                time: "时间",
+                context_stack: "上下文栈",
+                context: "上下文",
+                build: "构建",
+            },
+        }
+    }
+}
+
+/// 按语言渲染标签的多行格式化器；reason 文本需由调用方预先本地化
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LocalizedFormatter {
+    pub locale: Locale,
+}
+
+impl ErrorFormatter for LocalizedFormatter {
+    fn write_to(&self, view: &ErrorView<'_>, out: &mut dyn Write) -> fmt::Result {
+        let l = self.locale.labels();
+        write!(
+            out,
+            "[{} {}] {}",
+            l.error_code,
+            view.code_label(),
+            view.reason
+        )?;
+        if let Some(pos) = view.position {
+            write!(out, "\n  -> {}: {pos}", l.at)?;
+        }
+        for pos in view.position_trail {
+            write!(out, "\n     {} {pos}", l.via)?;
+        }
+        if let Some(target) = &view.target {
+            write!(out, "\n  -> {}: {target}", l.want)?;
+        }
+        if let Some(detail) = view.detail {
+            write!(out, "\n  -> {}: {detail}", l.details)?;
+        }
+        if let Some(id) = view.id {
+            write!(out, "\n  -> {}: {id}", l.error_id)?;
+        }
+        if let Some(cid) = view.correlation_id {
+            write!(out, "\n  -> {}: {cid}", l.correlation_id)?;
+        }
+        if let Some(ts) = view.timestamp {
+            write!(
+                out,
+                "\n  -> {}: {}",
+                l.time,
+                view.timestamp_format.format(ts)
+            )?;
+        }
+        if !view.contexts.is_empty() {
+            writeln!(out, "\n  -> {}:", l.context_stack)?;
+            for (i, c) in view.contexts.iter().enumerate() {
+                writeln!(out, "{} {i}: ", l.context)?;
+                writeln!(out, "{c}")?;
+            }
+        }
+        if let Some(app) = view.app {
+            if view.contexts.is_empty() {
+                out.write_char('\n')?;
+            }
+            write!(out, "  -> {}: {app}", l.build)?;
+        }
+        Ok(())
+    }
+}
+
+/// 以指定语言渲染错误
+pub fn format_error_localized<R>(err: &StructError<R>, locale: Locale) -> String
+where
+    R: DomainReason + ErrorCode + Localize,
+{
+    let mut view = err.view();
+    view.reason = err.reason().localize(locale);
+    let mut out = LocalizedFormatter { locale }.format(&view);
+    out.push('\n');
+    out.push_str(&"-".repeat(50));
+    out.push('\n');
+    out
+}
+
+pub fn print_error_localized<R>(err: &StructError<R>, locale: Locale)
+where
+    R: DomainReason + ErrorCode + Localize,
+{
+    print!("{}", format_error_localized(err, locale));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ErrorWith;
+
+    #[test]
+    fn test_uvs_reason_localize() {
+        assert_eq!(UvsReason::network_error().localize(Locale::Zh), "网络错误");
+        assert_eq!(
+            UvsReason::core_conf().localize(Locale::Zh),
+            "配置错误 << 核心配置"
+        );
+        assert_eq!(
+            ConfErrReason::missing_key("app.toml", "db.url").localize(Locale::Zh),
+            "缺少配置项 `db.url`（app.toml）"
+        );
+        assert_eq!(
+            UvsReason::core_conf().localize(Locale::En),
+            UvsReason::core_conf().to_string()
+        );
+    }
+
+    #[test]
+    fn test_format_error_localized() {
+        let err = StructError::from(UvsReason::core_conf())
+            .with_detail("missing db config")
+            .want("database_config");
+
+        let zh = format_error_localized(&err, Locale::Zh);
+        assert!(zh.starts_with("[错误代码 300] 配置错误 << 核心配置"));
+        assert!(zh.contains("-> 详情: missing db config"));
+        assert!(zh.contains("上下文 0:"));
+
+        let en = format_error_localized(&err, Locale::En);
+        assert!(en.starts_with("[error code 300] configuration error << core config"));
+        assert!(en.contains("-> Want: database_config"));
+    }
+
+    #[test]
+    fn test_localized_position_trail() {
+        let err = StructError::from(UvsReason::network_error())
+            .with_position("src/net.rs:1:1")
+            .position("src/api.rs:2:2")
+            .position("src/main.rs:3:3");
+
+        let zh = format_error_localized(&err, Locale::Zh);
+        assert!(zh.contains(
+            "-> 位置: src/net.rs:1:1\n     经由 src/api.rs:2:2\n     经由 src/main.rs:3:3"
+        ));
+        let en = format_error_localized(&err, Locale::En);
+        assert!(en.contains("\n     via src/api.rs:2:2\n     via src/main.rs:3:3"));
+    }
+}
@@ -0,0 +1,178 @@
+//! 带格式化消息的构造：`StructError::business_fmt(format_args!(..))` 与 `uvs_biz!` 等宏
+
+use std::fmt;
+
+use super::{domain::DomainReason, StructError, UvsFrom};
+
+/// 生成 `xxx_fmt(args)`：以对应的通用原因创建错误，格式化消息写入 `detail`
+macro_rules! fmt_ctors {
+    ($($name:ident => $from:ident),* $(,)?) => {
+        $(
+            pub fn $name(args: fmt::Arguments<'_>) -> Self {
+                Self::from_fmt(R::$from(), args)
+            }
+        )*
+    };
+}
+
+impl<R: DomainReason + UvsFrom> StructError<R> {
+    /// 以 `reason` 创建错误，消息写入 `detail`；无插值参数时直接复制静态文本，不经过格式化
+    pub fn from_fmt(reason: R, args: fmt::Arguments<'_>) -> Self {
+        StructError::from(reason).with_detail(fmt::format(args))
+    }
+
+    fmt_ctors!(
+        business_fmt => from_biz,
+        rule_fmt => from_rule,
+        validation_fmt => from_validation,
+        not_found_fmt => from_not_found,
+        permission_fmt => from_permission,
+        data_fmt => from_data,
+        conf_fmt => from_conf,
+        resource_fmt => from_res,
+        network_fmt => from_net,
+        timeout_fmt => from_timeout,
+        system_fmt => from_sys,
+        logic_fmt => from_logic,
+    );
+}
+
+/// 业务错误，参数同 `format!`，例如 `uvs_biz!("user {} blocked", id)`
+///
+/// 原因类型由上下文推断，可用于任何实现了 `From<UvsReason>` 的领域原因：
+///
+/// ```rust
+/// use orion_error::{uvs_biz, StructError, UvsReason};
+///
+/// fn block(id: u32) -> Result<(), StructError<UvsReason>> {
+///     Err(uvs_biz!("user {id} blocked"))
+/// }
+/// let err = block(7).unwrap_err();
+/// assert_eq!(err.detail().as_deref(), Some("user 7 blocked"));
+/// ```
+#[macro_export]
+macro_rules! uvs_biz {
+    ($($arg:tt)*) => {
+        $crate::StructError::business_fmt(format_args!($($arg)*))
+    };
+}
+
+/// 规则错误，参数同 `format!`
+#[macro_export]
+macro_rules! uvs_rule {
+    ($($arg:tt)*) => {
+        $crate::StructError::rule_fmt(format_args!($($arg)*))
+    };
+}
+
+/// 校验错误，参数同 `format!`
+#[macro_export]
+macro_rules! uvs_validation {
+    ($($arg:tt)*) => {
+        $crate::StructError::validation_fmt(format_args!($($arg)*))
+    };
+}
+
+/// 资源不存在，参数同 `format!`
+#[macro_export]
+macro_rules! uvs_not_found {
+    ($($arg:tt)*) => {
+        $crate::StructError::not_found_fmt(format_args!($($arg)*))
+    };
+}
+
+/// 权限错误，参数同 `format!`
+#[macro_export]
+macro_rules! uvs_permission {
+    ($($arg:tt)*) => {
+        $crate::StructError::permission_fmt(format_args!($($arg)*))
+    };
+}
+
+/// 数据错误，参数同 `format!`
+#[macro_export]
+macro_rules! uvs_data {
+    ($($arg:tt)*) => {
+        $crate::StructError::data_fmt(format_args!($($arg)*))
+    };
+}
+
+/// 配置错误，参数同 `format!`
+#[macro_export]
+macro_rules! uvs_conf {
+    ($($arg:tt)*) => {
+        $crate::StructError::conf_fmt(format_args!($($arg)*))
+    };
+}
+
+/// 资源错误，参数同 `format!`
+#[macro_export]
+macro_rules! uvs_res {
+    ($($arg:tt)*) => {
+        $crate::StructError::resource_fmt(format_args!($($arg)*))
+    };
+}
+
+/// 网络错误，参数同 `format!`
+#[macro_export]
+macro_rules! uvs_net {
+    ($($arg:tt)*) => {
+        $crate::StructError::network_fmt(format_args!($($arg)*))
+    };
+}
+
+/// 超时错误，参数同 `format!`
+#[macro_export]
+macro_rules! uvs_timeout {
+    ($($arg:tt)*) => {
+        $crate::StructError::timeout_fmt(format_args!($($arg)*))
+    };
+}
+
+/// 系统错误，参数同 `format!`
+#[macro_export]
+macro_rules! uvs_sys {
+    ($($arg:tt)*) => {
+        $crate::StructError::system_fmt(format_args!($($arg)*))
+    };
+}
+
+/// 逻辑错误，参数同 `format!`
+#[macro_export]
+macro_rules! uvs_logic {
+    ($($arg:tt)*) => {
+        $crate::StructError::logic_fmt(format_args!($($arg)*))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ErrorCode, UvsReason};
+
+    #[test]
+    fn test_fmt_constructors() {
+        let id = 42;
+        let err: StructError<UvsReason> =
+            StructError::business_fmt(format_args!("user {id} blocked"));
+        assert_eq!(err.reason(), &UvsReason::business_error());
+        assert_eq!(err.detail().as_deref(), Some("user 42 blocked"));
+
+        let err: StructError<UvsReason> = StructError::not_found_fmt(format_args!("no such order"));
+        assert_eq!(err.error_code(), 102);
+        assert_eq!(err.detail().as_deref(), Some("no such order"));
+    }
+
+    #[test]
+    fn test_fmt_macros() {
+        let path = "/etc/app.toml";
+        let err: StructError<UvsReason> = crate::uvs_conf!("missing {}", path);
+        assert_eq!(err.reason(), &UvsReason::core_conf());
+        assert_eq!(err.detail().as_deref(), Some("missing /etc/app.toml"));
+
+        let err: StructError<UvsReason> = crate::uvs_timeout!("op took {}ms", 1500);
+        assert_eq!(err.error_code(), 204);
+        let err: StructError<UvsReason> = crate::uvs_validation!("bad input");
+        assert_eq!(err.detail().as_deref(), Some("bad input"));
+    }
+}
@@ -1,18 +1,102 @@
+mod bundle;
 mod case;
+mod catalog;
+#[cfg(feature = "color")]
+mod color;
+pub(crate) mod config;
 mod context;
+#[cfg(feature = "miette")]
+mod diagnostic;
 mod domain;
+mod enrich;
 mod error;
+#[cfg(any(feature = "yaml", feature = "toml"))]
+mod export;
+mod extensions;
+mod fingerprint;
+mod format;
+#[cfg(feature = "arbitrary")]
+mod fuzz;
+#[cfg(feature = "reqwest")]
+mod http_client;
+mod id;
+mod limit;
+mod locale;
+mod message;
+mod panic;
+mod parse;
+mod problem;
+#[cfg(feature = "serde")]
+mod profile;
 mod reason;
+mod redact;
+mod report;
+mod schema;
+mod severity;
+pub(crate) mod stats;
+mod std_conv;
+mod strategy;
 mod universal;
-use std::fmt::Display;
+#[cfg(feature = "wasm")]
+mod wasm;
+use std::fmt::{Display, Write as _};
+use std::io;
 
+pub use bundle::{ErrorBundle, ErrorCollect};
+pub use catalog::{CatalogEntry, ErrorCatalog};
+#[cfg(feature = "color")]
+pub use color::{color_choice, set_color_choice, ColorChoice, ColorFormatter};
+pub use config::{app_info, error_config, AppInfo, DefaultFormat, ErrorConfig, TimestampFormat};
 pub use context::ContextAdd;
-pub use context::{ContextRecord, OperationContext, OperationScope, WithContext};
+pub use context::{
+    context_dedup, path_display, set_context_dedup, set_path_display, set_want_mode, want_mode,
+    DisplayValue, PathDisplay, WantMode,
+};
+pub use context::{
+    CallContext, ContextHandle, ContextItems, ContextKey, ContextRecord, ContextRecordExt,
+    IntoContextKey, OperationContext, OperationScope, WithContext,
+};
 pub use domain::DomainReason;
-pub use error::{convert_error, StructError, StructErrorBuilder, StructErrorTrait};
-pub use reason::ErrorCode;
-pub use universal::{ConfErrReason, UvsFrom, UvsReason};
+pub use enrich::{clear_context_enrichers, register_context_enricher, ContextEnricher};
+pub(crate) use error::wrap_error;
+pub use error::{
+    convert_error, convert_error_keep, ContextStack, ErrorSource, ReasonEq, StructError,
+    StructErrorBuilder, StructErrorTrait, UvsError, ERROR_SCHEMA_VERSION,
+};
+pub use extensions::{Extensions, RetryAfter};
+pub use fingerprint::{normalize_text, DefaultFingerprint, Fingerprint};
+pub use format::{
+    CompactFormatter, ErrorFormatter, ErrorView, JsonFormatter, VerboseFormatter, WrappedFormatter,
+};
+#[cfg(feature = "reqwest")]
+pub use http_client::ErrorOweHttp;
+pub use id::{error_id_enabled, new_error_id, set_error_id_enabled};
+pub use limit::{error_limits, set_error_limits, truncate_text, ErrorLimits, TRUNCATED_KEY};
+pub use locale::{
+    format_error_localized, print_error_localized, Locale, Localize, LocalizedFormatter,
+};
+pub use panic::{catch_panic, install_panic_hook};
+pub(crate) use parse::parse_error;
+pub use parse::{DataPos, ParsePosition};
+pub use problem::{expose_internal, set_expose_internal, PROBLEM_CONTENT_TYPE};
+#[cfg(feature = "serde")]
+pub use profile::{Profiled, SerializeProfile};
+pub use reason::{Code, DomainCode, ErrorCode, ErrorExitCode, HttpStatus, Namespace};
+pub use redact::{
+    clear_sensitive_keys, is_sensitive_key, redact_text, register_sensitive_key, REDACTED,
+};
+pub use report::{JsonlSink, ThrottledReporter};
+pub use schema::ContextSchema;
+pub use severity::{Severity, SeverityOf};
+pub use std_conv::ToUvsReason;
+pub use strategy::{ErrorApplyStrategy, ErrorIgnoreLogged, RetryPolicy, StrategyFor};
+pub use universal::{
+    AsUvsReason, AuthErrReason, ConfErrReason, FaultOf, FieldError, ResourceKind, Upstream,
+    UvsFrom, UvsReason,
+};
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum ErrStrategy {
     /// 带退避策略的重试（包含基本参数）
     Retry,
@@ -22,14 +106,75 @@ pub enum ErrStrategy {
     Throw,
 }
 
-pub fn print_error<R: DomainReason + ErrorCode + Display>(err: &StructError<R>) {
-    println!("[error code{}] \n{err}", err.reason().error_code());
+impl ErrStrategy {
+    pub fn is_retry(&self) -> bool {
+        matches!(self, Self::Retry)
+    }
+
+    pub fn is_ignore(&self) -> bool {
+        matches!(self, Self::Ignore)
+    }
+
+    pub fn is_throw(&self) -> bool {
+        matches!(self, Self::Throw)
+    }
+}
+
+/// 将错误渲染为字符串，格式与 `print_error` 输出一致
+pub fn format_error<R: DomainReason + ErrorCode + Display>(err: &StructError<R>) -> String {
+    let rendered = match config::error_config().format {
+        DefaultFormat::Classic => None,
+        DefaultFormat::Verbose => Some(err.format_with(&VerboseFormatter)),
+        DefaultFormat::Compact => Some(err.format_with(&CompactFormatter)),
+        DefaultFormat::Json => Some(err.format_with(&JsonFormatter)),
+    };
+    if let Some(mut out) = rendered {
+        out.push('\n');
+        return out;
+    }
+    let mut out = String::new();
+    let _ = writeln!(out, "[error code{}] \n{err}", err.reason().error_code());
     for ctx in err.context().iter() {
-        println!("context: {ctx}", ctx = ctx.context());
+        let _ = writeln!(out, "context: {ctx}", ctx = ctx.context());
+    }
+    let _ = writeln!(out, "{}", "-".repeat(50));
+    out
+}
+
+/// 将错误写入任意 writer，例如 `std::io::stderr()` 或测试用的 `Vec<u8>`
+pub fn write_error<R, W>(err: &StructError<R>, mut w: W) -> io::Result<()>
+where
+    R: DomainReason + ErrorCode + Display,
+    W: io::Write,
+{
+    w.write_all(format_error(err).as_bytes())
+}
+
+/// 输出到 stdout；启用 `color` 特性时按 `set_color_choice` 的策略着色。
+/// 着色只替换多行格式（`Classic` / `Verbose`），配置为 `Compact` / `Json` 时照常输出
+pub fn print_error<R: DomainReason + ErrorCode + Display>(err: &StructError<R>) {
+    #[cfg(feature = "color")]
+    if matches!(
+        config::error_config().format,
+        DefaultFormat::Classic | DefaultFormat::Verbose
+    ) && color_choice().enabled_for(&io::stdout())
+    {
+        println!("{}", err.format_with(&ColorFormatter));
+        println!("{}", "-".repeat(50));
+        return;
+    }
+    print!("{}", format_error(err));
+}
+
+impl<R: DomainReason + ErrorCode + ErrorExitCode + Display> StructError<R> {
+    /// 输出错误到 stderr，并以 `exit_code()` 结束进程，便于 CLI 脚本判断失败类别
+    pub fn exit_process(&self) -> ! {
+        let _ = write_error(self, io::stderr());
+        std::process::exit(self.exit_code())
     }
-    println!("{}", "-".repeat(50));
 }
 
+#[deprecated(since = "0.7.0", note = "use print_error_localized(err, Locale::Zh)")]
 pub fn print_error_zh<R: DomainReason + ErrorCode + Display>(err: &StructError<R>) {
     println!("[错误代码 {}] \n{err}", err.reason().error_code());
     for ctx in err.context().iter() {
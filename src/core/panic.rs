@@ -0,0 +1,75 @@
+use std::{
+    any::Any,
+    cell::RefCell,
+    panic::{self, UnwindSafe},
+    sync::Once,
+};
+
+use super::{domain::DomainReason, universal::UvsReason, StructError};
+
+thread_local! {
+    static LAST_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+static HOOK: Once = Once::new();
+
+/// 安装全局 panic hook：记录 panic 发生位置供 `catch_panic` 使用，随后调用原有 hook
+///
+/// 多次调用只安装一次。
+pub fn install_panic_hook() {
+    HOOK.call_once(|| {
+        let prev = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if let Some(loc) = info.location() {
+                let pos = format!("{}:{}:{}", loc.file(), loc.line(), loc.column());
+                LAST_LOCATION.with(|l| *l.borrow_mut() = Some(pos));
+            }
+            prev(info);
+        }));
+    });
+}
+
+/// 执行闭包并将 unwind panic 转换为 `LogicError`：panic 消息写入 detail，
+/// 安装了 `install_panic_hook` 时发生位置写入 position
+pub fn catch_panic<F, T, R>(f: F) -> Result<T, StructError<R>>
+where
+    F: FnOnce() -> T + UnwindSafe,
+    R: DomainReason + From<UvsReason>,
+{
+    LAST_LOCATION.with(|l| l.borrow_mut().take());
+    panic::catch_unwind(f).map_err(|payload| {
+        let mut err = StructError::from(R::from(UvsReason::LogicError))
+            .with_detail(panic_message(payload.as_ref()));
+        if let Some(pos) = LAST_LOCATION.with(|l| l.borrow_mut().take()) {
+            err = err.with_position(pos);
+        }
+        err
+    })
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        (*s).to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "panic with non-string payload".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 安装 hook 会影响整个测试进程，相关用例见 tests/test_panic_hook.rs
+    #[test]
+    fn test_catch_panic() {
+        let res: Result<(), StructError<UvsReason>> = catch_panic(|| panic!("boom {}", 42));
+        let err = res.unwrap_err();
+        assert_eq!(err.reason(), &UvsReason::LogicError);
+        assert_eq!(err.detail().as_deref(), Some("boom 42"));
+
+        let ok: Result<i32, StructError<UvsReason>> = catch_panic(|| 7);
+        assert_eq!(ok.unwrap(), 7);
+    }
+}
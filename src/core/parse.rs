@@ -0,0 +1,301 @@
+//! 解析类错误适配：serde_json / serde_yaml / toml 错误归类为数据错误，并提取行列位置
+
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use super::{domain::DomainReason, universal::UvsReason, StructError};
+
+/// 数据错误的位置：字节偏移、行列（从 1 开始）及可选的文件/来源名
+///
+/// 文本形式为 `line 3, column 7, offset 42`，有来源名时为 `app.toml (line 3, column 7)`；
+/// 各部分带标签且至少出现一项，不会与 `file:line:column` 形式的代码位置混淆。
+/// 可写入 `StructError` 的 position 并通过 `StructError::data_pos` 解析回来。
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DataPos {
+    pub offset: Option<usize>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub source: Option<String>,
+}
+
+impl DataPos {
+    pub fn at_offset(offset: usize) -> Self {
+        Self {
+            offset: Some(offset),
+            ..Default::default()
+        }
+    }
+
+    pub fn at_line_col(line: usize, column: usize) -> Self {
+        Self {
+            line: Some(line),
+            column: Some(column),
+            ..Default::default()
+        }
+    }
+
+    #[must_use]
+    pub fn with_offset(mut self, offset: usize) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    #[must_use]
+    pub fn with_source(mut self, source: impl Into<String>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// 带标签的各部分，例如 `["line 3", "column 7"]`
+    fn labeled_parts(&self) -> Vec<String> {
+        [
+            ("line", self.line),
+            ("column", self.column),
+            ("offset", self.offset),
+        ]
+        .into_iter()
+        .filter_map(|(label, value)| value.map(|v| format!("{label} {v}")))
+        .collect()
+    }
+}
+
+impl Display for DataPos {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts = self.labeled_parts().join(", ");
+        match &self.source {
+            Some(source) if !parts.is_empty() => write!(f, "{source} ({parts})"),
+            Some(source) => f.write_str(source),
+            None => f.write_str(&parts),
+        }
+    }
+}
+
+impl FromStr for DataPos {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bad = || format!("invalid data position: {s}");
+        // 来源名可含 `:`、`@`、括号等任意字符，只有末尾的 ` (...)` 是位置
+        let (source, parts) = match s.strip_suffix(')').and_then(|r| r.rsplit_once(" (")) {
+            Some((source, parts)) => (Some(source.to_string()), parts),
+            None => (None, s),
+        };
+        let mut pos = DataPos {
+            source,
+            ..Default::default()
+        };
+        for part in parts.split(", ") {
+            let (label, value) = part.split_once(' ').ok_or_else(bad)?;
+            let value = value.parse::<usize>().map_err(|_| bad())?;
+            let slot = match label {
+                "line" => &mut pos.line,
+                "column" => &mut pos.column,
+                "offset" => &mut pos.offset,
+                _ => return Err(bad()),
+            };
+            if slot.replace(value).is_some() {
+                return Err(bad());
+            }
+        }
+        Ok(pos)
+    }
+}
+
+impl From<DataPos> for String {
+    fn from(pos: DataPos) -> Self {
+        pos.to_string()
+    }
+}
+
+impl<T: DomainReason> StructError<T> {
+    /// 将 position 解析为 `DataPos`（position 不是数据位置格式时返回 `None`）
+    pub fn data_pos(&self) -> Option<DataPos> {
+        self.position().as_deref()?.parse().ok()
+    }
+}
+
+/// 可提供出错位置的解析错误
+pub trait ParsePosition: Display {
+    fn parse_position(&self) -> Option<DataPos>;
+}
+
+#[cfg(any(feature = "json", feature = "yaml", feature = "toml"))]
+fn line_col(line: usize, column: usize) -> Option<DataPos> {
+    (line > 0).then(|| DataPos::at_line_col(line, column))
+}
+
+/// 将解析错误转换为带位置的数据错误
+pub(crate) fn parse_error<R, E>(err: &E) -> StructError<R>
+where
+    R: DomainReason + From<UvsReason>,
+    E: ParsePosition,
+{
+    let mut se = StructError::from(R::from(UvsReason::DataError)).with_detail(err.to_string());
+    if let Some(pos) = err.parse_position() {
+        se = se.with_position(pos);
+    }
+    se
+}
+
+#[cfg(any(feature = "json", feature = "yaml", feature = "toml"))]
+macro_rules! impl_parse_error {
+    ($ty:ty) => {
+        impl super::std_conv::ToUvsReason for $ty {
+            fn to_uvs_reason(&self) -> UvsReason {
+                UvsReason::DataError
+            }
+        }
+
+        impl<R> From<$ty> for StructError<R>
+        where
+            R: DomainReason + From<UvsReason>,
+        {
+            fn from(err: $ty) -> Self {
+                parse_error(&err)
+            }
+        }
+    };
+}
+
+#[cfg(feature = "json")]
+impl ParsePosition for serde_json::Error {
+    fn parse_position(&self) -> Option<DataPos> {
+        line_col(self.line(), self.column())
+    }
+}
+#[cfg(feature = "json")]
+impl_parse_error!(serde_json::Error);
+
+#[cfg(feature = "yaml")]
+impl ParsePosition for serde_yaml::Error {
+    fn parse_position(&self) -> Option<DataPos> {
+        self.location().and_then(|loc| {
+            line_col(loc.line(), loc.column()).map(|pos| pos.with_offset(loc.index()))
+        })
+    }
+}
+#[cfg(feature = "yaml")]
+impl_parse_error!(serde_yaml::Error);
+
+#[cfg(feature = "toml")]
+impl ParsePosition for toml::de::Error {
+    fn parse_position(&self) -> Option<DataPos> {
+        // toml 只提供字节区间，行列信息位于消息首行：`TOML parse error at line L, column C`
+        let text = self.to_string();
+        let line_col = text
+            .lines()
+            .next()
+            .and_then(|first| first.split(" at line ").nth(1))
+            .and_then(|rest| rest.split_once(", column "))
+            .and_then(|(l, c)| line_col(l.trim().parse().ok()?, c.trim().parse().ok()?));
+        let offset = self.span().map(|span| span.start);
+        match (line_col, offset) {
+            (Some(pos), Some(off)) => Some(pos.with_offset(off)),
+            (Some(pos), None) => Some(pos),
+            (None, off) => off.map(DataPos::at_offset),
+        }
+    }
+}
+#[cfg(feature = "toml")]
+impl_parse_error!(toml::de::Error);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_data_pos_display_round_trip() {
+        let line_only = DataPos {
+            line: Some(3),
+            ..Default::default()
+        };
+        let cases = [
+            DataPos::at_line_col(3, 7),
+            DataPos::at_line_col(3, 7)
+                .with_offset(42)
+                .with_source("C:/cfg/app.toml"),
+            DataPos::at_offset(9),
+            DataPos::at_offset(9).with_source("stdin"),
+            DataPos::at_line_col(3, 7).with_source("user@host:cfg.toml"),
+            DataPos::at_line_col(3, 7)
+                .with_offset(42)
+                .with_source("user@host:cfg.toml"),
+            DataPos::at_offset(9).with_source("user@host"),
+            DataPos::at_offset(9).with_source("host:8080"),
+            DataPos::at_line_col(3, 7).with_source("host:8080"),
+            line_only.clone(),
+            line_only.clone().with_source("host:8080"),
+        ];
+        for pos in cases {
+            assert_eq!(pos.to_string().parse::<DataPos>().unwrap(), pos);
+        }
+        assert_eq!(
+            DataPos::at_line_col(3, 7).with_source("a.json").to_string(),
+            "a.json (line 3, column 7)"
+        );
+        assert_eq!(line_only.to_string(), "line 3");
+        assert!("src/main.rs".parse::<DataPos>().is_err());
+        assert!("src/main.rs:10:5".parse::<DataPos>().is_err());
+        assert!("line 3, line 4".parse::<DataPos>().is_err());
+
+        let err = StructError::from(UvsReason::DataError).with_position(DataPos::at_offset(5));
+        assert_eq!(err.data_pos(), Some(DataPos::at_offset(5)));
+        let pos = DataPos::at_line_col(2, 1).with_source("user@host:cfg.toml");
+        let err = StructError::from(UvsReason::DataError).with_position(pos.clone());
+        assert_eq!(err.data_pos(), Some(pos));
+    }
+
+    #[test]
+    fn test_data_pos_ignores_code_locations() {
+        use crate::ErrorWith;
+
+        let err = StructError::from(UvsReason::DataError).with_position(crate::location!());
+        assert_eq!(err.data_pos(), None);
+        let err = StructError::from(UvsReason::DataError).want("load config");
+        assert!(err.imp().position().is_some());
+        assert_eq!(err.data_pos(), None);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_json_error_position() {
+        let err = serde_json::from_str::<serde_json::Value>("{\n  \"a\": ,\n}").unwrap_err();
+        let se: StructError<UvsReason> = err.into();
+        assert_eq!(se.reason(), &UvsReason::DataError);
+        assert_eq!(se.position().as_deref(), Some("line 2, column 8"));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_owe_parse() {
+        use crate::ErrorOweParse;
+
+        let res: Result<serde_json::Value, StructError<UvsReason>> =
+            serde_json::from_str("[1, 2").owe_parse();
+        let se = res.unwrap_err();
+        assert_eq!(se.reason(), &UvsReason::DataError);
+        assert_eq!(se.position().as_deref(), Some("line 1, column 5"));
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_yaml_error_position() {
+        let err = serde_yaml::from_str::<serde_yaml::Value>("a: 1\nb: [1, 2\n").unwrap_err();
+        let se: StructError<UvsReason> = err.into();
+        assert!(se.position().is_some());
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_toml_error_position() {
+        let err = toml::from_str::<toml::Value>("a = 1\nb = \n").unwrap_err();
+        let se: StructError<UvsReason> = err.into();
+        assert_eq!(
+            se.position().as_deref(),
+            Some("line 2, column 5, offset 10")
+        );
+    }
+}
@@ -0,0 +1,198 @@
+//! RFC 7807 (`application/problem+json`) 响应体，供 web 框架集成使用
+
+use std::{
+    fmt::{self, Display, Write},
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+use super::{
+    domain::DomainReason,
+    format::{write_json_contexts, write_json_opt, write_json_str, ErrorView},
+    ErrorCode, HttpStatus, StructError,
+};
+
+pub const PROBLEM_CONTENT_TYPE: &str = "application/problem+json";
+
+const EXPOSE_UNSET: u8 = 0;
+const EXPOSE_ON: u8 = 1;
+const EXPOSE_OFF: u8 = 2;
+
+static EXPOSE_INTERNAL: AtomicU8 = AtomicU8::new(EXPOSE_UNSET);
+
+/// 是否在响应中暴露内部细节（`detail`、`position`、上下文）
+///
+/// 未设置时 debug 构建暴露、release 构建隐藏；错误 ID 与关联 ID 始终保留以便排查。
+pub fn set_expose_internal(expose: bool) {
+    let v = if expose { EXPOSE_ON } else { EXPOSE_OFF };
+    EXPOSE_INTERNAL.store(v, Ordering::Relaxed);
+}
+
+pub fn expose_internal() -> bool {
+    match EXPOSE_INTERNAL.load(Ordering::Relaxed) {
+        EXPOSE_ON => true,
+        EXPOSE_OFF => false,
+        _ => cfg!(debug_assertions),
+    }
+}
+
+impl<T> StructError<T>
+where
+    T: DomainReason + ErrorCode + HttpStatus + Display,
+{
+    /// 渲染 problem+json 响应体，是否包含内部细节由 `expose_internal()` 决定
+    pub fn to_problem_json(&self) -> String {
+        let mut out = String::new();
+        let _ = write_problem(
+            &self.view(),
+            self.http_status(),
+            expose_internal(),
+            &mut out,
+        );
+        out
+    }
+}
+
+fn write_problem(
+    view: &ErrorView<'_>,
+    status: u16,
+    expose: bool,
+    out: &mut dyn Write,
+) -> fmt::Result {
+    out.write_str("{\"type\":\"about:blank\",\"title\":")?;
+    write_json_str(out, &view.reason)?;
+    write!(out, ",\"status\":{status},\"code\":{}", view.code)?;
+    if let Some(domain) = &view.domain {
+        out.write_str(",\"domain\":")?;
+        write_json_str(out, domain)?;
+    }
+    if let Some(id) = view.id {
+        out.write_str(",\"id\":")?;
+        write_json_str(out, id)?;
+    }
+    if let Some(cid) = view.correlation_id {
+        out.write_str(",\"correlation_id\":")?;
+        write_json_str(out, cid)?;
+    }
+    if expose {
+        out.write_str(",\"detail\":")?;
+        write_json_opt(out, view.detail)?;
+        out.write_str(",\"position\":")?;
+        write_json_opt(out, view.position)?;
+        out.write_str(",\"context\":")?;
+        write_json_contexts(out, view.contexts)?;
+    }
+    out.write_char('}')
+}
+
+#[cfg(feature = "axum")]
+impl<T> axum::response::IntoResponse for StructError<T>
+where
+    T: DomainReason + ErrorCode + HttpStatus + Display,
+{
+    fn into_response(self) -> axum::response::Response {
+        let status = axum::http::StatusCode::from_u16(self.http_status())
+            .unwrap_or(axum::http::StatusCode::INTERNAL_SERVER_ERROR);
+        let retry_after = self
+            .retry_after_header()
+            .map(|v| [(axum::http::header::RETRY_AFTER, v)]);
+        (
+            status,
+            [(axum::http::header::CONTENT_TYPE, PROBLEM_CONTENT_TYPE)],
+            retry_after,
+            self.to_problem_json(),
+        )
+            .into_response()
+    }
+}
+
+#[cfg(feature = "actix")]
+impl<T> actix_web::ResponseError for StructError<T>
+where
+    T: DomainReason + ErrorCode + HttpStatus + Display + fmt::Debug,
+{
+    fn status_code(&self) -> actix_web::http::StatusCode {
+        actix_web::http::StatusCode::from_u16(self.http_status())
+            .unwrap_or(actix_web::http::StatusCode::INTERNAL_SERVER_ERROR)
+    }
+
+    fn error_response(&self) -> actix_web::HttpResponse {
+        let mut resp = actix_web::HttpResponse::build(self.status_code());
+        if let Some(v) = self.retry_after_header() {
+            resp.insert_header((actix_web::http::header::RETRY_AFTER, v));
+        }
+        resp.content_type(PROBLEM_CONTENT_TYPE)
+            .body(self.to_problem_json())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ErrorWith, UvsReason};
+
+    #[test]
+    fn test_problem_json_hides_internal() {
+        let err = StructError::from(UvsReason::not_found_error())
+            .with_detail("select * from users where id=7")
+            .with_position("src/repo.rs:12")
+            .with_correlation_id("req-1")
+            .want("load user");
+        let view = err.view();
+
+        let mut public = String::new();
+        write_problem(&view, err.http_status(), false, &mut public).unwrap();
+        assert_eq!(
+            public,
+            r#"{"type":"about:blank","title":"not found error","status":404,"code":102,"correlation_id":"req-1"}"#
+        );
+
+        let mut internal = String::new();
+        write_problem(&view, err.http_status(), true, &mut internal).unwrap();
+        assert!(internal.contains(r#""detail":"select * from users where id=7""#));
+        assert!(internal.contains(r#""position":"src/repo.rs:12""#));
+        assert!(internal.contains(r#""target":"load user""#));
+    }
+
+    #[cfg(feature = "axum")]
+    #[test]
+    fn test_axum_into_response() {
+        use axum::response::IntoResponse;
+
+        let resp = StructError::from(UvsReason::validation_error()).into_response();
+        assert_eq!(resp.status(), 400);
+        assert_eq!(
+            resp.headers()[axum::http::header::CONTENT_TYPE],
+            PROBLEM_CONTENT_TYPE
+        );
+        assert!(resp
+            .headers()
+            .get(axum::http::header::RETRY_AFTER)
+            .is_none());
+
+        let resp = StructError::from(UvsReason::resource_error())
+            .with_retry_after(std::time::Duration::from_secs(5))
+            .into_response();
+        assert_eq!(resp.status(), 503);
+        assert_eq!(resp.headers()[axum::http::header::RETRY_AFTER], "5");
+    }
+
+    #[cfg(feature = "actix")]
+    #[test]
+    fn test_actix_response_error() {
+        use actix_web::ResponseError;
+
+        let err = StructError::from(UvsReason::timeout_error());
+        assert_eq!(err.status_code().as_u16(), 504);
+        assert_eq!(err.error_response().status().as_u16(), 504);
+
+        let resp = StructError::from(UvsReason::external_error())
+            .with_retry_after(std::time::Duration::from_secs(2))
+            .error_response();
+        assert_eq!(
+            resp.headers()
+                .get(actix_web::http::header::RETRY_AFTER)
+                .unwrap(),
+            "2"
+        );
+    }
+}
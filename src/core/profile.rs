@@ -0,0 +1,171 @@
+//! 序列化档位：同一个错误对用户只暴露必要信息，对内部日志完整输出
+
+use serde::{Serialize, Serializer};
+
+use super::{
+    domain::DomainReason,
+    error::{StructErrorImpl, ERROR_SCHEMA_VERSION},
+    redact::{is_sensitive_key, REDACTED},
+    OperationContext, StructError,
+};
+
+/// 序列化档位
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SerializeProfile {
+    /// 面向最终用户：仅原因、目标、错误 ID 与非敏感的上下文条目，
+    /// 不含 `detail`、位置、错误源与构建信息
+    Public,
+    /// 面向内部日志：与直接序列化 `StructError` 相同
+    #[default]
+    Internal,
+    /// 排障用：在内部档位基础上附带调用栈与完整的错误链
+    Debug,
+}
+
+/// 按档位序列化的视图，可交给任意 serde 格式，例如 `serde_json::to_string(&err.serialize_public())`
+pub struct Profiled<'a, T: DomainReason> {
+    err: &'a StructError<T>,
+    profile: SerializeProfile,
+}
+
+impl<T: DomainReason> StructError<T> {
+    pub fn serialize_with_profile(&self, profile: SerializeProfile) -> Profiled<'_, T> {
+        Profiled { err: self, profile }
+    }
+
+    pub fn serialize_public(&self) -> Profiled<'_, T> {
+        self.serialize_with_profile(SerializeProfile::Public)
+    }
+
+    pub fn serialize_internal(&self) -> Profiled<'_, T> {
+        self.serialize_with_profile(SerializeProfile::Internal)
+    }
+
+    pub fn serialize_debug(&self) -> Profiled<'_, T> {
+        self.serialize_with_profile(SerializeProfile::Debug)
+    }
+}
+
+impl<T> Serialize for Profiled<'_, T>
+where
+    T: DomainReason + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let err = self.err;
+        match self.profile {
+            SerializeProfile::Internal => err.serialize(serializer),
+            SerializeProfile::Public => PublicView {
+                schema_version: ERROR_SCHEMA_VERSION,
+                reason: err.reason(),
+                target: err.target(),
+                id: err.id().as_deref(),
+                correlation_id: err.correlation_id().as_deref(),
+                context: err.contexts().iter().map(PublicFrame::new).collect(),
+            }
+            .serialize(serializer),
+            SerializeProfile::Debug => DebugView {
+                imp: err.imp(),
+                backtrace: err.backtrace().map(ToString::to_string),
+                source_chain: err.source_chain().map(|e| e.to_string()).collect(),
+            }
+            .serialize(serializer),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct PublicView<'a, T> {
+    schema_version: u32,
+    reason: &'a T,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    correlation_id: Option<&'a str>,
+    context: Vec<PublicFrame<'a>>,
+}
+
+/// 公开的上下文帧：只保留目标与非敏感条目（已脱敏的值同样省略）
+#[derive(Serialize)]
+struct PublicFrame<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target: Option<&'a str>,
+    items: Vec<(&'a str, &'a str)>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    children: Vec<PublicFrame<'a>>,
+}
+
+impl<'a> PublicFrame<'a> {
+    fn new(ctx: &'a OperationContext) -> Self {
+        Self {
+            target: ctx.target().as_deref(),
+            items: ctx
+                .context()
+                .items
+                .iter()
+                .filter(|(k, v)| v != REDACTED && !is_sensitive_key(k))
+                .map(|(k, v)| (k.as_ref(), v.as_str()))
+                .collect(),
+            children: ctx.children().iter().map(PublicFrame::new).collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct DebugView<'a, T: DomainReason> {
+    #[serde(flatten)]
+    imp: &'a StructErrorImpl<T>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    backtrace: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    source_chain: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ContextRecord, ErrorWith, UvsReason};
+
+    fn sample() -> StructError<UvsReason> {
+        let mut ctx = OperationContext::want("charge card");
+        ctx.record("order_id", "A-1");
+        ctx.record("card_token", REDACTED);
+        StructError::from(UvsReason::external_error())
+            .with_detail("gateway said: insufficient funds")
+            .with_position("src/pay.rs:10:1")
+            .with_error_id("01HX")
+            .with_source(std::io::Error::other("connection reset"))
+            .with(ctx)
+    }
+
+    #[test]
+    fn test_public_profile_hides_internals() {
+        let json = serde_json::to_value(sample().serialize_public()).unwrap();
+        assert_eq!(json["reason"], "ExternalError");
+        assert_eq!(json["target"], "charge card");
+        assert_eq!(json["id"], "01HX");
+        assert_eq!(
+            json["context"][0]["items"],
+            serde_json::json!([["order_id", "A-1"]])
+        );
+        for hidden in ["detail", "position", "source", "app", "timestamp"] {
+            assert!(json.get(hidden).is_none(), "{hidden} leaked");
+        }
+    }
+
+    #[test]
+    fn test_internal_and_debug_profiles() {
+        let err = sample();
+        let internal = serde_json::to_value(err.serialize_internal()).unwrap();
+        assert_eq!(internal, serde_json::to_value(&err).unwrap());
+        assert_eq!(internal["position"], "src/pay.rs:10:1");
+
+        let debug = serde_json::to_value(err.serialize_debug()).unwrap();
+        assert_eq!(debug["detail"], internal["detail"]);
+        assert_eq!(
+            debug["source_chain"],
+            serde_json::json!(["connection reset"])
+        );
+    }
+}
@@ -1,5 +1,174 @@
+use std::fmt::{self, Display};
+
 pub trait ErrorCode {
     fn error_code(&self) -> i32 {
         500
     }
+
+    /// 自描述的错误码，默认只有数值；通用原因带 `UVS` 命名空间与类别名
+    fn code(&self) -> Code {
+        Code::new(self.error_code())
+    }
+
+    /// 领域错误码基址：包装的 Uvs 原因按 `base + uvs_code` 组合（默认 0，沿用 Uvs 码），
+    /// 配合 `AsUvsReason::offset_uvs_code` 使用
+    fn error_code_base(&self) -> i32 {
+        0
+    }
+}
+
+/// 错误码命名空间：字符串缩写（如 `"ORD"`）或数字编号（如 `42`）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Namespace {
+    Name(&'static str),
+    Id(u32),
+}
+
+impl Display for Namespace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Namespace::Name(name) => f.write_str(name),
+            Namespace::Id(id) => write!(f, "{id}"),
+        }
+    }
+}
+
+/// 自描述的错误码：命名空间、数值与名称，显示为 `UVS-0201:system`，
+/// 无命名空间时为 `201`；日志与 JSON 中可替代裸整数
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Code {
+    domain: Option<Namespace>,
+    number: i32,
+    name: Option<&'static str>,
+}
+
+impl Code {
+    pub fn new(number: i32) -> Self {
+        Self {
+            domain: None,
+            number,
+            name: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_domain(mut self, domain: Namespace) -> Self {
+        self.domain = Some(domain);
+        self
+    }
+
+    #[must_use]
+    pub fn with_name(mut self, name: &'static str) -> Self {
+        self.name = Some(name);
+        self
+    }
+
+    pub fn domain(&self) -> Option<Namespace> {
+        self.domain
+    }
+
+    /// 与 `error_code()` 相同的数值
+    pub fn number(&self) -> i32 {
+        self.number
+    }
+
+    pub fn name(&self) -> Option<&'static str> {
+        self.name
+    }
+}
+
+impl From<Code> for i32 {
+    fn from(code: Code) -> Self {
+        code.number
+    }
+}
+
+impl Display for Code {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.domain {
+            Some(domain) => write!(f, "{domain}-{:04}", self.number)?,
+            None => write!(f, "{}", self.number)?,
+        }
+        if let Some(name) = self.name {
+            write!(f, ":{name}")?;
+        }
+        Ok(())
+    }
+}
+
+/// 序列化为 `{"domain":"UVS","number":201,"name":"system"}`，缺省的部分省略
+#[cfg(feature = "serde")]
+impl serde::Serialize for Code {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let len = 1 + usize::from(self.domain.is_some()) + usize::from(self.name.is_some());
+        let mut s = serializer.serialize_struct("Code", len)?;
+        match self.domain {
+            Some(domain) => s.serialize_field("domain", &domain.to_string())?,
+            None => s.skip_field("domain")?,
+        }
+        s.serialize_field("number", &self.number)?;
+        match self.name {
+            Some(name) => s.serialize_field("name", name)?,
+            None => s.skip_field("name")?,
+        }
+        s.end()
+    }
+}
+
+/// 为错误码提供服务/领域命名空间，多个服务共用 Uvs 码段时用于区分来源
+pub trait DomainCode: ErrorCode {
+    fn domain(&self) -> Namespace;
+
+    /// 组合错误码，例如 `ORD-0101`
+    fn domain_code(&self) -> String {
+        format!("{}-{:04}", self.domain(), self.error_code())
+    }
+}
+
+/// 错误对应的 HTTP 状态码，默认 500
+pub trait HttpStatus {
+    fn http_status(&self) -> u16 {
+        500
+    }
+}
+
+/// 错误对应的进程退出码，默认 1（通用失败）
+pub trait ErrorExitCode {
+    fn exit_code(&self) -> i32 {
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::UvsReason;
+
+    #[test]
+    fn test_code_display() {
+        assert_eq!(Code::new(500).to_string(), "500");
+        let code = Code::new(101)
+            .with_domain(Namespace::Name("ORD"))
+            .with_name("locked");
+        assert_eq!(code.to_string(), "ORD-0101:locked");
+        assert_eq!(i32::from(code), 101);
+        assert_eq!(
+            UvsReason::system_error().code().to_string(),
+            "UVS-0201:system"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_code_serialize() {
+        let json = serde_json::to_value(UvsReason::not_found_error().code()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"domain": "UVS", "number": 102, "name": "not_found"})
+        );
+        let json = serde_json::to_value(Code::new(7)).unwrap();
+        assert_eq!(json, serde_json::json!({"number": 7}));
+    }
 }
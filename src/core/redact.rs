@@ -0,0 +1,140 @@
+use std::{borrow::Cow, sync::RwLock};
+
+/// 脱敏后的占位文本
+pub const REDACTED: &str = "******";
+
+static SENSITIVE_KEYS: RwLock<Vec<String>> = RwLock::new(Vec::new());
+
+/// 注册全局敏感键模式（不区分大小写的子串匹配），例如 `"password"`、`"token"`
+///
+/// 匹配的上下文键在记录时即被脱敏，`detail` 中形如 `key=value` / `key: value` 的值同样会被遮盖，
+/// 因此 `Display`、序列化与退出日志都不会包含原值。
+pub fn register_sensitive_key<S: Into<String>>(pattern: S) {
+    let pattern = pattern.into().to_ascii_lowercase();
+    if pattern.is_empty() {
+        return;
+    }
+    let mut keys = SENSITIVE_KEYS.write().unwrap_or_else(|e| e.into_inner());
+    if !keys.contains(&pattern) {
+        keys.push(pattern);
+    }
+}
+
+/// 清空全局敏感键模式
+pub fn clear_sensitive_keys() {
+    SENSITIVE_KEYS
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .clear();
+}
+
+/// 判断键是否命中已注册的敏感模式
+pub fn is_sensitive_key(key: &str) -> bool {
+    let keys = SENSITIVE_KEYS.read().unwrap_or_else(|e| e.into_inner());
+    if keys.is_empty() {
+        return false;
+    }
+    let key = key.to_ascii_lowercase();
+    keys.iter().any(|p| key.contains(p.as_str()))
+}
+
+/// 遮盖文本中敏感键对应的值（`key=value`、`key: value`）
+pub fn redact_text(text: &str) -> Cow<'_, str> {
+    let keys = SENSITIVE_KEYS.read().unwrap_or_else(|e| e.into_inner());
+    if keys.is_empty() {
+        return Cow::Borrowed(text);
+    }
+    let lower = text.to_ascii_lowercase();
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for pattern in keys.iter() {
+        let mut from = 0;
+        while let Some(found) = lower[from..].find(pattern.as_str()) {
+            let key_end = from + found + pattern.len();
+            from = key_end;
+            if let Some(range) = value_range(&lower, key_end) {
+                ranges.push(range);
+            }
+        }
+    }
+    if ranges.is_empty() {
+        return Cow::Borrowed(text);
+    }
+    ranges.sort_unstable();
+    let mut out = String::with_capacity(text.len());
+    let mut last = 0;
+    for (start, end) in ranges {
+        if start < last {
+            continue;
+        }
+        out.push_str(&text[last..start]);
+        out.push_str(REDACTED);
+        last = end;
+    }
+    out.push_str(&text[last..]);
+    Cow::Owned(out)
+}
+
+/// `redact_text` 的所有权版本，未命中时不重新分配
+pub(crate) fn redact_owned(text: String) -> String {
+    match redact_text(&text) {
+        Cow::Borrowed(_) => text,
+        Cow::Owned(masked) => masked,
+    }
+}
+
+/// 定位键之后的值区间：跳过键名剩余字符与分隔符（`=` 或 `:`）
+fn value_range(lower: &str, key_end: usize) -> Option<(usize, usize)> {
+    let bytes = lower.as_bytes();
+    let mut i = key_end;
+    while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+        i += 1;
+    }
+    while i < bytes.len() && bytes[i] == b' ' {
+        i += 1;
+    }
+    if i >= bytes.len() || !(bytes[i] == b'=' || bytes[i] == b':') {
+        return None;
+    }
+    i += 1;
+    while i < bytes.len() && bytes[i] == b' ' {
+        i += 1;
+    }
+    let quote = matches!(bytes.get(i), Some(b'"') | Some(b'\''));
+    if quote {
+        i += 1;
+    }
+    let start = i;
+    while i < bytes.len() {
+        let b = bytes[i];
+        let stop = if quote {
+            b == b'"' || b == b'\''
+        } else {
+            b.is_ascii_whitespace() || matches!(b, b',' | b';' | b'&' | b'"' | b'\'')
+        };
+        if stop {
+            break;
+        }
+        i += 1;
+    }
+    (i > start).then_some((start, i))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_text_masks_registered_keys() {
+        register_sensitive_key("ut_redact_pwd");
+        assert!(is_sensitive_key("db_UT_REDACT_PWD"));
+        assert_eq!(
+            redact_text("user=alice ut_redact_pwd=hunter2, host=db"),
+            "user=alice ut_redact_pwd=******, host=db"
+        );
+        assert_eq!(
+            redact_text("ut_redact_pwd: \"p w\" end"),
+            "ut_redact_pwd: \"******\" end"
+        );
+        assert_eq!(redact_text("no secrets here"), "no secrets here");
+    }
+}
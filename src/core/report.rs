@@ -0,0 +1,375 @@
+//! 错误上报：对重复错误限流，避免抖动的依赖刷屏日志
+
+use std::{
+    collections::HashMap,
+    fmt::{self, Display, Write as _},
+    fs::{File, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    sync::Mutex,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+#[cfg(all(feature = "log-kv", not(feature = "tracing")))]
+use super::context::emit_log_kv;
+use super::{
+    context::{emit_log, OperationContext},
+    domain::DomainReason,
+    fingerprint::{DefaultFingerprint, Fingerprint},
+    format::{write_json_opt, write_json_str},
+    severity::SeverityOf,
+    AsUvsReason, ErrorCode, Severity, StructError,
+};
+
+type Sink = Box<dyn Fn(Severity, &str) + Send + Sync>;
+
+struct Window {
+    label: String,
+    start: Instant,
+    suppressed: u64,
+}
+
+/// 时间窗口内相同的错误（指纹与目标相同）只输出一次，窗口结束后汇总为 "repeated N times"
+pub struct ThrottledReporter {
+    window: Duration,
+    level: Severity,
+    sink: Sink,
+    fingerprint: Box<dyn Fingerprint + Send + Sync>,
+    seen: Mutex<HashMap<(u64, Option<String>), Window>>,
+}
+
+impl ThrottledReporter {
+    /// 默认以 `Error` 级别输出到 log / tracing
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            level: Severity::Error,
+            sink: Box::new(|level, msg| emit_log(level, module_path!(), format_args!("{msg}"))),
+            fingerprint: Box::new(DefaultFingerprint),
+            seen: Mutex::new(HashMap::new()),
+        }
+    }
+
+    #[must_use]
+    pub fn with_level(mut self, level: Severity) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// 自定义输出目标，例如写入告警通道或测试收集器
+    #[must_use]
+    pub fn with_sink<F>(mut self, sink: F) -> Self
+    where
+        F: Fn(Severity, &str) + Send + Sync + 'static,
+    {
+        self.sink = Box::new(sink);
+        self
+    }
+
+    /// 自定义判定“相同错误”的指纹算法
+    #[must_use]
+    pub fn with_fingerprint<F>(mut self, fingerprint: F) -> Self
+    where
+        F: Fingerprint + Send + Sync + 'static,
+    {
+        self.fingerprint = Box::new(fingerprint);
+        self
+    }
+
+    /// 上报错误；返回是否实际输出（窗口内的重复错误只计数）
+    pub fn report<R>(&self, err: &StructError<R>) -> bool
+    where
+        R: DomainReason + ErrorCode + Display,
+    {
+        let view = err.view();
+        let key = (self.fingerprint.fingerprint(&view), view.target.clone());
+        let now = Instant::now();
+        let mut seen = self.seen.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(w) = seen.get_mut(&key) {
+            if now.duration_since(w.start) < self.window {
+                w.suppressed += 1;
+                return false;
+            }
+            if w.suppressed > 0 {
+                self.summary(&w.label, w.suppressed);
+            }
+            w.start = now;
+            w.suppressed = 0;
+        } else {
+            seen.insert(
+                key,
+                Window {
+                    label: Self::label(&view.code_label(), &view.reason, view.target.as_deref()),
+                    start: now,
+                    suppressed: 0,
+                },
+            );
+        }
+        (self.sink)(self.level, &err.to_string());
+        true
+    }
+
+    /// 输出已过期窗口的重复汇总并清理，适合由定时任务调用
+    pub fn flush(&self) {
+        let now = Instant::now();
+        let mut seen = self.seen.lock().unwrap_or_else(|e| e.into_inner());
+        seen.retain(|_, w| {
+            if now.duration_since(w.start) < self.window {
+                return true;
+            }
+            if w.suppressed > 0 {
+                self.summary(&w.label, w.suppressed);
+            }
+            false
+        });
+    }
+
+    fn summary(&self, label: &str, count: u64) {
+        let msg = format!("{label} repeated {count} times in {:?}", self.window);
+        (self.sink)(self.level, &msg);
+    }
+
+    fn label(code: &str, reason: &str, target: Option<&str>) -> String {
+        match target {
+            Some(target) => format!("[{code}] {reason} @ {target}"),
+            None => format!("[{code}] {reason}"),
+        }
+    }
+}
+
+impl<R> StructError<R>
+where
+    R: DomainReason + ErrorCode + SeverityOf + Display,
+{
+    /// 按原因的严重级别输出单行紧凑格式，代替 `error!("{}", e)`；
+    /// 启用 `log-kv` 时错误码、目标与上下文条目同时作为结构化字段输出
+    pub fn log(&self) {
+        self.log_at(self.severity());
+    }
+}
+
+impl<R> StructError<R>
+where
+    R: DomainReason + ErrorCode + Display,
+{
+    /// 以指定级别输出，格式与 `log` 相同
+    pub fn log_at(&self, level: Severity) {
+        let line = self.to_compact_string();
+        log_error(self, level, format_args!("{line}"), format_args!("{line}"));
+    }
+}
+
+/// 输出结构化错误，日志 target 取最近一层上下文的模块路径；
+/// 启用 `log-kv`（且未启用 tracing）时以 `kv_msg` 为消息并附带错误码、detail、目标与上下文键值，否则输出 `msg`
+pub(crate) fn log_error<R>(
+    err: &StructError<R>,
+    level: Severity,
+    kv_msg: fmt::Arguments<'_>,
+    msg: fmt::Arguments<'_>,
+) where
+    R: DomainReason + ErrorCode,
+{
+    let mod_path = err
+        .context()
+        .last()
+        .map(|c| c.mod_path().as_str())
+        .unwrap_or(module_path!());
+    #[cfg(all(feature = "log-kv", not(feature = "tracing")))]
+    {
+        let _ = msg;
+        let code = err.error_code().to_string();
+        let mut kvs = vec![("code", code.as_str())];
+        if let Some(detail) = err.detail() {
+            kvs.push(("detail", detail.as_str()));
+        }
+        for ctx in err.contexts() {
+            if let Some(target) = ctx.target() {
+                kvs.push(("target", target.as_str()));
+            }
+            kvs.extend(ctx.context().render_items());
+        }
+        emit_log_kv(level, mod_path, kv_msg, &kvs);
+    }
+    #[cfg(not(all(feature = "log-kv", not(feature = "tracing"))))]
+    {
+        let _ = kv_msg;
+        emit_log(level, mod_path, msg);
+    }
+}
+
+/// JSON Lines 错误日志：每个错误一行 JSON，便于直接投递到 ELK 等日志系统，不依赖 serde
+///
+/// 每行字段：`ts`（Unix 毫秒，优先取错误创建时间）、`code`、`category`、`reason`、`detail`、
+/// `target`、`position`、`id`、`correlation_id` 以及展平后的 `context` 对象（同名键以最靠近出错位置的帧为准）。
+pub struct JsonlSink<W: Write> {
+    writer: Mutex<W>,
+}
+
+impl JsonlSink<File> {
+    /// 以追加模式打开（不存在时创建）日志文件
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self::new(file))
+    }
+}
+
+impl<W: Write> JsonlSink<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer: Mutex::new(writer),
+        }
+    }
+
+    /// 追加一行并立即刷新
+    pub fn write<R>(&self, err: &StructError<R>) -> io::Result<()>
+    where
+        R: DomainReason + ErrorCode + AsUvsReason + Display,
+    {
+        let mut line = String::new();
+        write_jsonl(err, &mut line).map_err(|_| io::Error::other("format error"))?;
+        line.push('\n');
+        let mut w = self.writer.lock().unwrap_or_else(|e| e.into_inner());
+        w.write_all(line.as_bytes())?;
+        w.flush()
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer.into_inner().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn write_jsonl<R>(err: &StructError<R>, out: &mut String) -> fmt::Result
+where
+    R: DomainReason + ErrorCode + AsUvsReason + Display,
+{
+    let view = err.view();
+    let ts = err
+        .timestamp()
+        .unwrap_or_else(SystemTime::now)
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+    write!(out, "{{\"ts\":{ts},\"code\":{},\"category\":", view.code)?;
+    write_json_opt(out, err.as_uvs().map(|u| u.category_name()))?;
+    out.push_str(",\"reason\":");
+    write_json_str(out, &view.reason)?;
+    for (key, value) in [
+        ("detail", view.detail),
+        ("target", view.target.as_deref()),
+        ("position", view.position),
+        ("id", view.id),
+        ("correlation_id", view.correlation_id),
+    ] {
+        if let Some(value) = value {
+            write!(out, ",\"{key}\":")?;
+            write_json_str(out, value)?;
+        }
+    }
+    let mut flat: Vec<(&str, &str)> = Vec::new();
+    flatten_items(view.contexts, &mut flat);
+    out.push_str(",\"context\":{");
+    for (i, (k, v)) in flat.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        write_json_str(out, k)?;
+        out.push(':');
+        write_json_str(out, v)?;
+    }
+    out.push_str("}}");
+    Ok(())
+}
+
+fn flatten_items<'a>(contexts: &'a [OperationContext], flat: &mut Vec<(&'a str, &'a str)>) {
+    // 首个上下文帧最靠近出错位置：跨帧保留先出现的键，同一帧内以最后记录的值为准
+    for ctx in contexts {
+        let start = flat.len();
+        for (k, v) in ctx.context().render_items() {
+            if let Some(slot) = flat[start..].iter_mut().find(|(fk, _)| *fk == k) {
+                slot.1 = v;
+            } else if !flat[..start].iter().any(|(fk, _)| *fk == k) {
+                flat.push((k, v));
+            }
+        }
+        flatten_items(ctx.children(), flat);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ContextRecord, ErrorWith, UvsReason};
+    use std::sync::Arc;
+
+    fn collector(window: Duration) -> (ThrottledReporter, Arc<Mutex<Vec<String>>>) {
+        let out = Arc::new(Mutex::new(Vec::new()));
+        let sink = out.clone();
+        let reporter = ThrottledReporter::new(window)
+            .with_sink(move |_, msg| sink.lock().unwrap().push(msg.to_string()));
+        (reporter, out)
+    }
+
+    #[test]
+    fn test_suppress_within_window() {
+        let (reporter, out) = collector(Duration::from_secs(60));
+        let err = StructError::from(UvsReason::network_error()).want("fetch quote");
+        assert!(reporter.report(&err));
+        assert!(!reporter.report(&err));
+        assert!(!reporter.report(&err));
+        // 不同目标视为不同错误；仅数字不同的 detail 视为相同错误
+        assert!(reporter.report(&err.clone().want("fetch rate")));
+        assert!(reporter.report(&err.clone().with_detail("attempt 1")));
+        assert!(!reporter.report(&err.clone().with_detail("attempt 2")));
+        assert_eq!(out.lock().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_jsonl_sink_lines() {
+        let mut ctx = OperationContext::want("load order");
+        ctx.record("order_id", "A-1");
+        ctx.record("path", "a\"b");
+        let mut inner = OperationContext::want("read file");
+        inner.record("path", "/tmp/o.json");
+        let err = StructError::from(UvsReason::not_found_error())
+            .with_detail("missing")
+            .with(ctx)
+            .with(inner);
+
+        let sink = JsonlSink::new(Vec::new());
+        sink.write(&err).unwrap();
+        sink.write(&StructError::from(UvsReason::network_error()))
+            .unwrap();
+        let text = String::from_utf8(sink.into_inner()).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 2);
+
+        let v: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert!(v["ts"].as_u64().unwrap() > 0);
+        assert_eq!(v["code"], 102);
+        assert_eq!(v["category"], "not_found");
+        assert_eq!(v["detail"], "missing");
+        assert_eq!(v["target"], "load order");
+        assert_eq!(v["context"]["order_id"], "A-1");
+        assert_eq!(v["context"]["path"], "a\"b");
+
+        let v: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(v["category"], "network");
+        assert!(v.get("detail").is_none());
+    }
+
+    #[test]
+    fn test_summary_after_window() {
+        let (reporter, out) = collector(Duration::from_millis(5));
+        let err = StructError::from(UvsReason::timeout_error());
+        reporter.report(&err);
+        reporter.report(&err);
+        reporter.report(&err);
+        std::thread::sleep(Duration::from_millis(10));
+        reporter.flush();
+
+        let out = out.lock().unwrap();
+        assert_eq!(out.len(), 2);
+        assert!(out[1].contains("repeated 2 times"));
+        assert!(out[1].starts_with("[204]"));
+    }
+}
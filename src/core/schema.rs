@@ -0,0 +1,118 @@
+//! 按错误原因声明期望的上下文键，并提供带类型的上下文取值
+
+use std::str::FromStr;
+
+use super::{
+    context::emit_log, domain::DomainReason, OperationContext, Severity, StructError, UvsReason,
+};
+
+/// 领域原因声明构造错误时应记录的上下文键，例如 `NotFound` 需要 `resource` 与 `id`
+pub trait ContextSchema {
+    fn required_keys(&self) -> &'static [&'static str] {
+        &[]
+    }
+}
+
+/// 通用原因的结构化变体已携带数据，不要求额外的上下文键
+impl ContextSchema for UvsReason {}
+
+impl<T: DomainReason + ContextSchema> StructError<T> {
+    /// 原因声明但所有上下文帧中都未记录的键
+    pub fn missing_context_keys(&self) -> Vec<&'static str> {
+        self.reason()
+            .required_keys()
+            .iter()
+            .copied()
+            .filter(|key| find_value(self.contexts(), key).is_none())
+            .collect()
+    }
+
+    /// 调试构建下缺少声明的上下文键时输出警告；发布构建不做检查
+    #[must_use]
+    pub fn check_schema(self) -> Self {
+        if cfg!(debug_assertions) {
+            let missing = self.missing_context_keys();
+            if !missing.is_empty() {
+                emit_log(
+                    Severity::Warn,
+                    module_path!(),
+                    format_args!(
+                        "error `{}` built without context keys: {}",
+                        self.reason(),
+                        missing.join(", ")
+                    ),
+                );
+            }
+        }
+        self
+    }
+}
+
+impl<T: DomainReason> StructError<T> {
+    /// 按类型解析上下文值，例如 `err.ctx_value::<u64>("user_id")`；
+    /// 多个帧记录同名键时取最靠近出错位置的帧，键不存在或解析失败时返回 `None`
+    pub fn ctx_value<V: FromStr>(&self, key: &str) -> Option<V> {
+        find_value(self.contexts(), key)?.parse().ok()
+    }
+}
+
+fn find_value<'a>(frames: &'a [OperationContext], key: &str) -> Option<&'a str> {
+    frames
+        .iter()
+        .find_map(|ctx| ctx.get(key).or_else(|| find_value(ctx.children(), key)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ContextRecord, ErrorWith};
+    use derive_more::From;
+    use thiserror::Error;
+
+    #[derive(Debug, PartialEq, Error, From)]
+    enum UserReason {
+        #[error("user not found")]
+        NotFound,
+        #[error("{0}")]
+        Uvs(UvsReason),
+    }
+
+    impl ContextSchema for UserReason {
+        fn required_keys(&self) -> &'static [&'static str] {
+            match self {
+                Self::NotFound => &["resource", "id"],
+                Self::Uvs(_) => &[],
+            }
+        }
+    }
+
+    #[test]
+    fn test_missing_context_keys() {
+        let err = StructError::from(UserReason::NotFound).with_kv("resource", "user");
+        assert_eq!(err.missing_context_keys(), ["id"]);
+        let err = err.with_kv("id", 42).check_schema();
+        assert!(err.missing_context_keys().is_empty());
+
+        let err = StructError::from(UserReason::from(UvsReason::system_error())).check_schema();
+        assert!(err.missing_context_keys().is_empty());
+    }
+
+    #[test]
+    fn test_ctx_value_typed() {
+        let mut inner = OperationContext::want("load user");
+        inner.record("user_id", "42");
+        inner.child("query").record("elapsed_ms", "17");
+        let mut outer = OperationContext::want("handle request");
+        outer.record("user_id", "7");
+        let err = StructError::from(UserReason::NotFound)
+            .with(inner)
+            .with(outer)
+            .with_kv("retry", "true");
+
+        assert_eq!(err.ctx_value::<u64>("user_id"), Some(42));
+        assert_eq!(err.ctx_value::<u32>("elapsed_ms"), Some(17));
+        assert_eq!(err.ctx_value::<bool>("retry"), Some(true));
+        assert_eq!(err.ctx_value::<u64>("retry"), None);
+        assert_eq!(err.ctx_value::<String>("missing"), None);
+    }
+}
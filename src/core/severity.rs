@@ -0,0 +1,33 @@
+use std::fmt::{self, Display};
+
+/// 错误严重级别，按从低到高排序，用于选择日志级别与告警策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Severity {
+    Debug,
+    Info,
+    Warn,
+    #[default]
+    Error,
+    Critical,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Debug => "debug",
+            Severity::Info => "info",
+            Severity::Warn => "warn",
+            Severity::Error => "error",
+            Severity::Critical => "critical",
+        })
+    }
+}
+
+/// 领域 reason 的严重级别，默认 `Severity::Error`
+pub trait SeverityOf {
+    fn severity(&self) -> Severity {
+        Severity::Error
+    }
+}
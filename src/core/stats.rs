@@ -0,0 +1,93 @@
+//! 按操作目标统计作用域的成功、失败与取消次数，无需单独的指标层即可导出错误率
+
+use std::{
+    collections::BTreeMap,
+    sync::{Mutex, OnceLock},
+};
+
+use super::context::OperationResult;
+
+/// 单个操作目标的累计结果
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OpStats {
+    pub success: u64,
+    pub failure: u64,
+    pub cancel: u64,
+}
+
+impl OpStats {
+    pub fn total(&self) -> u64 {
+        self.success + self.failure + self.cancel
+    }
+
+    /// 失败占比，尚无记录时为 0
+    pub fn error_rate(&self) -> f64 {
+        match self.total() {
+            0 => 0.0,
+            total => self.failure as f64 / total as f64,
+        }
+    }
+}
+
+fn registry() -> &'static Mutex<BTreeMap<String, OpStats>> {
+    static STATS: OnceLock<Mutex<BTreeMap<String, OpStats>>> = OnceLock::new();
+    STATS.get_or_init(|| Mutex::new(BTreeMap::new()))
+}
+
+/// 作用域结束时记录一次结果；未设置目标的作用域不计入
+pub(crate) fn record(target: Option<&str>, result: &OperationResult) {
+    let Some(target) = target else {
+        return;
+    };
+    let mut stats = registry().lock().unwrap_or_else(|e| e.into_inner());
+    let entry = stats.entry(target.to_string()).or_default();
+    match result {
+        OperationResult::Suc => entry.success += 1,
+        OperationResult::Fail => entry.failure += 1,
+        OperationResult::Cancel => entry.cancel += 1,
+    }
+}
+
+/// 进程内各操作目标的累计结果，按目标名排序
+pub fn snapshot() -> BTreeMap<String, OpStats> {
+    registry().lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// 清空统计，例如在每个指标上报周期之后
+pub fn reset() {
+    registry().lock().unwrap_or_else(|e| e.into_inner()).clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OperationContext;
+
+    #[test]
+    fn test_scope_outcomes_are_counted() {
+        for ok in [true, true, false] {
+            let mut ctx = OperationContext::want("stats_place_order");
+            let mut scope = ctx.scoped_success();
+            if !ok {
+                scope.mark_failure();
+            }
+        }
+        let mut ctx = OperationContext::want("stats_place_order");
+        ctx.scoped_success().cancel();
+        // 未设置目标的作用域不计入
+        let _ = OperationContext::new().scoped_success();
+
+        let stats = snapshot()["stats_place_order"];
+        assert_eq!(
+            stats,
+            OpStats {
+                success: 2,
+                failure: 1,
+                cancel: 1
+            }
+        );
+        assert_eq!(stats.total(), 4);
+        assert_eq!(stats.error_rate(), 0.25);
+        assert_eq!(OpStats::default().error_rate(), 0.0);
+    }
+}
@@ -0,0 +1,120 @@
+use std::{
+    convert::Infallible,
+    fmt, io,
+    num::{ParseFloatError, ParseIntError, TryFromIntError},
+    str::{ParseBoolError, Utf8Error},
+    string::FromUtf8Error,
+    time::SystemTimeError,
+};
+
+use crate::traits::ErrorWith;
+
+use super::{domain::DomainReason, universal::UvsReason, StructError};
+
+/// 常见标准库错误到 `UvsReason` 的默认归类
+pub trait ToUvsReason {
+    fn to_uvs_reason(&self) -> UvsReason;
+}
+
+impl ToUvsReason for io::Error {
+    fn to_uvs_reason(&self) -> UvsReason {
+        UvsReason::from_io(self)
+    }
+}
+
+/// 便于脚本与示例直接使用 `?`：按 `ErrorKind` 归类，并把 kind 记入上下文 `io_kind`
+impl<R> From<io::Error> for StructError<R>
+where
+    R: DomainReason + From<UvsReason>,
+{
+    fn from(err: io::Error) -> Self {
+        StructError::from(R::from(err.to_uvs_reason()))
+            .with_detail(err.to_string())
+            .with_kv("io_kind", format!("{:?}", err.kind()))
+    }
+}
+
+/// 泛型代码中 `Result<T, Infallible>` 可直接用 `?` 传播到结构错误
+impl<R: DomainReason> From<Infallible> for StructError<R> {
+    fn from(never: Infallible) -> Self {
+        match never {}
+    }
+}
+
+macro_rules! impl_to_uvs_reason {
+    ($($ty:ty => $reason:ident),* $(,)?) => {
+        $(
+            impl ToUvsReason for $ty {
+                fn to_uvs_reason(&self) -> UvsReason {
+                    UvsReason::$reason
+                }
+            }
+
+            impl<R> From<$ty> for StructError<R>
+            where
+                R: DomainReason + From<UvsReason>,
+            {
+                fn from(err: $ty) -> Self {
+                    StructError::from(R::from(err.to_uvs_reason())).with_detail(err.to_string())
+                }
+            }
+        )*
+    };
+}
+
+impl_to_uvs_reason! {
+    // 输入解析失败视为校验错误
+    ParseIntError => ValidationError,
+    ParseFloatError => ValidationError,
+    ParseBoolError => ValidationError,
+    TryFromIntError => ValidationError,
+    // 非法字节序列视为数据错误
+    Utf8Error => DataError,
+    FromUtf8Error => DataError,
+    // 格式化失败与系统时钟回拨属于系统错误
+    fmt::Error => SystemError,
+    SystemTimeError => SystemError,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ErrorCode;
+
+    fn parse(s: &str) -> Result<u8, StructError<UvsReason>> {
+        Ok(s.parse::<u8>()?)
+    }
+
+    #[test]
+    fn test_question_mark_conversion() {
+        let err = parse("x").unwrap_err();
+        assert_eq!(err.reason(), &UvsReason::ValidationError);
+        assert_eq!(
+            err.detail().as_deref(),
+            Some("invalid digit found in string")
+        );
+
+        let err: StructError<UvsReason> = String::from_utf8(vec![0xff]).unwrap_err().into();
+        assert_eq!(err.error_code(), 200);
+        assert_eq!(
+            u8::try_from(300_i32).unwrap_err().to_uvs_reason(),
+            UvsReason::ValidationError
+        );
+    }
+
+    fn read(path: &str) -> Result<String, StructError<UvsReason>> {
+        Ok(std::fs::read_to_string(path)?)
+    }
+
+    #[test]
+    fn test_io_error_question_mark() {
+        let err = read("/nonexistent/orion-error.toml").unwrap_err();
+        assert_eq!(err.reason(), &UvsReason::NotFoundError);
+        assert!(err.detail().is_some());
+        assert_eq!(err.contexts()[0].get("io_kind"), Some("NotFound"));
+
+        let err: StructError<UvsReason> = io::Error::from(io::ErrorKind::BrokenPipe).into();
+        assert_eq!(err.reason(), &UvsReason::SystemError);
+        assert_eq!(err.contexts()[0].get("io_kind"), Some("BrokenPipe"));
+    }
+}
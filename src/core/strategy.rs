@@ -0,0 +1,369 @@
+//! 按错误原因决定处理策略：重试、忽略或传播
+
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+use super::{
+    domain::DomainReason, report::log_error, AsUvsReason, ContextAdd, ContextRecord, ErrStrategy,
+    ErrorCode, OperationContext, Severity, StructError, UvsReason,
+};
+
+/// 错误原因对应的处理策略；默认按 `is_retryable()` 决定重试或传播，领域原因可覆盖
+pub trait StrategyFor: AsUvsReason {
+    fn strategy(&self) -> ErrStrategy {
+        if self.as_uvs().is_some_and(UvsReason::is_retryable) {
+            ErrStrategy::Retry
+        } else {
+            ErrStrategy::Throw
+        }
+    }
+}
+
+impl StrategyFor for UvsReason {}
+
+impl<T: DomainReason + StrategyFor> StrategyFor for StructError<T> {
+    fn strategy(&self) -> ErrStrategy {
+        match self.uvs_class() {
+            Some(uvs) => uvs.strategy(),
+            None => self.reason().strategy(),
+        }
+    }
+}
+
+/// 重试参数：总尝试次数（含首次）、指数退避的初始间隔与可选的整体截止时间
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub backoff: Duration,
+    pub deadline: Option<Duration>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            backoff: Duration::from_millis(100),
+            deadline: None,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts,
+            ..Self::default()
+        }
+    }
+
+    #[must_use]
+    pub fn with_backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// 整体时间预算（从首次失败开始计时）；下一次等待会超出预算时放弃并转为 `TimeoutError`
+    #[must_use]
+    pub fn with_deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// 第 `attempt` 次失败后的等待时间（从 1 开始），每次翻倍
+    pub fn delay_for(&self, attempt: u32) -> Duration {
+        self.backoff
+            .saturating_mul(1u32 << attempt.saturating_sub(1).min(16))
+    }
+}
+
+/// 按错误策略处理 `Result`
+pub trait ErrorApplyStrategy<T, R: DomainReason> {
+    /// `Retry` 时按 `policy` 调用 `retry` 重新执行，`Ignore` 时返回 `default`，`Throw` 时原样返回错误；
+    /// 每次重试失败后都会按新的错误重新判定策略，错误带有 `retry_after` 时至少等待该时间
+    fn apply_strategy<F>(
+        self,
+        policy: &RetryPolicy,
+        default: T,
+        retry: F,
+    ) -> Result<T, StructError<R>>
+    where
+        F: FnMut() -> Result<T, StructError<R>>;
+}
+
+impl<T, R> ErrorApplyStrategy<T, R> for Result<T, StructError<R>>
+where
+    R: DomainReason + StrategyFor + From<UvsReason>,
+{
+    fn apply_strategy<F>(
+        self,
+        policy: &RetryPolicy,
+        default: T,
+        mut retry: F,
+    ) -> Result<T, StructError<R>>
+    where
+        F: FnMut() -> Result<T, StructError<R>>,
+    {
+        let start = Instant::now();
+        let mut timings = Vec::new();
+        let mut result = self;
+        loop {
+            let err = match result {
+                Ok(v) => return Ok(v),
+                Err(e) => e,
+            };
+            timings.push(start.elapsed());
+            let attempt = timings.len() as u32;
+            match err.strategy() {
+                ErrStrategy::Ignore => return Ok(default),
+                ErrStrategy::Throw => return Err(with_timings(err, &timings)),
+                ErrStrategy::Retry if attempt >= policy.max_attempts => {
+                    return Err(with_timings(err, &timings))
+                }
+                ErrStrategy::Retry => {
+                    let delay = policy
+                        .delay_for(attempt)
+                        .max(err.retry_after().unwrap_or_default());
+                    if let Some(deadline) = policy.deadline {
+                        if start.elapsed() + delay > deadline {
+                            return Err(deadline_exceeded(err, deadline, &timings));
+                        }
+                    }
+                    std::thread::sleep(delay);
+                    result = retry();
+                }
+            }
+        }
+    }
+}
+
+/// 发生过重试时追加 `retry` 上下文帧，记录尝试次数与每次失败距首次失败的耗时
+fn with_timings<R: DomainReason>(mut err: StructError<R>, timings: &[Duration]) -> StructError<R> {
+    if timings.len() > 1 {
+        err.add_context(retry_frame(timings));
+    }
+    err
+}
+
+fn retry_frame(timings: &[Duration]) -> OperationContext {
+    let mut ctx = OperationContext::want("retry");
+    ctx.record("attempts", timings.len());
+    for (i, t) in timings.iter().enumerate() {
+        ctx.record(format!("attempt_{}", i + 1), *t);
+    }
+    ctx
+}
+
+fn deadline_exceeded<R>(
+    last: StructError<R>,
+    deadline: Duration,
+    timings: &[Duration],
+) -> StructError<R>
+where
+    R: DomainReason + From<UvsReason>,
+{
+    let mut contexts = last.contexts().to_vec();
+    contexts.push(retry_frame(timings));
+    StructError::new(
+        R::from(UvsReason::timeout_error()),
+        Some(format!(
+            "retry deadline {deadline:?} exceeded after {} attempts: {}",
+            timings.len(),
+            last.reason()
+        )),
+        last.position().clone(),
+        contexts,
+    )
+}
+
+/// `ErrStrategy::Ignore` 的落地：尽力而为的调用点记录完整错误后继续执行
+pub trait ErrorIgnoreLogged<T> {
+    /// 出错时按 `level` 输出结构化错误并返回 `None`
+    fn ignore_err_logged(self, level: Severity) -> Option<T>;
+
+    /// 出错时按 `level` 输出结构化错误并返回 `T::default()`
+    fn ignore_err_logged_or_default(self, level: Severity) -> T
+    where
+        T: Default,
+        Self: Sized,
+    {
+        self.ignore_err_logged(level).unwrap_or_default()
+    }
+}
+
+impl<T, R> ErrorIgnoreLogged<T> for Result<T, StructError<R>>
+where
+    R: DomainReason + ErrorCode + Display,
+{
+    fn ignore_err_logged(self, level: Severity) -> Option<T> {
+        match self {
+            Ok(v) => Some(v),
+            Err(e) => {
+                log_error(
+                    &e,
+                    level,
+                    format_args!("ignored! {}", e.reason()),
+                    format_args!("ignored! {e}"),
+                );
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ErrorCode;
+    use derive_more::From;
+    use thiserror::Error;
+
+    #[derive(Debug, PartialEq, Error, From)]
+    enum CacheReason {
+        #[error("cache miss")]
+        Miss,
+        #[error("{0}")]
+        Uvs(UvsReason),
+    }
+
+    impl AsUvsReason for CacheReason {
+        fn as_uvs(&self) -> Option<&UvsReason> {
+            match self {
+                Self::Uvs(uvs) => Some(uvs),
+                _ => None,
+            }
+        }
+    }
+
+    impl StrategyFor for CacheReason {
+        fn strategy(&self) -> ErrStrategy {
+            match self {
+                Self::Miss => ErrStrategy::Ignore,
+                _ => self.as_uvs().map_or(ErrStrategy::Throw, |u| u.strategy()),
+            }
+        }
+    }
+
+    fn fast() -> RetryPolicy {
+        RetryPolicy::new(3).with_backoff(Duration::from_millis(1))
+    }
+
+    #[test]
+    fn test_default_strategy_follows_retryable() {
+        assert_eq!(UvsReason::network_error().strategy(), ErrStrategy::Retry);
+        assert_eq!(UvsReason::validation_error().strategy(), ErrStrategy::Throw);
+        let err = StructError::from(CacheReason::Miss);
+        assert_eq!(err.strategy(), ErrStrategy::Ignore);
+    }
+
+    #[test]
+    fn test_apply_strategy_retries_until_success() {
+        let mut calls = 0;
+        let first: Result<i32, StructError<UvsReason>> = Err(UvsReason::network_error().into());
+        let res = first.apply_strategy(&fast(), 0, || {
+            calls += 1;
+            if calls < 2 {
+                Err(UvsReason::timeout_error().into())
+            } else {
+                Ok(7)
+            }
+        });
+        assert_eq!(res.unwrap(), 7);
+        assert_eq!(calls, 2);
+    }
+
+    #[test]
+    fn test_apply_strategy_gives_up_after_max_attempts() {
+        let mut calls = 0;
+        let first: Result<i32, StructError<UvsReason>> = Err(UvsReason::network_error().into());
+        let res = first.apply_strategy(&fast(), 0, || {
+            calls += 1;
+            Err(UvsReason::network_error().into())
+        });
+        assert_eq!(res.unwrap_err().error_code(), 202);
+        assert_eq!(calls, 2);
+    }
+
+    #[test]
+    fn test_apply_strategy_ignore_and_throw() {
+        let miss: Result<i32, StructError<CacheReason>> = Err(CacheReason::Miss.into());
+        assert_eq!(miss.apply_strategy(&fast(), -1, || unreachable!()), Ok(-1));
+
+        let invalid: Result<i32, StructError<CacheReason>> =
+            Err(CacheReason::from(UvsReason::validation_error()).into());
+        assert!(invalid
+            .apply_strategy(&fast(), -1, || unreachable!())
+            .is_err());
+    }
+
+    #[test]
+    fn test_ignore_err_logged() {
+        let ok: Result<i32, StructError<UvsReason>> = Ok(3);
+        assert_eq!(ok.ignore_err_logged(Severity::Warn), Some(3));
+
+        let err: Result<i32, StructError<UvsReason>> = Err(UvsReason::network_error().into());
+        assert_eq!(err.clone().ignore_err_logged(Severity::Warn), None);
+        assert_eq!(err.ignore_err_logged_or_default(Severity::Debug), 0);
+    }
+
+    #[test]
+    fn test_retry_records_attempt_timings() {
+        let first: Result<i32, StructError<UvsReason>> = Err(UvsReason::network_error().into());
+        let err = first
+            .apply_strategy(&fast(), 0, || Err(UvsReason::network_error().into()))
+            .unwrap_err();
+        let frame = err.contexts().last().unwrap();
+        assert_eq!(frame.target().as_deref(), Some("retry"));
+        assert_eq!(frame.get("attempts"), Some("3"));
+        assert!(frame.get("attempt_3").is_some());
+    }
+
+    #[test]
+    fn test_retry_deadline_becomes_timeout() {
+        let policy = RetryPolicy::new(10)
+            .with_backoff(Duration::from_millis(20))
+            .with_deadline(Duration::from_millis(50));
+        let mut calls = 0;
+        let first: Result<i32, StructError<UvsReason>> = Err(UvsReason::network_error().into());
+        let err = first
+            .apply_strategy(&policy, 0, || {
+                calls += 1;
+                Err(UvsReason::network_error().into())
+            })
+            .unwrap_err();
+        assert_eq!(err.reason(), &UvsReason::timeout_error());
+        assert!(calls < 9);
+        assert!(err.detail().as_deref().unwrap().contains("deadline"));
+        assert_eq!(
+            err.contexts().last().unwrap().target().as_deref(),
+            Some("retry")
+        );
+    }
+
+    #[test]
+    fn test_retry_honors_retry_after() {
+        let first: Result<i32, StructError<UvsReason>> =
+            Err(StructError::from(UvsReason::resource_error())
+                .with_retry_after(Duration::from_millis(40)));
+        let start = Instant::now();
+        let res = first.apply_strategy(&fast(), 0, || Ok(7));
+        assert_eq!(res.unwrap(), 7);
+        assert!(start.elapsed() >= Duration::from_millis(40));
+
+        // 建议间隔超出整体预算时直接放弃
+        let policy = fast().with_deadline(Duration::from_millis(10));
+        let first: Result<i32, StructError<UvsReason>> =
+            Err(StructError::from(UvsReason::resource_error())
+                .with_retry_after(Duration::from_secs(60)));
+        let err = first.apply_strategy(&policy, 0, || Ok(7)).unwrap_err();
+        assert_eq!(err.reason(), &UvsReason::timeout_error());
+    }
+
+    #[test]
+    fn test_delay_doubles() {
+        let p = RetryPolicy::new(5).with_backoff(Duration::from_millis(10));
+        assert_eq!(p.delay_for(1), Duration::from_millis(10));
+        assert_eq!(p.delay_for(3), Duration::from_millis(40));
+    }
+}
@@ -1,11 +1,22 @@
+use std::{
+    fmt::{self, Display, Write},
+    io,
+    time::Duration,
+};
+
 use thiserror::Error;
 
-use super::ErrorCode;
+use super::{
+    format::write_json_str,
+    severity::{Severity, SeverityOf},
+    Code, ErrorCode, ErrorExitCode, HttpStatus, Namespace,
+};
 
 /// Configuration error sub-classification
 /// 配置错误子分类
 #[derive(Debug, Error, PartialEq, Clone)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum ConfErrReason {
     #[error("core config")]
     Core,
@@ -13,6 +24,231 @@ pub enum ConfErrReason {
     Feature,
     #[error("dynamic config error")]
     Dynamic,
+    /// 缺少必需的配置项
+    #[error("missing config key `{key}` in {file}")]
+    MissingKey { file: String, key: String },
+    /// 配置项类型不符
+    #[error("invalid config type for `{key}` in {file}: expected {expected}")]
+    InvalidType {
+        file: String,
+        key: String,
+        expected: String,
+    },
+}
+
+impl ConfErrReason {
+    pub fn missing_key(file: impl Into<String>, key: impl Into<String>) -> Self {
+        Self::MissingKey {
+            file: file.into(),
+            key: key.into(),
+        }
+    }
+
+    pub fn invalid_type(
+        file: impl Into<String>,
+        key: impl Into<String>,
+        expected: impl Into<String>,
+    ) -> Self {
+        Self::InvalidType {
+            file: file.into(),
+            key: key.into(),
+            expected: expected.into(),
+        }
+    }
+
+    pub fn is_missing_key(&self) -> bool {
+        matches!(self, Self::MissingKey { .. })
+    }
+
+    pub fn is_invalid_type(&self) -> bool {
+        matches!(self, Self::InvalidType { .. })
+    }
+
+    /// 出错的配置文件（来源）
+    pub fn file(&self) -> Option<&str> {
+        match self {
+            Self::MissingKey { file, .. } | Self::InvalidType { file, .. } => Some(file),
+            _ => None,
+        }
+    }
+
+    /// 出错的配置键路径
+    pub fn key(&self) -> Option<&str> {
+        match self {
+            Self::MissingKey { key, .. } | Self::InvalidType { key, .. } => Some(key),
+            _ => None,
+        }
+    }
+}
+
+/// Permission error sub-classification
+/// 权限错误子分类：认证失败（身份未知）与授权失败（身份已知但权限不足）
+#[derive(Debug, Error, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AuthErrReason {
+    /// 未认证或凭据无效，对应 HTTP 401
+    #[error("authentication required")]
+    Unauthenticated,
+    /// 已认证但缺少所需的角色或权限范围，对应 HTTP 403
+    #[error("permission denied: requires `{required}`")]
+    Forbidden { required: String },
+}
+
+impl AuthErrReason {
+    pub fn forbidden(required: impl Into<String>) -> Self {
+        Self::Forbidden {
+            required: required.into(),
+        }
+    }
+
+    /// 是否为认证失败
+    pub fn is_authn(&self) -> bool {
+        matches!(self, Self::Unauthenticated)
+    }
+
+    /// 缺少的角色或权限范围
+    pub fn required(&self) -> Option<&str> {
+        match self {
+            Self::Forbidden { required } => Some(required),
+            Self::Unauthenticated => None,
+        }
+    }
+}
+
+/// Kind of exhausted resource
+/// 耗尽的资源类型，可作为容量告警的路由键
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ResourceKind {
+    Memory,
+    Disk,
+    FileHandles,
+    ConnectionPool,
+}
+
+impl ResourceKind {
+    /// 适合作为指标标签的名称
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Memory => "memory",
+            Self::Disk => "disk",
+            Self::FileHandles => "file_handles",
+            Self::ConnectionPool => "connection_pool",
+        }
+    }
+}
+
+impl Display for ResourceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Upstream service failure details
+/// 上游服务出错的来源信息：服务名、调用端点与上游自身的状态码/错误码
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Upstream {
+    pub service: String,
+    pub endpoint: Option<String>,
+    pub status: Option<u16>,
+    pub code: Option<String>,
+}
+
+impl Upstream {
+    pub fn new(service: impl Into<String>) -> Self {
+        Self {
+            service: service.into(),
+            endpoint: None,
+            status: None,
+            code: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = Some(endpoint.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_status(mut self, status: u16) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    #[must_use]
+    pub fn with_code(mut self, code: impl Into<String>) -> Self {
+        self.code = Some(code.into());
+        self
+    }
+}
+
+impl Display for Upstream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.service)?;
+        if let Some(endpoint) = &self.endpoint {
+            write!(f, " ({endpoint})")?;
+        }
+        match self.status {
+            Some(status) => write!(f, " returned {status}")?,
+            None => f.write_str(" failed")?,
+        }
+        if let Some(code) = &self.code {
+            write!(f, " [{code}]")?;
+        }
+        Ok(())
+    }
+}
+
+/// Field-level validation failure
+/// 字段级校验错误：出错字段、机器可读的错误码与面向用户的说明
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FieldError {
+    pub field: String,
+    pub code: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(
+        field: impl Into<String>,
+        code: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            field: field.into(),
+            code: code.into(),
+            message: message.into(),
+        }
+    }
+
+    /// 必填字段缺失，错误码为 `required`
+    pub fn required(field: impl Into<String>) -> Self {
+        let field = field.into();
+        let message = format!("{field} is required");
+        Self::new(field, "required", message)
+    }
+
+    /// 字段值不合法，错误码为 `invalid`
+    pub fn invalid(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(field, "invalid", message)
+    }
+}
+
+impl Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+pub(crate) fn join_fields(fields: &[FieldError]) -> String {
+    fields
+        .iter()
+        .map(FieldError::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
 }
 
 /// Universal error reason classification with clear hierarchical structure
@@ -23,13 +259,18 @@ pub enum ConfErrReason {
 /// - 200-299: Infrastructure Layer Errors (基础设施层错误)
 /// - 300-399: Configuration & External Layer Errors (配置和外部层错误)
 #[derive(Debug, Error, PartialEq, Clone)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum UvsReason {
     // === Business Layer Errors (100-199) ===
     /// Input validation errors (格式错误、参数校验失败等)
     #[error("validation error")]
     ValidationError,
 
+    /// Input validation errors with per-field details (逐字段的校验错误)
+    #[error("validation error: {}", join_fields(.0))]
+    ValidationFields(Vec<FieldError>),
+
     /// Business logic rule violations (业务规则违反、状态冲突等)
     #[error("business logic error")]
     BusinessError,
@@ -42,10 +283,18 @@ pub enum UvsReason {
     #[error("not found error")]
     NotFoundError,
 
+    /// Typed resource not found (带资源类型与标识的不存在错误)
+    #[error("{kind} `{id}` not found")]
+    NotFoundResource { kind: String, id: String },
+
     /// Permission and authorization errors (权限不足、认证失败)
     #[error("permission error")]
     PermissionError,
 
+    /// Authentication / authorization failures with details (区分认证与授权的权限错误)
+    #[error("{0}")]
+    AuthError(AuthErrReason),
+
     // === Infrastructure Layer Errors (200-299) ===
     /// Database and data processing errors (数据库操作、数据格式错误)
     #[error("data error")]
@@ -63,10 +312,26 @@ pub enum UvsReason {
     #[error("resource error")]
     ResourceError,
 
+    /// Resource exhaustion with utilization data (带用量与上限的资源耗尽错误)
+    #[error("{kind} exhausted: {current}/{limit}")]
+    ResourceExhausted {
+        kind: ResourceKind,
+        current: u64,
+        limit: u64,
+    },
+
     /// Operation timeouts (操作超时)
     #[error("timeout error")]
     TimeoutError,
 
+    /// Operation timeout with elapsed time and limit (带耗时与时限的超时错误)
+    #[error("{op} timed out after {elapsed:?} (limit {limit:?})")]
+    TimeoutAfter {
+        op: String,
+        elapsed: Duration,
+        limit: Duration,
+    },
+
     // === Configuration & External Layer Errors (300-399) ===
     /// Configuration-related errors (配置相关错误)
     #[error("configuration error << {0}")]
@@ -76,6 +341,10 @@ pub enum UvsReason {
     #[error("external service error")]
     ExternalError,
 
+    /// Third-party service errors with provenance (带来源信息的第三方服务错误)
+    #[error("{0}")]
+    UpstreamError(Upstream),
+
     /// Third-party service errors (第三方服务错误)
     #[error("BUG :logic error")]
     LogicError,
@@ -100,6 +369,11 @@ impl UvsReason {
         Self::ValidationError
     }
 
+    /// 携带逐字段错误的校验错误，错误码与 `ValidationError` 相同
+    pub fn validation_fields(fields: impl IntoIterator<Item = FieldError>) -> Self {
+        Self::ValidationFields(fields.into_iter().collect())
+    }
+
     pub fn business_error() -> Self {
         Self::BusinessError
     }
@@ -112,10 +386,28 @@ impl UvsReason {
         Self::NotFoundError
     }
 
+    /// 指定资源不存在，渲染为 "user `42` not found"，错误码与 `NotFoundError` 相同
+    pub fn not_found_resource(kind: impl Into<String>, id: impl ToString) -> Self {
+        Self::NotFoundResource {
+            kind: kind.into(),
+            id: id.to_string(),
+        }
+    }
+
     pub fn permission_error() -> Self {
         Self::PermissionError
     }
 
+    /// 认证失败（HTTP 401）
+    pub fn unauthenticated() -> Self {
+        Self::AuthError(AuthErrReason::Unauthenticated)
+    }
+
+    /// 授权失败并注明所需的角色或权限范围（HTTP 403）
+    pub fn forbidden(required: impl Into<String>) -> Self {
+        Self::AuthError(AuthErrReason::forbidden(required))
+    }
+
     // === Infrastructure Layer Constructors ===
     pub fn data_error() -> Self {
         Self::DataError
@@ -133,18 +425,171 @@ impl UvsReason {
         Self::ResourceError
     }
 
+    /// 资源 `kind` 用量 `current` 达到上限 `limit`，错误码与 `ResourceError` 相同
+    pub fn resource_exhausted(kind: ResourceKind, current: u64, limit: u64) -> Self {
+        Self::ResourceExhausted {
+            kind,
+            current,
+            limit,
+        }
+    }
+
     pub fn timeout_error() -> Self {
         Self::TimeoutError
     }
 
+    /// 操作 `op` 耗时 `elapsed` 超出时限 `limit`，错误码与 `TimeoutError` 相同
+    pub fn timeout_after(op: impl Into<String>, elapsed: Duration, limit: Duration) -> Self {
+        Self::TimeoutAfter {
+            op: op.into(),
+            elapsed,
+            limit,
+        }
+    }
+
     // === External Layer Constructors ===
     pub fn external_error() -> Self {
         Self::ExternalError
     }
 
+    /// 上游服务出错，渲染为 "payments-api returned 503"，错误码与 `ExternalError` 相同
+    pub fn upstream_error(upstream: Upstream) -> Self {
+        Self::UpstreamError(upstream)
+    }
+
     pub fn logic_error() -> Self {
         Self::LogicError
     }
+
+    /// Map an I/O error to the closest reason by its `ErrorKind`
+    /// 按 `io::ErrorKind` 映射到最接近的错误类别
+    pub fn from_io(err: &io::Error) -> Self {
+        match err.kind() {
+            io::ErrorKind::NotFound => Self::NotFoundError,
+            io::ErrorKind::PermissionDenied => Self::PermissionError,
+            io::ErrorKind::TimedOut => Self::TimeoutError,
+            io::ErrorKind::OutOfMemory => Self::ResourceError,
+            _ => Self::SystemError,
+        }
+    }
+
+    // === Category Predicates ===
+    // 枚举为 `#[non_exhaustive]`，下游按类别分支时用这些方法代替穷尽匹配；
+    // 同一类别下携带结构化数据的变体一并计入
+    pub fn is_validation(&self) -> bool {
+        matches!(self, Self::ValidationError | Self::ValidationFields(_))
+    }
+
+    pub fn is_business(&self) -> bool {
+        matches!(self, Self::BusinessError)
+    }
+
+    pub fn is_rule(&self) -> bool {
+        matches!(self, Self::RunRuleError)
+    }
+
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, Self::NotFoundError | Self::NotFoundResource { .. })
+    }
+
+    pub fn is_permission(&self) -> bool {
+        matches!(self, Self::PermissionError | Self::AuthError(_))
+    }
+
+    pub fn is_data(&self) -> bool {
+        matches!(self, Self::DataError)
+    }
+
+    pub fn is_system(&self) -> bool {
+        matches!(self, Self::SystemError)
+    }
+
+    pub fn is_network(&self) -> bool {
+        matches!(self, Self::NetworkError)
+    }
+
+    pub fn is_resource(&self) -> bool {
+        matches!(self, Self::ResourceError | Self::ResourceExhausted { .. })
+    }
+
+    pub fn is_timeout(&self) -> bool {
+        matches!(self, Self::TimeoutError | Self::TimeoutAfter { .. })
+    }
+
+    pub fn is_config(&self) -> bool {
+        matches!(self, Self::ConfigError(_))
+    }
+
+    pub fn is_external(&self) -> bool {
+        matches!(self, Self::ExternalError | Self::UpstreamError(_))
+    }
+
+    pub fn is_logic(&self) -> bool {
+        matches!(self, Self::LogicError)
+    }
+
+    /// 不存在的资源类型与标识，可作为指标标签或 404 响应体字段
+    pub fn resource(&self) -> Option<(&str, &str)> {
+        match self {
+            Self::NotFoundResource { kind, id } => Some((kind, id)),
+            _ => None,
+        }
+    }
+
+    /// 上游服务的来源信息
+    pub fn upstream(&self) -> Option<&Upstream> {
+        match self {
+            Self::UpstreamError(upstream) => Some(upstream),
+            _ => None,
+        }
+    }
+
+    /// 耗尽的资源类型
+    pub fn resource_kind(&self) -> Option<ResourceKind> {
+        match self {
+            Self::ResourceExhausted { kind, .. } => Some(*kind),
+            _ => None,
+        }
+    }
+
+    /// 超时的实际耗时与时限
+    pub fn timeout_durations(&self) -> Option<(Duration, Duration)> {
+        match self {
+            Self::TimeoutAfter { elapsed, limit, .. } => Some((*elapsed, *limit)),
+            _ => None,
+        }
+    }
+
+    /// 逐字段的校验错误；其他原因返回空切片
+    pub fn field_errors(&self) -> &[FieldError] {
+        match self {
+            Self::ValidationFields(fields) => fields,
+            _ => &[],
+        }
+    }
+
+    /// 字段错误的 JSON 数组 `[{"field","code","message"}]`，不依赖 serde，供 API 响应直接嵌入
+    pub fn field_errors_json(&self) -> String {
+        let mut out = String::from("[");
+        for (i, e) in self.field_errors().iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            let _ = write_field_json(&mut out, e);
+        }
+        out.push(']');
+        out
+    }
+}
+
+fn write_field_json(out: &mut String, e: &FieldError) -> fmt::Result {
+    out.write_str("{\"field\":")?;
+    write_json_str(out, &e.field)?;
+    out.write_str(",\"code\":")?;
+    write_json_str(out, &e.code)?;
+    out.write_str(",\"message\":")?;
+    write_json_str(out, &e.message)?;
+    out.write_char('}')
 }
 
 /// Unified constructor helpers for types that can be converted from `UvsReason`.
@@ -181,6 +626,10 @@ pub trait UvsFrom: From<UvsReason> + Sized {
         Self::from(UvsReason::resource_error())
     }
 
+    fn from_res_exhausted(kind: ResourceKind, current: u64, limit: u64) -> Self {
+        Self::from(UvsReason::resource_exhausted(kind, current, limit))
+    }
+
     fn from_net() -> Self {
         Self::from(UvsReason::network_error())
     }
@@ -189,33 +638,99 @@ pub trait UvsFrom: From<UvsReason> + Sized {
         Self::from(UvsReason::timeout_error())
     }
 
+    fn from_timeout_after(op: impl Into<String>, elapsed: Duration, limit: Duration) -> Self {
+        Self::from(UvsReason::timeout_after(op, elapsed, limit))
+    }
+
     fn from_validation() -> Self {
         Self::from(UvsReason::validation_error())
     }
 
+    fn from_validation_fields(fields: impl IntoIterator<Item = FieldError>) -> Self {
+        Self::from(UvsReason::validation_fields(fields))
+    }
+
     fn from_not_found() -> Self {
         Self::from(UvsReason::not_found_error())
     }
 
+    fn from_not_found_resource(kind: impl Into<String>, id: impl ToString) -> Self {
+        Self::from(UvsReason::not_found_resource(kind, id))
+    }
+
     fn from_permission() -> Self {
         Self::from(UvsReason::permission_error())
     }
 
+    fn from_unauthenticated() -> Self {
+        Self::from(UvsReason::unauthenticated())
+    }
+
+    fn from_forbidden(required: impl Into<String>) -> Self {
+        Self::from(UvsReason::forbidden(required))
+    }
+
     fn from_external() -> Self {
         Self::from(UvsReason::external_error())
     }
+
+    fn from_upstream(upstream: Upstream) -> Self {
+        Self::from(UvsReason::upstream_error(upstream))
+    }
 }
 
 impl<T> UvsFrom for T where T: From<UvsReason> {}
 
+/// Access the wrapped `UvsReason` of a domain reason, if any
+/// 取出领域 reason 中包装的 `UvsReason`，中间件可据此按通用类别分支
+pub trait AsUvsReason {
+    fn as_uvs(&self) -> Option<&UvsReason>;
+
+    /// 是否属于指定类别（见 `UvsReason::category_name`）
+    fn is_category(&self, name: &str) -> bool {
+        self.as_uvs().is_some_and(|r| r.category_name() == name)
+    }
+
+    /// 包装的 Uvs 原因加上领域基址后的错误码，例如基址 1000 时 `NotFoundError` 为 1102
+    fn offset_uvs_code(&self) -> Option<i32>
+    where
+        Self: ErrorCode,
+    {
+        self.as_uvs()
+            .map(|uvs| self.error_code_base() + uvs.error_code())
+    }
+}
+
+/// 错误归因：调用方导致（请求本身有误）还是系统导致（服务端或依赖故障）
+///
+/// HTTP/gRPC 映射、重试判断与 SLO 错误预算统计共用同一归因；
+/// 默认按包装的 `UvsReason` 判断，无法判断时视为系统导致。
+pub trait FaultOf: AsUvsReason {
+    fn is_client_fault(&self) -> bool {
+        self.as_uvs().is_some_and(UvsReason::is_client_fault)
+    }
+
+    fn is_server_fault(&self) -> bool {
+        !self.is_client_fault()
+    }
+}
+
+impl FaultOf for UvsReason {}
+
+impl AsUvsReason for UvsReason {
+    fn as_uvs(&self) -> Option<&UvsReason> {
+        Some(self)
+    }
+}
+
 impl ErrorCode for UvsReason {
     fn error_code(&self) -> i32 {
         match self {
             // === Business Layer Errors (100-199) ===
-            UvsReason::ValidationError => 100,
+            UvsReason::ValidationError | UvsReason::ValidationFields(_) => 100,
             UvsReason::BusinessError => 101,
-            UvsReason::NotFoundError => 102,
-            UvsReason::PermissionError => 103,
+            UvsReason::NotFoundError | UvsReason::NotFoundResource { .. } => 102,
+            UvsReason::PermissionError | UvsReason::AuthError(_) => 103,
             UvsReason::LogicError => 104,
             UvsReason::RunRuleError => 105,
 
@@ -223,16 +738,74 @@ impl ErrorCode for UvsReason {
             UvsReason::DataError => 200,
             UvsReason::SystemError => 201,
             UvsReason::NetworkError => 202,
-            UvsReason::ResourceError => 203,
-            UvsReason::TimeoutError => 204,
+            UvsReason::ResourceError | UvsReason::ResourceExhausted { .. } => 203,
+            UvsReason::TimeoutError | UvsReason::TimeoutAfter { .. } => 204,
 
             // === Configuration & External Layer Errors (300-399) ===
             UvsReason::ConfigError(_) => 300,
-            UvsReason::ExternalError => 301,
+            UvsReason::ExternalError | UvsReason::UpstreamError(_) => 301,
+        }
+    }
+
+    fn code(&self) -> Code {
+        Code::new(self.error_code())
+            .with_domain(Namespace::Name("UVS"))
+            .with_name(self.category_name())
+    }
+}
+
+impl HttpStatus for UvsReason {
+    fn http_status(&self) -> u16 {
+        match self {
+            UvsReason::ValidationError | UvsReason::ValidationFields(_) => 400,
+            UvsReason::BusinessError => 422,
+            UvsReason::RunRuleError => 422,
+            UvsReason::NotFoundError | UvsReason::NotFoundResource { .. } => 404,
+            UvsReason::PermissionError => 403,
+            UvsReason::AuthError(AuthErrReason::Unauthenticated) => 401,
+            UvsReason::AuthError(AuthErrReason::Forbidden { .. }) => 403,
+
+            UvsReason::NetworkError => 502,
+            UvsReason::ResourceError | UvsReason::ResourceExhausted { .. } => 503,
+            UvsReason::TimeoutError | UvsReason::TimeoutAfter { .. } => 504,
+            UvsReason::ExternalError | UvsReason::UpstreamError(_) => 502,
+
+            UvsReason::DataError => 500,
+            UvsReason::SystemError => 500,
+            UvsReason::ConfigError(_) => 500,
+            UvsReason::LogicError => 500,
+        }
+    }
+}
+
+/// Exit codes follow BSD `sysexits.h`
+/// 退出码参考 BSD `sysexits.h`
+impl ErrorExitCode for UvsReason {
+    fn exit_code(&self) -> i32 {
+        match self {
+            UvsReason::ValidationError | UvsReason::ValidationFields(_) => 65, // EX_DATAERR
+            UvsReason::DataError => 65,                                        // EX_DATAERR
+            UvsReason::NotFoundError | UvsReason::NotFoundResource { .. } => 66, // EX_NOINPUT
+            UvsReason::NetworkError => 69,                                     // EX_UNAVAILABLE
+            UvsReason::ExternalError | UvsReason::UpstreamError(_) => 69,      // EX_UNAVAILABLE
+            UvsReason::LogicError => 70,                                       // EX_SOFTWARE
+            UvsReason::SystemError => 71,                                      // EX_OSERR
+            UvsReason::ResourceError | UvsReason::ResourceExhausted { .. } => 75, // EX_TEMPFAIL
+            UvsReason::TimeoutError | UvsReason::TimeoutAfter { .. } => 75,    // EX_TEMPFAIL
+            UvsReason::PermissionError | UvsReason::AuthError(_) => 77,        // EX_NOPERM
+            UvsReason::ConfigError(_) => 78,                                   // EX_CONFIG
+            UvsReason::BusinessError => 1,
+            UvsReason::RunRuleError => 1,
         }
     }
 }
 
+impl SeverityOf for UvsReason {
+    fn severity(&self) -> Severity {
+        UvsReason::severity(self)
+    }
+}
+
 impl UvsReason {
     /// Check if this error is retryable
     /// 检查错误是否可重试
@@ -241,16 +814,22 @@ impl UvsReason {
             // Infrastructure errors are often retryable
             UvsReason::NetworkError => true,
             UvsReason::TimeoutError => true,
+            UvsReason::TimeoutAfter { .. } => true,
             UvsReason::ResourceError => true,
+            UvsReason::ResourceExhausted { .. } => true,
             UvsReason::SystemError => true,
             UvsReason::ExternalError => true,
+            UvsReason::UpstreamError(_) => true,
 
             // Business logic errors are generally not retryable
             UvsReason::ValidationError => false,
+            UvsReason::ValidationFields(_) => false,
             UvsReason::BusinessError => false,
             UvsReason::RunRuleError => false,
             UvsReason::NotFoundError => false,
+            UvsReason::NotFoundResource { .. } => false,
             UvsReason::PermissionError => false,
+            UvsReason::AuthError(_) => false,
 
             // Configuration errors require manual intervention
             UvsReason::ConfigError(_) => false,
@@ -259,17 +838,74 @@ impl UvsReason {
         }
     }
 
+    /// Check if this error was caused by the caller
+    /// 检查错误是否由调用方导致（输入、权限、业务规则、资源不存在）
+    pub fn is_client_fault(&self) -> bool {
+        match self {
+            UvsReason::ValidationError
+            | UvsReason::ValidationFields(_)
+            | UvsReason::BusinessError
+            | UvsReason::RunRuleError
+            | UvsReason::NotFoundError
+            | UvsReason::NotFoundResource { .. }
+            | UvsReason::PermissionError
+            | UvsReason::AuthError(_) => true,
+
+            UvsReason::DataError
+            | UvsReason::SystemError
+            | UvsReason::NetworkError
+            | UvsReason::ResourceError
+            | UvsReason::ResourceExhausted { .. }
+            | UvsReason::TimeoutError
+            | UvsReason::TimeoutAfter { .. }
+            | UvsReason::ConfigError(_)
+            | UvsReason::ExternalError
+            | UvsReason::UpstreamError(_)
+            | UvsReason::LogicError => false,
+        }
+    }
+
+    /// Check if this error was caused by the system or its dependencies
+    /// 检查错误是否由系统或其依赖导致
+    pub fn is_server_fault(&self) -> bool {
+        !self.is_client_fault()
+    }
+
     /// Check if this error should be logged with high severity
     /// 检查错误是否需要高优先级记录
     pub fn is_high_severity(&self) -> bool {
+        self.severity() >= Severity::Critical
+    }
+
+    /// Severity used to pick log levels and alerting
+    /// 错误严重级别，用于选择日志级别与告警
+    pub fn severity(&self) -> Severity {
         match self {
-            // System and infrastructure issues are high severity
-            UvsReason::SystemError => true,
-            UvsReason::ResourceError => true,
-            UvsReason::ConfigError(_) => true,
+            // Expected outcomes of user input
+            UvsReason::ValidationError => Severity::Info,
+            UvsReason::ValidationFields(_) => Severity::Info,
+            UvsReason::NotFoundError => Severity::Info,
+            UvsReason::NotFoundResource { .. } => Severity::Info,
+
+            // Normal business failures and transient infrastructure issues
+            UvsReason::BusinessError => Severity::Warn,
+            UvsReason::RunRuleError => Severity::Warn,
+            UvsReason::PermissionError => Severity::Warn,
+            UvsReason::AuthError(_) => Severity::Warn,
+            UvsReason::NetworkError => Severity::Warn,
+            UvsReason::TimeoutError => Severity::Warn,
+            UvsReason::TimeoutAfter { .. } => Severity::Warn,
+
+            UvsReason::DataError => Severity::Error,
+            UvsReason::ExternalError => Severity::Error,
+            UvsReason::UpstreamError(_) => Severity::Error,
+            UvsReason::LogicError => Severity::Error,
 
-            // Others are normal business operations
-            _ => false,
+            // System and infrastructure issues are high severity
+            UvsReason::SystemError => Severity::Critical,
+            UvsReason::ResourceError => Severity::Critical,
+            UvsReason::ResourceExhausted { .. } => Severity::Critical,
+            UvsReason::ConfigError(_) => Severity::Critical,
         }
     }
 
@@ -277,18 +913,18 @@ impl UvsReason {
     /// 获取错误类别名称用于监控和指标
     pub fn category_name(&self) -> &'static str {
         match self {
-            UvsReason::ValidationError => "validation",
+            UvsReason::ValidationError | UvsReason::ValidationFields(_) => "validation",
             UvsReason::BusinessError => "business",
             UvsReason::RunRuleError => "runrule",
-            UvsReason::NotFoundError => "not_found",
-            UvsReason::PermissionError => "permission",
+            UvsReason::NotFoundError | UvsReason::NotFoundResource { .. } => "not_found",
+            UvsReason::PermissionError | UvsReason::AuthError(_) => "permission",
             UvsReason::DataError => "data",
             UvsReason::SystemError => "system",
             UvsReason::NetworkError => "network",
-            UvsReason::ResourceError => "resource",
-            UvsReason::TimeoutError => "timeout",
+            UvsReason::ResourceError | UvsReason::ResourceExhausted { .. } => "resource",
+            UvsReason::TimeoutError | UvsReason::TimeoutAfter { .. } => "timeout",
             UvsReason::ConfigError(_) => "config",
-            UvsReason::ExternalError => "external",
+            UvsReason::ExternalError | UvsReason::UpstreamError(_) => "external",
             UvsReason::LogicError => "logic",
         }
     }
@@ -318,6 +954,42 @@ mod tests {
         assert_eq!(UvsReason::external_error().error_code(), 301);
     }
 
+    #[test]
+    fn test_offset_uvs_code() {
+        enum BillingReason {
+            Overdue,
+            Uvs(UvsReason),
+        }
+        impl AsUvsReason for BillingReason {
+            fn as_uvs(&self) -> Option<&UvsReason> {
+                match self {
+                    Self::Uvs(uvs) => Some(uvs),
+                    _ => None,
+                }
+            }
+        }
+        impl ErrorCode for BillingReason {
+            fn error_code_base(&self) -> i32 {
+                1000
+            }
+            fn error_code(&self) -> i32 {
+                match self {
+                    Self::Overdue => 1001,
+                    _ => self.offset_uvs_code().unwrap_or(500),
+                }
+            }
+        }
+
+        assert_eq!(BillingReason::Overdue.error_code(), 1001);
+        assert_eq!(
+            BillingReason::Uvs(UvsReason::not_found_error()).error_code(),
+            1102
+        );
+        assert_eq!(BillingReason::Overdue.offset_uvs_code(), None);
+        // Uvs 自身基址为 0
+        assert_eq!(UvsReason::network_error().offset_uvs_code(), Some(202));
+    }
+
     #[test]
     fn test_retryable_errors() {
         assert!(UvsReason::network_error().is_retryable());
@@ -334,6 +1006,15 @@ mod tests {
         assert!(!UvsReason::NotFoundError.is_high_severity());
     }
 
+    #[test]
+    fn test_severity_levels() {
+        assert_eq!(UvsReason::validation_error().severity(), Severity::Info);
+        assert_eq!(UvsReason::network_error().severity(), Severity::Warn);
+        assert_eq!(UvsReason::logic_error().severity(), Severity::Error);
+        assert_eq!(UvsReason::core_conf().severity(), Severity::Critical);
+        assert!(Severity::Warn < Severity::Error);
+    }
+
     #[test]
     fn test_category_names() {
         assert_eq!(UvsReason::network_error().category_name(), "network");
@@ -352,4 +1033,217 @@ mod tests {
         let reason: UvsReason = <UvsReason as UvsFrom>::from_external();
         assert_eq!(reason.error_code(), 301);
     }
+
+    #[test]
+    fn test_http_status_mapping() {
+        assert_eq!(UvsReason::validation_error().http_status(), 400);
+        assert_eq!(UvsReason::not_found_error().http_status(), 404);
+        assert_eq!(UvsReason::permission_error().http_status(), 403);
+        assert_eq!(UvsReason::timeout_error().http_status(), 504);
+        assert_eq!(UvsReason::core_conf().http_status(), 500);
+    }
+
+    #[test]
+    fn test_conf_err_metadata() {
+        let reason = ConfErrReason::missing_key("app.toml", "db.url");
+        assert_eq!(
+            reason.to_string(),
+            "missing config key `db.url` in app.toml"
+        );
+        assert_eq!(reason.file(), Some("app.toml"));
+        assert_eq!(reason.key(), Some("db.url"));
+
+        let reason =
+            UvsReason::ConfigError(ConfErrReason::invalid_type("app.toml", "port", "integer"));
+        assert_eq!(reason.error_code(), 300);
+        assert_eq!(
+            reason.to_string(),
+            "configuration error << invalid config type for `port` in app.toml: expected integer"
+        );
+        assert_eq!(ConfErrReason::Core.key(), None);
+    }
+
+    #[test]
+    fn test_exit_code_mapping() {
+        assert_eq!(UvsReason::core_conf().exit_code(), 78);
+        assert_eq!(UvsReason::permission_error().exit_code(), 77);
+        assert_eq!(UvsReason::timeout_error().exit_code(), 75);
+        assert_eq!(UvsReason::business_error().exit_code(), 1);
+    }
+
+    #[test]
+    fn test_from_io_kind_mapping() {
+        let of = |kind| UvsReason::from_io(&io::Error::new(kind, "io"));
+        assert_eq!(of(io::ErrorKind::NotFound), UvsReason::NotFoundError);
+        assert_eq!(
+            of(io::ErrorKind::PermissionDenied),
+            UvsReason::PermissionError
+        );
+        assert_eq!(of(io::ErrorKind::TimedOut), UvsReason::TimeoutError);
+        assert_eq!(of(io::ErrorKind::OutOfMemory), UvsReason::ResourceError);
+        assert_eq!(of(io::ErrorKind::BrokenPipe), UvsReason::SystemError);
+    }
+
+    #[test]
+    fn test_validation_fields() {
+        let reason = UvsReason::validation_fields([
+            FieldError::required("email"),
+            FieldError::new("age", "range", "must be between 0 and \"150\""),
+        ]);
+        assert_eq!(reason.error_code(), 100);
+        assert_eq!(reason.http_status(), 400);
+        assert_eq!(reason.category_name(), "validation");
+        assert_eq!(
+            reason.to_string(),
+            "validation error: email: email is required; age: must be between 0 and \"150\""
+        );
+        assert_eq!(reason.field_errors()[1].code, "range");
+        assert_eq!(
+            reason.field_errors_json(),
+            r#"[{"field":"email","code":"required","message":"email is required"},{"field":"age","code":"range","message":"must be between 0 and \"150\""}]"#
+        );
+        assert!(UvsReason::validation_error().field_errors().is_empty());
+        assert_eq!(UvsReason::validation_error().field_errors_json(), "[]");
+    }
+
+    #[test]
+    fn test_not_found_resource() {
+        let reason = UvsReason::not_found_resource("user", 42);
+        assert_eq!(reason.to_string(), "user `42` not found");
+        assert_eq!(reason.error_code(), 102);
+        assert_eq!(reason.http_status(), 404);
+        assert_eq!(reason.category_name(), "not_found");
+        assert_eq!(reason.resource(), Some(("user", "42")));
+        assert_eq!(UvsReason::not_found_error().resource(), None);
+    }
+
+    #[test]
+    fn test_auth_error_status() {
+        let authn = UvsReason::unauthenticated();
+        assert_eq!(authn.http_status(), 401);
+        assert_eq!(authn.error_code(), 103);
+        assert_eq!(authn.to_string(), "authentication required");
+
+        let authz = UvsReason::forbidden("orders:write");
+        assert_eq!(authz.http_status(), 403);
+        assert_eq!(authz.category_name(), "permission");
+        assert_eq!(
+            authz.to_string(),
+            "permission denied: requires `orders:write`"
+        );
+        match authz {
+            UvsReason::AuthError(reason) => {
+                assert!(!reason.is_authn());
+                assert_eq!(reason.required(), Some("orders:write"));
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_timeout_after() {
+        let reason = UvsReason::timeout_after(
+            "fetch quote",
+            Duration::from_millis(1500),
+            Duration::from_secs(1),
+        );
+        assert_eq!(
+            reason.to_string(),
+            "fetch quote timed out after 1.5s (limit 1s)"
+        );
+        assert_eq!(reason.error_code(), 204);
+        assert!(reason.is_retryable());
+        assert_eq!(
+            reason.timeout_durations(),
+            Some((Duration::from_millis(1500), Duration::from_secs(1)))
+        );
+        assert_eq!(UvsReason::timeout_error().timeout_durations(), None);
+    }
+
+    #[test]
+    fn test_resource_exhausted() {
+        let reason = UvsReason::resource_exhausted(ResourceKind::ConnectionPool, 32, 32);
+        assert_eq!(reason.to_string(), "connection_pool exhausted: 32/32");
+        assert_eq!(reason.error_code(), 203);
+        assert_eq!(reason.severity(), Severity::Critical);
+        assert_eq!(reason.resource_kind(), Some(ResourceKind::ConnectionPool));
+        assert_eq!(UvsReason::resource_error().resource_kind(), None);
+    }
+
+    #[test]
+    fn test_upstream_error() {
+        let reason = UvsReason::upstream_error(Upstream::new("payments-api").with_status(503));
+        assert_eq!(reason.to_string(), "payments-api returned 503");
+        assert_eq!(reason.error_code(), 301);
+        assert_eq!(reason.category_name(), "external");
+
+        let reason = UvsReason::upstream_error(
+            Upstream::new("payments-api")
+                .with_endpoint("POST /charges")
+                .with_code("card_declined"),
+        );
+        assert_eq!(
+            reason.to_string(),
+            "payments-api (POST /charges) failed [card_declined]"
+        );
+        let upstream = reason.upstream().unwrap();
+        assert_eq!(upstream.status, None);
+        assert_eq!(upstream.code.as_deref(), Some("card_declined"));
+        assert!(UvsReason::external_error().upstream().is_none());
+    }
+
+    #[test]
+    fn test_fault_classification() {
+        assert!(UvsReason::validation_error().is_client_fault());
+        assert!(UvsReason::forbidden("admin").is_client_fault());
+        assert!(UvsReason::not_found_resource("user", 1).is_client_fault());
+        assert!(UvsReason::timeout_error().is_server_fault());
+        assert!(UvsReason::core_conf().is_server_fault());
+        assert!(!UvsReason::logic_error().is_client_fault());
+
+        enum ImportReason {
+            BadRow,
+            Uvs(UvsReason),
+        }
+        impl AsUvsReason for ImportReason {
+            fn as_uvs(&self) -> Option<&UvsReason> {
+                match self {
+                    Self::Uvs(uvs) => Some(uvs),
+                    _ => None,
+                }
+            }
+        }
+        impl FaultOf for ImportReason {}
+        assert!(ImportReason::BadRow.is_server_fault());
+        assert!(ImportReason::Uvs(UvsReason::validation_error()).is_client_fault());
+    }
+
+    #[test]
+    fn test_category_predicates() {
+        assert!(UvsReason::validation_fields([FieldError::required("id")]).is_validation());
+        assert!(UvsReason::unauthenticated().is_permission());
+        assert!(UvsReason::timeout_after("op", Duration::ZERO, Duration::ZERO).is_timeout());
+        assert!(!UvsReason::timeout_error().is_network());
+        assert!(ConfErrReason::missing_key("a.toml", "k").is_missing_key());
+        for reason in [
+            UvsReason::business_error(),
+            UvsReason::rule_error(),
+            UvsReason::data_error(),
+            UvsReason::system_error(),
+            UvsReason::core_conf(),
+            UvsReason::upstream_error(Upstream::new("svc")),
+            UvsReason::logic_error(),
+        ] {
+            let hits = [
+                reason.is_business(),
+                reason.is_rule(),
+                reason.is_data(),
+                reason.is_system(),
+                reason.is_config(),
+                reason.is_external(),
+                reason.is_logic(),
+            ];
+            assert_eq!(hits.iter().filter(|h| **h).count(), 1, "{reason}");
+        }
+    }
 }
@@ -0,0 +1,51 @@
+//! wasm 前端集成：将错误转换为结构化的 JS 对象
+
+use js_sys::{Array, Object, Reflect};
+use wasm_bindgen::JsValue;
+
+use super::{domain::DomainReason, ErrorCode, OperationContext, StructError};
+
+fn set(obj: &Object, key: &str, val: impl Into<JsValue>) {
+    // 对普通 Object 设置属性不会失败
+    let _ = Reflect::set(obj, &JsValue::from_str(key), &val.into());
+}
+
+fn context_to_js(ctx: &OperationContext) -> Object {
+    let obj = Object::new();
+    if let Some(target) = ctx.target() {
+        set(&obj, "target", target.as_str());
+    }
+    let items = Object::new();
+    for (k, v) in ctx.context().render_items() {
+        set(&items, k, v);
+    }
+    set(&obj, "items", items);
+    if !ctx.children().is_empty() {
+        let children: Array = ctx.children().iter().map(context_to_js).collect();
+        set(&obj, "children", children);
+    }
+    obj
+}
+
+impl<T: DomainReason + ErrorCode> StructError<T> {
+    /// 转换为 JS 对象：`{ code, reason, detail?, position?, id?, context: [{ target?, items, children? }] }`
+    ///
+    /// 仅能在 wasm32 目标上调用；原生平台调用 wasm-bindgen 导入函数会 panic。
+    pub fn to_js_value(&self) -> JsValue {
+        let obj = Object::new();
+        set(&obj, "code", self.reason().error_code());
+        set(&obj, "reason", self.reason().to_string());
+        if let Some(detail) = self.detail() {
+            set(&obj, "detail", detail.as_str());
+        }
+        if let Some(position) = self.position() {
+            set(&obj, "position", position.as_str());
+        }
+        if let Some(id) = self.id() {
+            set(&obj, "id", id.as_str());
+        }
+        let context: Array = self.context().iter().map(context_to_js).collect();
+        set(&obj, "context", context);
+        obj.into()
+    }
+}
@@ -1,17 +1,64 @@
 mod core;
+pub mod test_utils;
 mod testcase;
 mod traits;
 
-pub use core::ErrStrategy;
+#[allow(deprecated)]
+pub use core::print_error_zh;
+#[cfg(feature = "reqwest")]
+pub use core::ErrorOweHttp;
+pub use core::{catch_panic, install_panic_hook};
+pub use core::{clear_context_enrichers, register_context_enricher, ContextEnricher};
 pub use core::{
-    print_error, print_error_zh, ConfErrReason, DomainReason, ErrorCode, StructErrorTrait, UvsFrom,
-    UvsReason,
-};
-pub use core::{ContextRecord, OperationContext, OperationScope, WithContext};
-pub use core::{StructError, StructErrorBuilder};
-pub use testcase::{TestAssert, TestAssertWithMsg};
-pub use traits::{ConvStructError, ErrorConv, ErrorWith, ToStructError};
-pub use traits::{ErrorOwe, ErrorOweBase};
+    clear_sensitive_keys, is_sensitive_key, redact_text, register_sensitive_key, REDACTED,
+};
+#[cfg(feature = "color")]
+pub use core::{color_choice, set_color_choice, ColorChoice, ColorFormatter};
+pub use core::{context_dedup, set_context_dedup, DisplayValue};
+pub use core::{error_id_enabled, new_error_id, set_error_id_enabled};
+pub use core::{error_limits, set_error_limits, truncate_text, ErrorLimits, TRUNCATED_KEY};
+pub use core::{expose_internal, set_expose_internal, PROBLEM_CONTENT_TYPE};
+pub use core::{
+    format_error, print_error, write_error, AuthErrReason, Code, ConfErrReason, DomainCode,
+    DomainReason, ErrorCode, ErrorExitCode, FieldError, HttpStatus, Namespace, ResourceKind,
+    StructErrorTrait, Upstream, UvsFrom, UvsReason,
+};
+pub use core::{
+    format_error_localized, print_error_localized, Locale, Localize, LocalizedFormatter,
+};
+pub use core::{normalize_text, DefaultFingerprint, Fingerprint};
+pub use core::{path_display, set_path_display, PathDisplay};
+pub use core::{set_want_mode, want_mode, WantMode};
+pub use core::{AsUvsReason, ContextSchema, FaultOf};
+pub use core::{
+    CallContext, ContextHandle, ContextItems, ContextKey, ContextRecord, ContextRecordExt,
+    ContextStack, IntoContextKey, OperationContext, OperationScope, WithContext,
+};
+pub use core::{CatalogEntry, ErrorCatalog};
+pub use core::{
+    CompactFormatter, ErrorFormatter, ErrorView, JsonFormatter, VerboseFormatter, WrappedFormatter,
+};
+pub use core::{DataPos, ParsePosition, ToUvsReason};
+pub use core::{ErrStrategy, ErrorApplyStrategy, ErrorIgnoreLogged, RetryPolicy, StrategyFor};
+pub use core::{ErrorBundle, ErrorCollect, Extensions, RetryAfter};
+pub use core::{
+    ErrorSource, ReasonEq, StructError, StructErrorBuilder, UvsError, ERROR_SCHEMA_VERSION,
+};
+#[cfg(feature = "serde")]
+pub use core::{Profiled, SerializeProfile};
+pub use core::{Severity, SeverityOf};
+#[cfg(feature = "macros")]
+pub use orion_error_macros::err_context;
+pub use testcase::{TestAssert, TestAssertErr, TestAssertFail, TestAssertWithMsg};
+pub use traits::ErrorTap;
+pub use traits::{
+    ConvStructError, ErrorConv, ErrorConvKeep, ErrorMapReason, ErrorWith, ErrorWithLazy,
+    ToStructError,
+};
+pub use traits::{
+    ErrorOwe, ErrorOweAuto, ErrorOweBase, ErrorOweFrom, ErrorOweIo, ErrorOweParse, ErrorOweSource,
+    ErrorOweWrap,
+};
 
 /// Commonly used traits and types for convenient wildcard imports.
 ///
@@ -21,24 +68,60 @@ pub use traits::{ErrorOwe, ErrorOweBase};
 /// ```
 pub mod prelude {
     pub use crate::{
-        ContextRecord, ErrorCode, ErrorConv, ErrorOwe, ErrorOweBase, ErrorWith, ToStructError,
-        UvsFrom,
+        ContextRecord, ContextRecordExt, ErrorCode, ErrorConv, ErrorIgnoreLogged, ErrorOwe,
+        ErrorOweAuto, ErrorOweBase, ErrorOweIo, ErrorTap, ErrorWith, ErrorWithLazy, TestAssert,
+        TestAssertFail, ToStructError, UvsFrom,
     };
-    pub use crate::{OperationContext, OperationScope, StructError, StructErrorBuilder, UvsReason};
+    pub use crate::{
+        DomainReason, OperationContext, OperationScope, StructError, StructErrorBuilder, UvsError,
+        UvsReason,
+    };
+}
+
+/// Deployment-wide error policy.
+///
+/// ```rust
+/// use orion_error::config::ErrorConfig;
+/// let _ = ErrorConfig::from_env().init();
+/// ```
+pub mod config {
+    pub use crate::core::{
+        app_info, error_config, AppInfo, DefaultFormat, ErrorConfig, TimestampFormat,
+    };
+}
+
+/// Per-operation success / failure counters collected from `OperationScope`.
+///
+/// ```rust
+/// let rate = orion_error::stats::snapshot()
+///     .get("place_order")
+///     .map(|s| s.error_rate());
+/// # let _ = rate;
+/// ```
+pub mod stats {
+    pub use crate::core::stats::{reset, snapshot, OpStats};
+}
+
+/// Error reporting sinks.
+pub mod report {
+    pub use crate::core::{JsonlSink, ThrottledReporter};
 }
+pub use report::{JsonlSink, ThrottledReporter};
 
 /// Grouped core types and enums.
 pub mod types {
     pub use crate::{
         ConfErrReason, ErrStrategy, OperationContext, OperationScope, StructError,
-        StructErrorBuilder, UvsReason, WithContext,
+        StructErrorBuilder, UvsError, UvsReason, WithContext,
     };
 }
 
 /// Grouped conversion and context extension traits.
 pub mod traits_ext {
     pub use crate::{
-        ContextRecord, ConvStructError, ErrorCode, ErrorConv, ErrorOwe, ErrorOweBase, ErrorWith,
-        ToStructError, UvsFrom,
+        ContextRecord, ContextRecordExt, ConvStructError, ErrorApplyStrategy, ErrorCode,
+        ErrorCollect, ErrorConv, ErrorConvKeep, ErrorMapReason, ErrorOwe, ErrorOweAuto,
+        ErrorOweBase, ErrorOweFrom, ErrorOweIo, ErrorOweSource, ErrorOweWrap, ErrorTap, ErrorWith,
+        ErrorWithLazy, StrategyFor, ToStructError, UvsFrom,
     };
 }
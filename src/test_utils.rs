@@ -0,0 +1,117 @@
+//! 构造测试用错误的辅助函数，省去逐个拼装 `StructError::new(..)` 的样板代码
+//!
+//! ```rust
+//! use orion_error::{assert_display_contains, test_utils, ErrorCode, UvsError};
+//!
+//! let err: UvsError = test_utils::fake_error(202);
+//! assert_eq!(err.error_code(), 202);
+//!
+//! let err: UvsError = test_utils::error_with_ctx([("user", "42"), ("op", "login")]);
+//! assert_display_contains!(err, "user: 42");
+//! ```
+
+use std::fmt::Display;
+
+use crate::{
+    ContextRecord, DomainReason, ErrorWith, IntoContextKey, OperationContext, StructError,
+    UvsReason,
+};
+
+/// 按通用错误码构造错误，例如 `fake_error(202)` 为网络错误；未知的错误码直接 panic
+pub fn fake_error<R: DomainReason + From<UvsReason>>(code: i32) -> StructError<R> {
+    StructError::from(R::from(uvs_for_code(code)))
+}
+
+/// 系统错误，附带一个 `test` 上下文帧，按顺序记录给定的键值；位置记录为调用点
+#[track_caller]
+pub fn error_with_ctx<R, K, V, I>(items: I) -> StructError<R>
+where
+    R: DomainReason + From<UvsReason>,
+    K: IntoContextKey,
+    V: Display,
+    I: IntoIterator<Item = (K, V)>,
+{
+    let mut ctx = OperationContext::want("test");
+    for (key, val) in items {
+        ctx.record(key.into_context_key(), val.to_string());
+    }
+    StructError::from(R::from(UvsReason::system_error())).with(ctx)
+}
+
+fn uvs_for_code(code: i32) -> UvsReason {
+    match code {
+        100 => UvsReason::validation_error(),
+        101 => UvsReason::business_error(),
+        102 => UvsReason::not_found_error(),
+        103 => UvsReason::permission_error(),
+        104 => UvsReason::logic_error(),
+        105 => UvsReason::rule_error(),
+        200 => UvsReason::data_error(),
+        201 => UvsReason::system_error(),
+        202 => UvsReason::network_error(),
+        203 => UvsReason::resource_error(),
+        204 => UvsReason::timeout_error(),
+        300 => UvsReason::core_conf(),
+        301 => UvsReason::external_error(),
+        _ => panic!(
+            "fake_error: no universal reason with code {code} (expected 100-105, 200-204, 300-301)"
+        ),
+    }
+}
+
+/// 断言错误的显示文本包含指定片段，失败时输出完整文本
+#[macro_export]
+macro_rules! assert_display_contains {
+    ($err:expr, $needle:expr $(,)?) => {{
+        let text = ::std::string::ToString::to_string(&$err);
+        let needle: &str = ::std::convert::AsRef::as_ref(&$needle);
+        if !text.contains(needle) {
+            panic!(
+                "[TEST ASSERTION FAILED] display does not contain {needle:?}\n Error details: {text}"
+            );
+        }
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ErrorCode, UvsError};
+
+    #[test]
+    fn test_fake_error_codes() {
+        for code in [100, 103, 105, 201, 204, 300, 301] {
+            let err: UvsError = fake_error(code);
+            assert_eq!(err.error_code(), code);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "no universal reason with code 999")]
+    fn test_fake_error_unknown_code() {
+        let _: UvsError = fake_error(999);
+    }
+
+    #[test]
+    fn test_error_with_ctx() {
+        let err: UvsError = error_with_ctx([("user", 42), ("retry", 3)]);
+        let ctx = &err.contexts()[0];
+        assert_eq!(ctx.target().as_deref(), Some("test"));
+        assert_eq!(ctx.get("user"), Some("42"));
+        assert_eq!(ctx.get("retry"), Some("3"));
+        assert!(err
+            .imp()
+            .position()
+            .as_deref()
+            .unwrap()
+            .starts_with(file!()));
+        crate::assert_display_contains!(err, "system error");
+    }
+
+    #[test]
+    #[should_panic(expected = "display does not contain \"timeout\"")]
+    fn test_assert_display_contains_fails() {
+        let err: UvsError = fake_error(202);
+        crate::assert_display_contains!(err, "timeout");
+    }
+}
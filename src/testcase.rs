@@ -1,3 +1,7 @@
+use std::fmt::{Debug, Display};
+
+use crate::{AsUvsReason, DomainReason, ErrorCode, StructError};
+
 // 测试专用断言 (无消息)
 pub trait TestAssert {
     type Output;
@@ -39,3 +43,103 @@ impl<T> TestAssert for Option<T> {
         self.unwrap_or_else(|| panic!("[OPTION ASSERTION FAILED] ",))
     }
 }
+
+/// 失败路径断言：期望 `Err` 并返回错误，得到 `Ok` 时输出其值；
+/// 比 `unwrap_err()` 的 Debug 输出更易读
+pub trait TestAssertFail<E> {
+    fn assert_err(self) -> E;
+
+    /// 额外要求错误的显示文本包含 `expected`，不包含时输出完整文本
+    fn assert_err_msg(self, expected: &str) -> E;
+}
+
+impl<T: Debug, E: Display> TestAssertFail<E> for Result<T, E> {
+    #[track_caller]
+    fn assert_err(self) -> E {
+        match self {
+            Ok(v) => panic!("[TEST ASSERTION FAILED] expected Err, got Ok({v:?})"),
+            Err(e) => e,
+        }
+    }
+
+    #[track_caller]
+    fn assert_err_msg(self, expected: &str) -> E {
+        let err = self.assert_err();
+        let text = err.to_string();
+        if !text.contains(expected) {
+            panic!(
+                "[TEST ASSERTION FAILED] error message does not contain {expected:?}\n Error details: {text}"
+            );
+        }
+        err
+    }
+}
+
+/// 测试专用错误断言：失败时输出期望值、实际值与完整的错误信息
+pub trait TestAssertErr<R: DomainReason> {
+    fn assert_err_code(self, expected: i32) -> StructError<R>;
+    fn assert_reason(self, expected: &R) -> StructError<R>;
+    fn assert_category(self, expected: &str) -> StructError<R>
+    where
+        R: AsUvsReason;
+}
+
+impl<T, R> TestAssertErr<R> for Result<T, StructError<R>>
+where
+    R: DomainReason + ErrorCode + Debug,
+{
+    #[track_caller]
+    fn assert_err_code(self, expected: i32) -> StructError<R> {
+        let err = expect_err(self, "assert_err_code");
+        let actual = err.error_code();
+        if actual != expected {
+            fail("error code", &expected, &actual, &err);
+        }
+        err
+    }
+
+    #[track_caller]
+    fn assert_reason(self, expected: &R) -> StructError<R> {
+        let err = expect_err(self, "assert_reason");
+        if err.reason() != expected {
+            fail("reason", expected, err.reason(), &err);
+        }
+        err
+    }
+
+    #[track_caller]
+    fn assert_category(self, expected: &str) -> StructError<R>
+    where
+        R: AsUvsReason,
+    {
+        let err = expect_err(self, "assert_category");
+        let actual = err.as_uvs().map(|r| r.category_name());
+        if actual != Some(expected) {
+            fail("category", &Some(expected), &actual, &err);
+        }
+        err
+    }
+}
+
+#[track_caller]
+fn expect_err<T, R: DomainReason>(res: Result<T, StructError<R>>, what: &str) -> StructError<R> {
+    match res {
+        Ok(_) => panic!("[TEST ASSERTION FAILED] {what}: expected Err, got Ok"),
+        Err(e) => e,
+    }
+}
+
+#[track_caller]
+fn fail<R, A: Debug + ?Sized, B: Debug + ?Sized>(
+    what: &str,
+    expected: &A,
+    actual: &B,
+    err: &StructError<R>,
+) -> !
+where
+    R: DomainReason + ErrorCode,
+{
+    panic!(
+        "[TEST ASSERTION FAILED] {what} mismatch\n  expected: {expected:?}\n    actual: {actual:?}\n Error details: {err}"
+    )
+}
@@ -1,19 +1,112 @@
-use crate::OperationContext;
+use std::fmt::Display;
+
+use crate::{ContextRecord, IntoContextKey, OperationContext};
 
 pub trait ErrorWith {
+    /// 设置目标；已有上下文时按 `set_want_mode` 覆盖最近一帧的目标或追加新帧。
+    /// `StructError` 与 `Result` 的实现会把调用位置记入该帧，并追加到错误的位置轨迹
     fn want<S: Into<String>>(self, desc: S) -> Self;
+    /// 记录标注点：尚无位置时作为 `position`，否则追加到位置轨迹（见 `positions`）；需覆盖时用 `with_position`
     fn position<S: Into<String>>(self, desc: S) -> Self;
     fn with<C: Into<OperationContext>>(self, ctx: C) -> Self;
+
+    /// 总是追加一个以 `desc` 为目标的新帧，保留已有的目标
+    #[track_caller]
+    fn push_want<S: Into<String>>(self, desc: S) -> Self
+    where
+        Self: Sized,
+    {
+        self.with(OperationContext::want(desc))
+    }
+
+    /// 附加单个上下文键值
+    #[track_caller]
+    fn with_kv<K: IntoContextKey, V: Display>(self, key: K, val: V) -> Self
+    where
+        Self: Sized,
+    {
+        self.with_all([(key, val)])
+    }
+
+    /// 附加敏感键值，值总是以脱敏形式保存，例如 `.with_secret("password", pw)`
+    #[track_caller]
+    fn with_secret<K: IntoContextKey, V: Into<String>>(self, key: K, val: V) -> Self
+    where
+        Self: Sized,
+    {
+        let mut ctx = OperationContext::new();
+        ctx.with_secret(key, val);
+        self.with(ctx)
+    }
+
+    /// 一次附加多个上下文键值，例如 `.with_all([("user", id), ("order", oid)])`
+    #[track_caller]
+    fn with_all<I, K, V>(self, items: I) -> Self
+    where
+        Self: Sized,
+        I: IntoIterator<Item = (K, V)>,
+        K: IntoContextKey,
+        V: Display,
+    {
+        let mut ctx = OperationContext::new();
+        for (k, v) in items {
+            ctx.record(k, v.to_string());
+        }
+        self.with(ctx)
+    }
 }
 
 impl<T, E: ErrorWith> ErrorWith for Result<T, E> {
+    // 不经闭包直接调用，使 `#[track_caller]` 的调用位置指向用户代码
+    #[track_caller]
     fn want<S: Into<String>>(self, desc: S) -> Self {
-        self.map_err(|e| e.want(desc))
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.want(desc)),
+        }
     }
     fn position<S: Into<String>>(self, desc: S) -> Self {
         self.map_err(|e| e.position(desc))
     }
+    #[track_caller]
     fn with<C: Into<OperationContext>>(self, ctx: C) -> Self {
-        self.map_err(|e| e.with(ctx))
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.with(ctx)),
+        }
+    }
+    #[track_caller]
+    fn with_all<I, K, V>(self, items: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: IntoContextKey,
+        V: Display,
+    {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.with_all(items)),
+        }
+    }
+}
+
+/// 仅在出错时才构建上下文，避免成功路径上格式化上下文的开销
+pub trait ErrorWithLazy {
+    fn ctx_with<C, F>(self, f: F) -> Self
+    where
+        C: Into<OperationContext>,
+        F: FnOnce() -> C;
+}
+
+impl<T, E: ErrorWith> ErrorWithLazy for Result<T, E> {
+    #[track_caller]
+    fn ctx_with<C, F>(self, f: F) -> Self
+    where
+        C: Into<OperationContext>,
+        F: FnOnce() -> C,
+    {
+        match self {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.with(f())),
+        }
     }
 }
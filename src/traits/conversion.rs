@@ -1,4 +1,9 @@
-use crate::{core::convert_error, DomainReason, StructError};
+use std::convert::Infallible;
+
+use crate::{
+    core::{convert_error, convert_error_keep},
+    DomainReason, StructError,
+};
 
 pub trait ErrorConv<T, R: DomainReason>: Sized {
     fn err_conv(self) -> Result<T, StructError<R>>;
@@ -31,6 +36,65 @@ where
     }
 }
 
+/// 不可能失败的结果同样可以 `err_conv`，便于按错误类型参数化的泛型代码组合
+impl<T, R: DomainReason> ErrorConv<T, R> for Result<T, Infallible> {
+    fn err_conv(self) -> Result<T, StructError<R>> {
+        match self {
+            Ok(v) => Ok(v),
+            Err(never) => match never {},
+        }
+    }
+}
+
+impl<R: DomainReason> ConvStructError<R> for Infallible {
+    fn conv(self) -> StructError<R> {
+        match self {}
+    }
+}
+
+/// 转换时保留原始错误，见 `StructErrorImpl::origin`
+pub trait ErrorConvKeep<T, R: DomainReason>: Sized {
+    fn err_conv_keep(self) -> Result<T, StructError<R>>;
+}
+
+impl<T, R1, R2> ErrorConvKeep<T, R2> for Result<T, StructError<R1>>
+where
+    R1: DomainReason + Clone + Send + Sync + 'static,
+    R2: DomainReason + From<R1>,
+{
+    fn err_conv_keep(self) -> Result<T, StructError<R2>> {
+        self.map_err(convert_error_keep::<R1, R2>)
+    }
+}
+
+impl<R1> StructError<R1>
+where
+    R1: DomainReason + Clone + Send + Sync + 'static,
+{
+    /// `conv` 的保留原始错误版本
+    pub fn conv_keep<R2: DomainReason + From<R1>>(self) -> StructError<R2> {
+        convert_error_keep::<R1, R2>(self)
+    }
+}
+
+/// `Result` 上的 `StructError::map_reason`
+pub trait ErrorMapReason<T, R: DomainReason>: Sized {
+    fn map_reason<R2, F>(self, f: F) -> Result<T, StructError<R2>>
+    where
+        R2: DomainReason,
+        F: FnOnce(R) -> R2;
+}
+
+impl<T, R: DomainReason> ErrorMapReason<T, R> for Result<T, StructError<R>> {
+    fn map_reason<R2, F>(self, f: F) -> Result<T, StructError<R2>>
+    where
+        R2: DomainReason,
+        F: FnOnce(R) -> R2,
+    {
+        self.map_err(|e| e.map_reason(f))
+    }
+}
+
 pub trait ToStructError<R>
 where
     R: DomainReason,
@@ -177,4 +241,83 @@ mod tests {
         assert!(uvs_result.is_err());
         assert_eq!(uvs_result.unwrap_err().error_code(), 100);
     }
+
+    #[test]
+    fn test_conversion_trail() {
+        let err: StructError<TestReason> = TestReason::TestError.to_err();
+        assert!(err.conversion_trail().is_empty());
+        assert!(!err.to_string().contains("Converted"));
+
+        let err: StructError<AnotherReason> = err.conv();
+        assert_eq!(err.conversion_trail(), ["TestReason: test error"]);
+        assert!(err.to_string().contains(
+            "-> Converted:\n     AnotherReason: another error\n     <- TestReason: test error"
+        ));
+
+        // 多层转换按自外向内渲染
+        let err: StructError<AnotherReason> = convert_error::<TestReason, AnotherReason>(
+            TestReason::Uvs(UvsReason::network_error()).to_err(),
+        );
+        let err: StructError<AnotherReason> = err.conv();
+        assert_eq!(err.conversion_trail().len(), 2);
+    }
+
+    #[test]
+    fn test_keep_origin_across_conversions() {
+        let low: Result<(), StructError<TestReason>> = Err(TestReason::TestError.to_err());
+        let err: StructError<AnotherReason> = low.err_conv_keep().unwrap_err();
+        assert_eq!(err.reason(), &AnotherReason::AnotherError);
+        let origin = err.origin::<TestReason>().unwrap();
+        assert_eq!(origin.reason(), &TestReason::TestError);
+        assert!(err.origin::<UvsReason>().is_none());
+
+        // 普通转换不保留；再次保留时仍能向内找到最初的原因
+        let plain: StructError<AnotherReason> = TestReason::TestError.to_err().conv();
+        assert!(plain.origin::<TestReason>().is_none());
+        let outer: StructError<AnotherReason> = err.conv_keep();
+        assert!(outer.origin::<AnotherReason>().is_some());
+        assert_eq!(
+            outer.origin::<TestReason>().unwrap().reason(),
+            &TestReason::TestError
+        );
+    }
+
+    #[test]
+    fn test_map_reason_keeps_body() {
+        use crate::ErrorWith;
+
+        let res: Result<(), StructError<TestReason>> = Err(TestReason::TestError
+            .to_err()
+            .with_detail("disk full")
+            .want("save"));
+        let err = res
+            .map_reason(|r| match r {
+                TestReason::TestError => AnotherReason::Uvs(UvsReason::resource_error()),
+                TestReason::Uvs(u) => AnotherReason::Uvs(u),
+            })
+            .unwrap_err();
+        assert_eq!(
+            err.reason(),
+            &AnotherReason::Uvs(UvsReason::resource_error())
+        );
+        assert_eq!(err.detail().as_deref(), Some("disk full"));
+        assert_eq!(err.target().as_deref(), Some("save"));
+    }
+
+    fn parse_generic<E>(res: Result<u8, E>) -> Result<u8, StructError<TestReason>>
+    where
+        StructError<TestReason>: From<E>,
+    {
+        Ok(res? + 1)
+    }
+
+    #[test]
+    fn test_infallible_composes() {
+        let ok: Result<u8, Infallible> = Ok(1);
+        assert_eq!(parse_generic(ok), Ok(2));
+        let conv: Result<u8, StructError<TestReason>> = Ok::<u8, Infallible>(3).err_conv();
+        assert_eq!(conv, Ok(3));
+        let from_str: Result<String, Infallible> = "x".parse();
+        assert_eq!(parse_generic(from_str.map(|s| s.len() as u8)), Ok(2));
+    }
 }
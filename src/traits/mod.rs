@@ -1,7 +1,12 @@
 mod contextual;
 mod conversion;
 mod owenance;
+mod tap;
 
-pub use contextual::ErrorWith;
-pub use conversion::{ConvStructError, ErrorConv, ToStructError};
-pub use owenance::{ErrorOwe, ErrorOweBase};
+pub use contextual::{ErrorWith, ErrorWithLazy};
+pub use conversion::{ConvStructError, ErrorConv, ErrorConvKeep, ErrorMapReason, ToStructError};
+pub use owenance::{
+    ErrorOwe, ErrorOweAuto, ErrorOweBase, ErrorOweFrom, ErrorOweIo, ErrorOweParse, ErrorOweSource,
+    ErrorOweWrap,
+};
+pub use tap::ErrorTap;
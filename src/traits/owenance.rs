@@ -1,13 +1,40 @@
-use crate::{core::DomainReason, StructError, UvsFrom};
+use crate::{
+    core::{wrap_error, DomainReason},
+    ErrorWith, OperationContext, ParsePosition, StructError, ToUvsReason, UvsFrom, UvsReason,
+};
 
 /// 非结构错误(StructError) 转化为结构错误。
 ///
-use std::fmt::Display;
+use std::{fmt::Display, io};
 pub trait ErrorOweBase<T, R>
 where
     R: DomainReason,
 {
     fn owe(self, reason: R) -> Result<T, StructError<R>>;
+
+    /// `owe(reason)` 并附加上下文，等同于 `.owe(reason).with(ctx)`
+    #[track_caller]
+    fn owe_with<C: Into<OperationContext>>(self, reason: R, ctx: C) -> Result<T, StructError<R>>
+    where
+        Self: Sized,
+    {
+        self.owe(reason).with(ctx)
+    }
+}
+
+/// 生成 `owe_*_with(ctx)`：转换的同时附加上下文，避免遗漏 `.with(&ctx)`
+macro_rules! owe_with_ctx {
+    ($($with:ident => $owe:ident),* $(,)?) => {
+        $(
+            #[track_caller]
+            fn $with<C: Into<OperationContext>>(self, ctx: C) -> Result<T, StructError<R>>
+            where
+                Self: Sized,
+            {
+                self.$owe().with(ctx)
+            }
+        )*
+    };
 }
 
 pub trait ErrorOwe<T, R>: ErrorOweBase<T, R>
@@ -24,6 +51,19 @@ where
     fn owe_net(self) -> Result<T, StructError<R>>;
     fn owe_timeout(self) -> Result<T, StructError<R>>;
     fn owe_sys(self) -> Result<T, StructError<R>>;
+
+    owe_with_ctx!(
+        owe_logic_with => owe_logic,
+        owe_biz_with => owe_biz,
+        owe_rule_with => owe_rule,
+        owe_validation_with => owe_validation,
+        owe_data_with => owe_data,
+        owe_conf_with => owe_conf,
+        owe_res_with => owe_res,
+        owe_net_with => owe_net,
+        owe_timeout_with => owe_timeout,
+        owe_sys_with => owe_sys,
+    );
 }
 
 impl<T, E, R> ErrorOweBase<T, R> for Result<T, E>
@@ -79,6 +119,139 @@ where
     }
 }
 
+/// I/O 错误按 `ErrorKind` 自动归类，而不是统一归为系统错误
+pub trait ErrorOweIo<T, R>
+where
+    R: DomainReason + UvsFrom,
+{
+    fn owe_io(self) -> Result<T, StructError<R>>;
+}
+
+impl<T, R> ErrorOweIo<T, R> for Result<T, io::Error>
+where
+    R: DomainReason + UvsFrom,
+{
+    fn owe_io(self) -> Result<T, StructError<R>> {
+        self.map_err(|e| {
+            StructError::from(R::from(UvsReason::from_io(&e))).with_detail(e.to_string())
+        })
+    }
+}
+
+/// thiserror 等库错误提升为结构错误：显示文本作为 `detail`，原错误作为错误源，
+/// `#[source]` / `#[from]` 标注的嵌套错误可经 `source_chain` 或 `Error::source` 逐层访问
+pub trait ErrorOweSource<T, R>
+where
+    R: DomainReason,
+{
+    fn owe_source(self, reason: R) -> Result<T, StructError<R>>;
+}
+
+impl<T, E, R> ErrorOweSource<T, R> for Result<T, E>
+where
+    E: std::error::Error + Send + Sync + 'static,
+    R: DomainReason,
+{
+    fn owe_source(self, reason: R) -> Result<T, StructError<R>> {
+        self.map_err(|e| lift_source(reason, e))
+    }
+}
+
+/// 由库错误推导领域原因（`R: From<&E>`），同时保留完整的错误链
+///
+/// ```rust,ignore
+/// impl From<&StoreError> for OrderReason {
+///     fn from(e: &StoreError) -> Self {
+///         match e {
+///             StoreError::Missing { .. } => OrderReason::NotFound,
+///             _ => OrderReason::Uvs(UvsReason::system_error()),
+///         }
+///     }
+/// }
+/// store.load(id).owe_from()?;
+/// ```
+pub trait ErrorOweFrom<T, R>
+where
+    R: DomainReason,
+{
+    fn owe_from(self) -> Result<T, StructError<R>>;
+}
+
+impl<T, E, R> ErrorOweFrom<T, R> for Result<T, E>
+where
+    E: std::error::Error + Send + Sync + 'static,
+    R: DomainReason + for<'a> From<&'a E>,
+{
+    fn owe_from(self) -> Result<T, StructError<R>> {
+        self.map_err(|e| lift_source(R::from(&e), e))
+    }
+}
+
+fn lift_source<R, E>(reason: R, err: E) -> StructError<R>
+where
+    R: DomainReason,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let detail = err.to_string();
+    StructError::from(reason)
+        .with_detail(detail)
+        .with_source(err)
+}
+
+/// 结构错误改用另一领域的原因：与 `owe` 不同，不会把整个错误渲染进 `detail`，
+/// 而是保留原错误的 detail、位置与上下文帧，原原因记入转换轨迹
+pub trait ErrorOweWrap<T, R1>
+where
+    R1: DomainReason,
+{
+    fn owe_wrap<R2: DomainReason>(self, reason: R2) -> Result<T, StructError<R2>>;
+}
+
+impl<T, R1> ErrorOweWrap<T, R1> for Result<T, StructError<R1>>
+where
+    R1: DomainReason,
+{
+    fn owe_wrap<R2: DomainReason>(self, reason: R2) -> Result<T, StructError<R2>> {
+        self.map_err(|e| wrap_error(e, |_| reason))
+    }
+}
+
+/// 按错误源类型自动归类（见 `ToUvsReason`）
+pub trait ErrorOweAuto<T, R>
+where
+    R: DomainReason + UvsFrom,
+{
+    fn owe_auto(self) -> Result<T, StructError<R>>;
+}
+
+impl<T, E, R> ErrorOweAuto<T, R> for Result<T, E>
+where
+    E: ToUvsReason + Display,
+    R: DomainReason + UvsFrom,
+{
+    fn owe_auto(self) -> Result<T, StructError<R>> {
+        self.map_err(|e| StructError::from(R::from(e.to_uvs_reason())).with_detail(e.to_string()))
+    }
+}
+
+/// 解析错误转换为数据错误，并把出错的行列写入 position
+pub trait ErrorOweParse<T, R>
+where
+    R: DomainReason + UvsFrom,
+{
+    fn owe_parse(self) -> Result<T, StructError<R>>;
+}
+
+impl<T, E, R> ErrorOweParse<T, R> for Result<T, E>
+where
+    E: ParsePosition,
+    R: DomainReason + UvsFrom,
+{
+    fn owe_parse(self) -> Result<T, StructError<R>> {
+        self.map_err(|e| crate::core::parse_error(&e))
+    }
+}
+
 fn map_err_with<T, E, R, F>(result: Result<T, E>, f: F) -> Result<T, StructError<R>>
 where
     E: Display,
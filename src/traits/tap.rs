@@ -0,0 +1,60 @@
+use std::fmt::Display;
+
+use crate::{core::DomainReason, ErrorCode, Severity, StructError};
+
+/// 在中间层观察错误而不消耗 `Result`，记录后仍可用 `?` 继续传播
+pub trait ErrorTap<T, R>: Sized
+where
+    R: DomainReason,
+{
+    /// 出错时以错误的引用调用 `f`，原样返回
+    fn tap_err<F: FnOnce(&StructError<R>)>(self, f: F) -> Self;
+
+    /// 出错时按 `level` 输出紧凑格式（见 `StructError::log_at`），原样返回
+    fn tap_err_log(self, level: Severity) -> Self
+    where
+        R: ErrorCode + Display,
+    {
+        self.tap_err(|e| e.log_at(level))
+    }
+}
+
+impl<T, R> ErrorTap<T, R> for Result<T, StructError<R>>
+where
+    R: DomainReason,
+{
+    fn tap_err<F: FnOnce(&StructError<R>)>(self, f: F) -> Self {
+        if let Err(e) = &self {
+            f(e);
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ErrorWith, UvsReason};
+
+    fn load() -> Result<u8, StructError<UvsReason>> {
+        Err(StructError::from(UvsReason::network_error()).want("load"))
+    }
+
+    fn handle() -> Result<u8, StructError<UvsReason>> {
+        let mut seen = None;
+        let res = load()
+            .tap_err(|e| seen = e.target())
+            .tap_err_log(Severity::Warn);
+        assert_eq!(seen.as_deref(), Some("load"));
+        Ok(res? + 1)
+    }
+
+    #[test]
+    fn test_tap_err_keeps_result() {
+        assert_eq!(handle().unwrap_err().reason(), &UvsReason::network_error());
+
+        let ok: Result<u8, StructError<UvsReason>> = Ok(1);
+        let res = ok.tap_err(|_| unreachable!()).tap_err_log(Severity::Error);
+        assert_eq!(res, Ok(1));
+    }
+}
@@ -0,0 +1,45 @@
+use orion_error::{
+    config::ErrorConfig, format_error_localized, JsonFormatter, Locale, StructError, UvsReason,
+};
+
+// 构建信息是全局设置，因此单独放在一个测试进程内
+#[test]
+fn test_app_info_stamped_on_errors() {
+    let before = StructError::from(UvsReason::network_error());
+    ErrorConfig::set_app_info("billing", "1.4.2", "3f2a1c9");
+    let err = StructError::from(UvsReason::network_error());
+
+    assert!(before.app_info().is_none());
+    let app = err.app_info().unwrap();
+    assert_eq!(app.to_string(), "billing 1.4.2 (3f2a1c9)");
+    assert!(err
+        .to_string()
+        .ends_with("\n  -> Build: billing 1.4.2 (3f2a1c9)"));
+
+    let json: serde_json::Value = serde_json::from_str(&err.format_with(&JsonFormatter)).unwrap();
+    assert_eq!(json["app"]["version"], "1.4.2");
+    assert_eq!(json["app"]["git_sha"], "3f2a1c9");
+
+    #[cfg(feature = "serde")]
+    {
+        let value = serde_json::to_value(&err).unwrap();
+        assert_eq!(value["app"]["name"], "billing");
+    }
+
+    // 各多行格式化器都以构建信息作页脚，稳定渲染则省略
+    assert!(err
+        .format_width(80)
+        .ends_with("\n  -> Build: billing 1.4.2 (3f2a1c9)"));
+    assert!(
+        format_error_localized(&err, Locale::Zh).contains("\n  -> 构建: billing 1.4.2 (3f2a1c9)")
+    );
+    #[cfg(feature = "color")]
+    assert!(err
+        .format_with(&orion_error::ColorFormatter)
+        .contains("Build:\x1b[0m billing 1.4.2 (3f2a1c9)"));
+    assert!(!err.to_stable_string().contains("Build"));
+
+    ErrorConfig::set_app_info("billing", "1.4.3", "");
+    let err = StructError::from(UvsReason::network_error());
+    assert_eq!(err.app_info().unwrap().git_sha, None);
+}
@@ -0,0 +1,37 @@
+use std::error::Error;
+
+use orion_error::{ErrorOwe, StructError, UvsReason};
+
+fn parse_port(text: &str) -> Result<u16, StructError<UvsReason>> {
+    text.parse::<u16>().owe_validation()
+}
+
+fn load(text: &str) -> Result<u16, Box<dyn Error + Send + Sync>> {
+    // `?` 经标准库的 `From<E: Error>` 装箱
+    let port = parse_port(text)?;
+    Ok(port)
+}
+
+#[test]
+fn test_question_mark_into_boxed_error() {
+    assert_eq!(load("8080").unwrap(), 8080);
+    let boxed = load("http").unwrap_err();
+    let err = boxed.downcast_ref::<StructError<UvsReason>>().unwrap();
+    assert_eq!(err.reason(), &UvsReason::validation_error());
+    assert!(boxed.to_string().starts_with("[100]"));
+}
+
+#[test]
+fn test_into_boxed_keeps_source_chain() {
+    let boxed = StructError::from(UvsReason::system_error())
+        .with_source(std::io::Error::other("disk gone"))
+        .into_boxed();
+    assert_eq!(boxed.source().unwrap().to_string(), "disk gone");
+}
+
+// `main() -> Result<(), StructError<R>>` 只需 `Debug`
+#[test]
+fn test_main_style_result() -> Result<(), StructError<UvsReason>> {
+    parse_port("443")?;
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use std::time::Duration;
+
+use orion_error::{
+    AsUvsReason, ErrorApplyStrategy, ErrorCode, FaultOf, HttpStatus, RetryPolicy, Severity,
+    SeverityOf, StrategyFor, StructError, UvsReason,
+};
+use thiserror::Error;
+
+/// 纯领域原因：不包装 `UvsReason`，通用原因统一折叠为 `Internal`
+#[derive(Debug, Clone, PartialEq, Error)]
+enum PayReason {
+    #[error("gateway unreachable")]
+    GatewayDown,
+    #[error("card declined")]
+    Declined,
+    #[error("internal error")]
+    Internal,
+}
+
+impl From<UvsReason> for PayReason {
+    fn from(_: UvsReason) -> Self {
+        Self::Internal
+    }
+}
+
+impl ErrorCode for PayReason {
+    fn error_code(&self) -> i32 {
+        match self {
+            Self::GatewayDown => 7001,
+            Self::Declined => 7002,
+            Self::Internal => 7500,
+        }
+    }
+}
+
+impl HttpStatus for PayReason {}
+impl SeverityOf for PayReason {}
+impl FaultOf for PayReason {}
+impl StrategyFor for PayReason {}
+
+impl AsUvsReason for PayReason {
+    fn as_uvs(&self) -> Option<&UvsReason> {
+        None
+    }
+}
+
+#[test]
+fn test_classify_drives_universal_mappings() {
+    let plain = StructError::from(PayReason::Declined);
+    assert_eq!(plain.as_uvs(), None);
+    assert_eq!(plain.http_status(), 500);
+    assert!(plain.strategy().is_throw());
+
+    let err = StructError::from(PayReason::GatewayDown).classify(UvsReason::network_error());
+    assert_eq!(err.uvs_class(), Some(&UvsReason::network_error()));
+    assert_eq!(err.as_uvs(), Some(&UvsReason::network_error()));
+    assert_eq!(err.error_code(), 7001);
+    assert_eq!(err.http_status(), 502);
+    assert_eq!(err.severity(), Severity::Warn);
+    assert!(err.is_server_fault());
+    assert!(err.strategy().is_retry());
+    assert!(err.is_category("network"));
+
+    let err = StructError::from(PayReason::Declined).classify(UvsReason::business_error());
+    assert_eq!(err.http_status(), 422);
+    assert!(err.is_client_fault());
+}
+
+#[test]
+fn test_classified_error_is_retried() {
+    let policy = RetryPolicy::new(3).with_backoff(Duration::from_millis(1));
+    let mut calls = 0;
+    let first: Result<u32, StructError<PayReason>> =
+        Err(StructError::from(PayReason::GatewayDown).classify(UvsReason::network_error()));
+    let res = first.apply_strategy(&policy, 0, || {
+        calls += 1;
+        Err(StructError::from(PayReason::GatewayDown).classify(UvsReason::network_error()))
+    });
+    assert_eq!(calls, 2);
+    assert_eq!(res.unwrap_err().reason(), &PayReason::GatewayDown);
+}
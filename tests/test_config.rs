@@ -0,0 +1,30 @@
+use orion_error::config::{error_config, DefaultFormat, ErrorConfig};
+use orion_error::{format_error, ErrorOwe, StructError, UvsReason};
+
+// 全局配置仅能安装一次，因此集中在单个测试进程内验证
+#[test]
+fn test_error_config_init_applies_globally() {
+    let cfg = ErrorConfig::new()
+        .with_backtrace(true)
+        .with_timestamp(true)
+        .with_error_id(true)
+        .with_max_detail_len(8)
+        .with_sensitive_key("api_key")
+        .with_format(DefaultFormat::Compact);
+    assert!(cfg.clone().init().is_ok());
+    assert!(ErrorConfig::new().init().is_err());
+    assert_eq!(error_config().format, DefaultFormat::Compact);
+
+    let res: Result<(), StructError<UvsReason>> = Err("api_key=secret and a long tail").owe_sys();
+    let err = res.unwrap_err();
+    assert!(err.timestamp().is_some());
+    assert!(err.backtrace().is_some());
+    assert!(err.id().is_some());
+    let detail = err.detail().clone().unwrap();
+    assert!(!detail.contains("secret"));
+    assert!(detail.contains("truncated"));
+
+    let out = format_error(&err);
+    assert!(!out.starts_with("[error code"));
+    assert!(out.contains("201"));
+}
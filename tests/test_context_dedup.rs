@@ -0,0 +1,19 @@
+use orion_error::{set_context_dedup, CallContext};
+
+// 去重开关是全局设置，单独放在一个测试进程内，避免与其他渲染上下文的用例相互干扰
+#[test]
+fn test_render_items_dedup() {
+    let mut ctx = CallContext::default();
+    ctx.items.push(("k".into(), "1".into()));
+    ctx.items.push(("j".into(), "2".into()));
+    ctx.items.push(("k".into(), "3".into()));
+    assert_eq!(
+        ctx.to_string(),
+        "\ncall context:\n\tk : 1\n\tj : 2\n\tk : 3\n"
+    );
+
+    set_context_dedup(true);
+    let rendered = ctx.to_string();
+    set_context_dedup(false);
+    assert_eq!(rendered, "\ncall context:\n\tk : 3\n\tj : 2\n");
+}
@@ -0,0 +1,26 @@
+use orion_error::{
+    clear_context_enrichers, register_context_enricher, ContextRecord, ErrorOwe, ErrorWith,
+    OperationContext, StructError, ToStructError, UvsReason,
+};
+
+// 注册的是全局增强器，因此单独放在一个测试进程内
+#[test]
+fn test_enrichers_apply_on_creation() {
+    register_context_enricher(|ctx: &mut OperationContext| ctx.record("tenant", "acme"));
+    register_context_enricher(|ctx: &mut OperationContext| ctx.record("build", "1.2.3"));
+
+    let err = UvsReason::network_error().to_err();
+    assert_eq!(err.contexts().len(), 1);
+    assert_eq!(err.contexts()[0].get("tenant"), Some("acme"));
+    assert_eq!(err.contexts()[0].get("build"), Some("1.2.3"));
+
+    let res: Result<(), StructError<UvsReason>> = Err("boom").owe_sys();
+    let err = res.with(OperationContext::want("sync")).unwrap_err();
+    assert_eq!(err.contexts().len(), 2);
+    assert_eq!(err.contexts()[0].get("tenant"), Some("acme"));
+
+    clear_context_enrichers();
+    register_context_enricher(|_: &mut OperationContext| {});
+    assert!(UvsReason::network_error().to_err().contexts().is_empty());
+    clear_context_enrichers();
+}
@@ -0,0 +1,71 @@
+#![cfg(feature = "macros")]
+
+use std::future::Future;
+
+use orion_error::{err_context, ErrorCode, ErrorOwe, StructError, UvsReason};
+
+#[err_context(want = "place_order", args(user_id, amount))]
+fn place_order(user_id: u64, amount: f64) -> Result<u64, StructError<UvsReason>> {
+    if amount <= 0.0 {
+        return Err(StructError::from(UvsReason::validation_error()));
+    }
+    let stock: u64 = "x".parse().owe_data()?;
+    Ok(user_id + stock)
+}
+
+#[err_context(args(path))]
+async fn load(path: String) -> Result<String, StructError<UvsReason>> {
+    Err(StructError::from(UvsReason::not_found_error()).with_detail(path))
+}
+
+struct Repo {
+    name: &'static str,
+}
+
+impl Repo {
+    #[err_context(want = "repo get")]
+    fn get(&self, id: u32) -> Result<&'static str, StructError<UvsReason>> {
+        if id == 0 {
+            Ok(self.name)
+        } else {
+            Err(StructError::from(UvsReason::not_found_error()))
+        }
+    }
+}
+
+#[test]
+fn test_err_context_records_args() {
+    let err = place_order(7, -1.0).unwrap_err();
+    let ctx = &err.contexts()[0];
+    assert_eq!(ctx.target().as_deref(), Some("place_order"));
+    assert_eq!(ctx.get("user_id"), Some("7"));
+    assert_eq!(ctx.get("amount"), Some("-1"));
+    assert_eq!(ctx.mod_path().as_str(), module_path!());
+
+    // `?` 提前返回的错误同样附加上下文
+    let err = place_order(7, 1.0).unwrap_err();
+    assert_eq!(err.error_code(), 200);
+    assert_eq!(err.target().as_deref(), Some("place_order"));
+}
+
+#[test]
+fn test_err_context_defaults_and_methods() {
+    let repo = Repo { name: "orders" };
+    assert_eq!(repo.get(0).unwrap(), "orders");
+    assert_eq!(
+        repo.get(1).unwrap_err().target().as_deref(),
+        Some("repo get")
+    );
+
+    // 无 async 运行时：手动轮询一次即完成的 future
+    let fut = load("/tmp/a".to_string());
+    let waker = std::task::Waker::noop();
+    let mut cx = std::task::Context::from_waker(waker);
+    let mut fut = std::pin::pin!(fut);
+    let std::task::Poll::Ready(res) = fut.as_mut().poll(&mut cx) else {
+        panic!("future should complete immediately");
+    };
+    let err = res.unwrap_err();
+    assert_eq!(err.target().as_deref(), Some("load"));
+    assert_eq!(err.contexts()[0].get("path"), Some("/tmp/a"));
+}
@@ -0,0 +1,42 @@
+use orion_error::{
+    set_error_limits, ContextRecord, ErrorLimits, ErrorWith, OperationContext, StructError,
+    UvsReason, TRUNCATED_KEY,
+};
+
+/// 测试结束（包括断言失败）时恢复默认限制
+struct RestoreLimits;
+
+impl Drop for RestoreLimits {
+    fn drop(&mut self) {
+        set_error_limits(ErrorLimits::default());
+    }
+}
+
+// 体积限制是全局设置，单独放在一个测试进程内
+#[test]
+fn test_error_limits_truncate_detail_and_context() {
+    let _restore = RestoreLimits;
+    set_error_limits(ErrorLimits {
+        max_detail_len: Some(10_000),
+        max_context_items: Some(64),
+        max_value_len: Some(4_096),
+        max_context_depth: None,
+    });
+    let mut ctx = OperationContext::want("bulk_load");
+    for i in 0..66 {
+        ctx.record(format!("row{i}"), "x");
+    }
+    ctx.record("body", "y".repeat(5_000));
+    let err = StructError::from(UvsReason::data_error())
+        .with_detail("z".repeat(12_000))
+        .with(ctx);
+
+    let detail = err.detail().as_ref().unwrap();
+    assert!(detail.ends_with("…(truncated, 2000 bytes)"));
+    let items = &err.context()[0].context().items;
+    assert_eq!(items.len(), 65);
+    assert_eq!(
+        items[64],
+        (TRUNCATED_KEY.into(), "(truncated, 3 items)".to_string())
+    );
+}
@@ -123,6 +123,26 @@ fn test_owe_sys() {
     assert!(error.detail().as_ref().unwrap().contains("system crash"));
 }
 
+#[test]
+fn test_owe_with_context_in_one_call() {
+    use orion_error::{ContextRecord, OperationContext};
+
+    let mut ctx = OperationContext::want("load config");
+    ctx.record("path", "/etc/app.toml");
+    let result: Result<(), &str> = Err("permission denied");
+    let error: StructError<UvsReason> = result.owe_sys_with(&ctx).unwrap_err();
+    assert_eq!(error.error_code(), 201);
+    assert_eq!(error.target().as_deref(), Some("load config"));
+    assert_eq!(error.contexts()[0].get("path"), Some("/etc/app.toml"));
+
+    let result: Result<(), &str> = Err("bad amount");
+    let error: StructError<UvsReason> = result
+        .owe_with(UvsReason::validation_error(), ("amount", "-1"))
+        .unwrap_err();
+    assert_eq!(error.error_code(), 100);
+    assert_eq!(error.contexts()[0].get("amount"), Some("-1"));
+}
+
 #[test]
 fn test_owe_logic() {
     // Test owe_logic for logic errors
@@ -283,3 +303,174 @@ fn test_error_code_implementation() {
         .unwrap()
         .contains("test error"));
 }
+
+#[test]
+fn test_owe_io_maps_error_kind() {
+    use orion_error::ErrorOweIo;
+    use std::io;
+
+    let result: Result<(), io::Error> =
+        Err(io::Error::new(io::ErrorKind::NotFound, "no such file"));
+    let converted: Result<(), StructError<UvsReason>> = result.owe_io();
+    let error = converted.unwrap_err();
+    assert_eq!(error.reason(), &UvsReason::NotFoundError);
+    assert!(error.detail().as_ref().unwrap().contains("no such file"));
+
+    let result: Result<(), io::Error> = Err(io::Error::other("disk on fire"));
+    let converted: Result<(), StructError<UvsReason>> = result.owe_io();
+    assert_eq!(converted.unwrap_err().error_code(), 201);
+}
+
+#[test]
+fn test_owe_auto_std_errors() {
+    use orion_error::ErrorOweAuto;
+
+    let converted: Result<i32, StructError<UvsReason>> = "abc".parse::<i32>().owe_auto();
+    assert_eq!(converted.unwrap_err().error_code(), 100);
+
+    let bytes: Vec<u8> = "é".bytes().take(1).collect();
+    let converted: Result<&str, StructError<UvsReason>> = std::str::from_utf8(&bytes).owe_auto();
+    assert_eq!(converted.unwrap_err().reason(), &UvsReason::DataError);
+}
+
+#[test]
+fn test_with_kv_and_with_all() {
+    use orion_error::ErrorWith;
+
+    let result: Result<(), &str> = Err("missing order");
+    let converted: Result<(), StructError<UvsReason>> = result
+        .owe_biz()
+        .want("load order")
+        .with_all([("user", 42), ("order", 7)])
+        .with_kv("region", "eu");
+
+    let error = converted.unwrap_err();
+    assert_eq!(error.contexts().len(), 1);
+    let ctx = &error.contexts()[0];
+    assert_eq!(ctx.target().as_deref(), Some("load order"));
+    assert_eq!(ctx.get("user"), Some("42"));
+    assert_eq!(ctx.get("order"), Some("7"));
+    assert_eq!(ctx.get("region"), Some("eu"));
+}
+
+#[test]
+fn test_ctx_with_is_lazy() {
+    use orion_error::{ErrorWithLazy, OperationContext};
+    use std::cell::Cell;
+
+    let calls = Cell::new(0);
+    let build = || {
+        calls.set(calls.get() + 1);
+        OperationContext::want("expensive")
+    };
+
+    let ok: Result<i32, StructError<UvsReason>> = Ok(1);
+    assert_eq!(ok.ctx_with(build).unwrap(), 1);
+    assert_eq!(calls.get(), 0);
+
+    let err: Result<i32, StructError<UvsReason>> = Err("boom").owe_sys();
+    let err = err.ctx_with(build).unwrap_err();
+    assert_eq!(calls.get(), 1);
+    assert_eq!(err.target().as_deref(), Some("expensive"));
+}
+
+#[test]
+fn test_assert_err_helpers() {
+    use orion_error::TestAssertErr;
+
+    let result: Result<(), &str> = Err("connection reset");
+    let converted: Result<(), StructError<UvsReason>> = result.owe_net();
+    let err = converted.clone().assert_err_code(202);
+    assert!(err.detail().as_ref().unwrap().contains("connection reset"));
+    converted.clone().assert_reason(&UvsReason::NetworkError);
+    converted.assert_category("network");
+}
+
+#[test]
+#[should_panic(expected = "expected: 100")]
+fn test_assert_err_code_mismatch_panics() {
+    use orion_error::TestAssertErr;
+
+    let result: Result<(), StructError<UvsReason>> = Err("x").owe_sys();
+    result.assert_err_code(100);
+}
+
+#[test]
+fn test_assert_err_on_failure_path() {
+    use orion_error::TestAssertFail;
+
+    let result: Result<u32, StructError<UvsReason>> = Err("disk offline").owe_sys();
+    let err = result.clone().assert_err();
+    assert_eq!(err.reason(), &UvsReason::SystemError);
+    result.assert_err_msg("disk offline");
+
+    let plain: Result<(), &str> = Err("boom");
+    assert_eq!(plain.assert_err_msg("boo"), "boom");
+}
+
+#[test]
+#[should_panic(expected = "expected Err, got Ok(42)")]
+fn test_assert_err_on_ok_shows_value() {
+    use orion_error::TestAssertFail;
+
+    let result: Result<u32, StructError<UvsReason>> = Ok(42);
+    result.assert_err();
+}
+
+#[test]
+#[should_panic(expected = "does not contain \"timeout\"")]
+fn test_assert_err_msg_mismatch_panics() {
+    use orion_error::TestAssertFail;
+
+    let result: Result<(), StructError<UvsReason>> = Err("connection reset").owe_net();
+    result.assert_err_msg("timeout");
+}
+
+#[test]
+fn test_owe_wrap_keeps_inner_structure() {
+    use orion_error::{ErrorOweWrap, ErrorWith};
+
+    #[derive(Debug, PartialEq, thiserror::Error, derive_more::From)]
+    enum OrderReason {
+        #[error("order unavailable")]
+        Unavailable,
+        #[error("{0}")]
+        Uvs(UvsReason),
+    }
+
+    let inner: Result<(), StructError<UvsReason>> =
+        Err(StructError::from(UvsReason::network_error())
+            .with_detail("peer reset")
+            .with_position("src/net.rs:3:1")
+            .want("fetch stock")
+            .with(("sku", "S-1")));
+
+    let err = inner.owe_wrap(OrderReason::Unavailable).unwrap_err();
+    assert_eq!(err.reason(), &OrderReason::Unavailable);
+    assert_eq!(err.detail().as_deref(), Some("peer reset"));
+    assert_eq!(err.imp().position().as_deref(), Some("src/net.rs:3:1"));
+    assert_eq!(err.target().as_deref(), Some("fetch stock"));
+    assert_eq!(err.contexts().len(), 2);
+    assert_eq!(err.conversion_trail(), ["UvsReason: network error"]);
+}
+
+mod prelude_only {
+    use orion_error::prelude::*;
+
+    fn load(path: &str) -> Result<String, UvsError> {
+        std::fs::read_to_string(path)
+            .owe_sys()
+            .want("load config")
+            .with(("path", path))
+    }
+
+    #[test]
+    fn test_prelude_covers_common_usage() {
+        let err = load("/nonexistent/orion.toml").unwrap_err();
+        assert_eq!(err.error_code(), UvsReason::system_error().error_code());
+        assert_eq!(err.target().as_deref(), Some("load config"));
+
+        let ok: Result<i32, UvsError> = Ok(1);
+        assert_eq!(ok.assert(), 1);
+    }
+}
@@ -0,0 +1,103 @@
+#![cfg(all(feature = "log-kv", not(feature = "tracing")))]
+
+use std::sync::Mutex;
+
+use log::kv::{Error, Key, Value, VisitSource};
+use orion_error::{
+    ContextRecord, ErrorIgnoreLogged, ErrorWith, OperationContext, Severity, StructError, UvsReason,
+};
+
+type Captured = (String, Vec<(String, String)>);
+
+static RECORDS: Mutex<Vec<Captured>> = Mutex::new(Vec::new());
+static LEVELS: Mutex<Vec<log::Level>> = Mutex::new(Vec::new());
+
+struct Capture;
+
+struct Pairs(Vec<(String, String)>);
+
+impl<'kvs> VisitSource<'kvs> for Pairs {
+    fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), Error> {
+        self.0.push((key.to_string(), value.to_string()));
+        Ok(())
+    }
+}
+
+impl log::Log for Capture {
+    fn enabled(&self, _: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        let mut pairs = Pairs(Vec::new());
+        let _ = record.key_values().visit(&mut pairs);
+        LEVELS.lock().unwrap().push(record.level());
+        RECORDS
+            .lock()
+            .unwrap()
+            .push((record.args().to_string(), pairs.0));
+    }
+
+    fn flush(&self) {}
+}
+
+fn take() -> Vec<Captured> {
+    LEVELS.lock().unwrap().clear();
+    std::mem::take(&mut *RECORDS.lock().unwrap())
+}
+
+fn has(pairs: &[(String, String)], k: &str, v: &str) -> bool {
+    pairs.iter().any(|(pk, pv)| pk == k && pv == v)
+}
+
+// logger 全局只能安装一次，因此集中在单个测试内验证
+#[test]
+fn test_structured_key_values() {
+    log::set_logger(&Capture).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    {
+        let mut ctx = OperationContext::want("place_order").with_auto_log();
+        ctx.record("order_id", "A-1");
+        ctx.info("checking stock");
+        ctx.mark_fail_with(&StructError::from(UvsReason::network_error()));
+    }
+    let records = take();
+    assert_eq!(records.len(), 2);
+    let (msg, kvs) = &records[0];
+    assert_eq!(msg, "checking stock");
+    assert!(has(kvs, "target", "place_order"));
+    assert!(has(kvs, "order_id", "A-1"));
+
+    let (msg, kvs) = &records[1];
+    assert_eq!(msg, "fail! place_order");
+    assert!(has(kvs, "code", "202"));
+    assert!(has(kvs, "category", "network"));
+    assert!(has(kvs, "order_id", "A-1"));
+
+    let mut ctx = OperationContext::want("load");
+    ctx.record("path", "/tmp/a");
+    let res: Result<(), StructError<UvsReason>> =
+        Err(StructError::from(UvsReason::not_found_error()).with_detail("missing"));
+    let _ = res.with(ctx).ignore_err_logged(Severity::Warn);
+    let records = take();
+    let (msg, kvs) = &records[0];
+    assert_eq!(msg, "ignored! not found error");
+    assert!(has(kvs, "code", "102"));
+    assert!(has(kvs, "detail", "missing"));
+    assert!(has(kvs, "path", "/tmp/a"));
+
+    // log() 按严重级别选择日志级别，消息为紧凑格式
+    let err = StructError::from(UvsReason::not_found_error())
+        .with_detail("order A-1")
+        .with_kv("order_id", "A-1");
+    err.log();
+    StructError::from(UvsReason::system_error()).log();
+    let levels = LEVELS.lock().unwrap().clone();
+    let records = take();
+    assert_eq!(levels, [log::Level::Info, log::Level::Error]);
+    let (msg, kvs) = &records[0];
+    assert_eq!(msg, &err.to_compact_string());
+    assert!(has(kvs, "code", "102"));
+    assert!(has(kvs, "order_id", "A-1"));
+}
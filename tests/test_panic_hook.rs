@@ -0,0 +1,47 @@
+use orion_error::{
+    catch_panic, install_panic_hook, StructError, TestAssertErr, UvsError, UvsReason,
+};
+
+// panic hook 是进程级设置，单独放在一个测试进程内
+#[test]
+fn test_catch_panic_records_location() {
+    install_panic_hook();
+    let res: Result<(), StructError<UvsReason>> = catch_panic(|| panic!("boom {}", 42));
+    let err = res.unwrap_err();
+    assert_eq!(err.reason(), &UvsReason::LogicError);
+    assert_eq!(err.detail().as_deref(), Some("boom 42"));
+    let position = err.imp().position().clone().unwrap();
+    assert!(
+        position.starts_with("tests/test_panic_hook.rs:"),
+        "{position}"
+    );
+
+    let ok: Result<i32, StructError<UvsReason>> = catch_panic(|| 7);
+    assert_eq!(ok.unwrap(), 7);
+}
+
+#[test]
+fn test_assert_err_failures_point_at_caller() {
+    install_panic_hook();
+    type Check = fn(Result<(), UvsError>);
+    let checks: [Check; 4] = [
+        |res| drop(res.assert_err_code(404)),
+        |res| drop(res.assert_reason(&UvsReason::network_error())),
+        |res| drop(res.assert_category("network")),
+        |_| drop(Ok::<(), UvsError>(()).assert_err_code(201)),
+    ];
+    for check in checks {
+        let err: UvsError =
+            catch_panic(move || check(Err(UvsReason::system_error().into()))).unwrap_err();
+        assert!(err
+            .detail()
+            .as_deref()
+            .unwrap()
+            .contains("[TEST ASSERTION FAILED]"));
+        let position = err.imp().position().clone().unwrap();
+        assert!(
+            position.starts_with("tests/test_panic_hook.rs:"),
+            "{position}"
+        );
+    }
+}
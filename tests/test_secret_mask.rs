@@ -0,0 +1,47 @@
+#![cfg(all(feature = "log", feature = "serde", not(feature = "tracing")))]
+
+use std::sync::Mutex;
+
+use orion_error::{ErrorWith, OperationContext, StructError, UvsReason};
+
+static MESSAGES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+struct Capture;
+
+impl log::Log for Capture {
+    fn enabled(&self, _: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        MESSAGES.lock().unwrap().push(record.args().to_string());
+    }
+
+    fn flush(&self) {}
+}
+
+// logger 全局只能安装一次，因此放在单独的测试二进制中
+#[test]
+fn test_with_secret_is_masked_everywhere() {
+    log::set_logger(&Capture).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    let err = StructError::from(UvsReason::permission_error())
+        .with_secret("password", "hunter2")
+        .with_kv("user", "alice");
+    let display = err.to_string();
+    assert!(display.contains("password"));
+    assert!(display.contains("alice"));
+    assert!(!display.contains("hunter2"));
+    let json = serde_json::to_string(&err).unwrap();
+    assert!(json.contains("password"));
+    assert!(!json.contains("hunter2"));
+
+    {
+        let mut ctx = OperationContext::want("login").with_auto_log();
+        ctx.with_secret("password", "hunter2");
+    }
+    let messages = MESSAGES.lock().unwrap();
+    assert!(messages.iter().any(|m| m.contains("password")));
+    assert!(messages.iter().all(|m| !m.contains("hunter2")));
+}
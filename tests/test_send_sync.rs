@@ -0,0 +1,33 @@
+use orion_error::{
+    config::AppInfo, ContextHandle, DomainReason, ErrorBundle, ErrorSource, ErrorWith,
+    OperationContext, StructError, UvsReason,
+};
+
+fn assert_send_sync<T: Send + Sync + 'static>() {}
+
+// 编译期断言：reason 满足 Send + Sync + 'static 时，错误及其组成部分同样满足
+#[allow(dead_code)]
+fn generic_reason<R: DomainReason + Send + Sync + 'static>() {
+    assert_send_sync::<StructError<R>>();
+    assert_send_sync::<ErrorBundle<R>>();
+    assert_send_sync::<Result<(), StructError<R>>>();
+}
+
+#[test]
+fn test_error_types_are_send_sync() {
+    generic_reason::<UvsReason>();
+    assert_send_sync::<OperationContext>();
+    assert_send_sync::<ContextHandle>();
+    assert_send_sync::<ErrorSource>();
+    assert_send_sync::<AppInfo>();
+}
+
+#[test]
+fn test_error_crosses_thread_boundary() {
+    let err = StructError::from(UvsReason::network_error())
+        .with_detail("peer reset")
+        .with_source(std::io::Error::other("reset"));
+    let handle = std::thread::spawn(move || err.want("fetch"));
+    let err = handle.join().unwrap();
+    assert_eq!(err.detail().as_deref(), Some("peer reset"));
+}
@@ -0,0 +1,109 @@
+//! 序列化格式兼容性：`tests/compat/` 中的载荷是已持久化数据的样例，修改格式时不得破坏它们
+#![cfg(all(feature = "serde", feature = "json"))]
+
+use std::collections::BTreeSet;
+
+use orion_error::{
+    ConfErrReason, ContextRecord, ErrorWith, OperationContext, StructError, UvsReason,
+    ERROR_SCHEMA_VERSION,
+};
+use serde_json::{json, Value};
+
+fn corpus(name: &str) -> Value {
+    let path = format!("{}/tests/compat/{name}", env!("CARGO_MANIFEST_DIR"));
+    let text = std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{path}: {e}"));
+    serde_json::from_str(&text).unwrap()
+}
+
+fn load(name: &str) -> StructError<UvsReason> {
+    StructError::deserialize_any_version(corpus(name)).unwrap()
+}
+
+#[test]
+fn test_v1_payloads_upgrade() {
+    let err = load("v1_minimal.json");
+    assert_eq!(err.reason(), &UvsReason::system_error());
+    assert_eq!(err.detail().as_deref(), Some("disk offline"));
+    assert!(err.contexts().is_empty());
+
+    let err = load("v1_with_context.json");
+    assert_eq!(
+        err.reason(),
+        &UvsReason::ConfigError(ConfErrReason::missing_key("app.toml", "db.url"))
+    );
+    assert_eq!(err.imp().position().as_deref(), Some("src/config.rs:42:9"));
+    let frame = &err.contexts()[0];
+    assert_eq!(frame.target().as_deref(), Some("load config"));
+    assert_eq!(frame.get("file"), Some("app.toml"));
+
+    let upgraded = serde_json::to_value(&err).unwrap();
+    assert_eq!(upgraded["schema_version"], ERROR_SCHEMA_VERSION);
+}
+
+#[test]
+fn test_v2_payload_round_trips_unchanged() {
+    let original = corpus("v2_full.json");
+    let err = load("v2_full.json");
+    assert_eq!(err.id().as_deref(), Some("01HX"));
+    assert_eq!(err.correlation_id().as_deref(), Some("req-7"));
+    assert_eq!(err.uvs_class(), Some(&UvsReason::not_found_error()));
+    assert_eq!(err.position_trail(), ["src/service.rs:88:13"]);
+    assert_eq!(err.conversion_trail(), ["RepoReason: row missing"]);
+    assert_eq!(
+        err.error_source().map(|s| s.to_string()).as_deref(),
+        Some("connection reset")
+    );
+
+    assert_eq!(serde_json::to_value(&err).unwrap(), original);
+}
+
+#[test]
+fn test_unknown_fields_ignored_and_newer_versions_rejected() {
+    let mut payload = corpus("v2_full.json");
+    payload["added_by_a_later_minor"] = json!({"any": "thing"});
+    assert!(StructError::<UvsReason>::deserialize_any_version(payload).is_ok());
+
+    let err = StructError::<UvsReason>::deserialize_any_version(corpus("v3_future.json"))
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("unsupported error schema_version 3"), "{err}");
+}
+
+#[test]
+fn test_field_names_are_stable() {
+    let mut ctx = OperationContext::want("load order");
+    ctx.record("order_id", "A-1");
+    let err = StructError::from(UvsReason::not_found_error())
+        .with_detail("no row")
+        .with(ctx)
+        .with_position("src/repo.rs:10:5")
+        .with_correlation_id("req-7");
+    let value = serde_json::to_value(&err).unwrap();
+
+    assert_eq!(
+        keys(&value),
+        BTreeSet::from([
+            "reason",
+            "schema_version",
+            "detail",
+            "position",
+            "context",
+            "correlation_id"
+        ])
+    );
+    let frame = &value["context"][0];
+    assert_eq!(
+        keys(frame),
+        BTreeSet::from(["context", "result", "exit_log", "mod_path", "target", "location"])
+    );
+    assert_eq!(frame["context"]["items"], json!([["order_id", "A-1"]]));
+}
+
+fn keys(value: &Value) -> BTreeSet<&str> {
+    value
+        .as_object()
+        .unwrap()
+        .keys()
+        .map(String::as_str)
+        .collect()
+}
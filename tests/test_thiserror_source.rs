@@ -0,0 +1,129 @@
+use std::io;
+
+use derive_more::From;
+use orion_error::{ErrorConv, ErrorOweFrom, ErrorOweSource, StructError, ToStructError, UvsReason};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+enum DiskError {
+    #[error("disk read failed")]
+    Read(#[from] io::Error),
+    #[error("disk full")]
+    Full,
+}
+
+#[derive(Debug, Error)]
+enum StoreError {
+    #[error("load key `{key}`")]
+    Load {
+        key: String,
+        #[source]
+        source: DiskError,
+    },
+    #[error(transparent)]
+    Disk(DiskError),
+    #[error("corrupt record {0}")]
+    Corrupt(u32),
+}
+
+#[derive(Debug, Error)]
+enum RepoError {
+    #[error("repository unavailable")]
+    Store(#[from] StoreError),
+}
+
+#[derive(Debug, Clone, PartialEq, Error, From)]
+enum OrderReason {
+    #[error("order not found")]
+    NotFound,
+    #[error("{0}")]
+    Uvs(UvsReason),
+}
+
+impl From<&RepoError> for OrderReason {
+    fn from(e: &RepoError) -> Self {
+        match e {
+            RepoError::Store(StoreError::Corrupt(_)) => Self::Uvs(UvsReason::data_error()),
+            RepoError::Store(_) => Self::Uvs(UvsReason::system_error()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Error, From)]
+enum ApiReason {
+    #[error("{0}")]
+    Order(OrderReason),
+    #[error("{0}")]
+    Uvs(UvsReason),
+}
+
+fn load(key: &str) -> Result<(), RepoError> {
+    let io = io::Error::new(io::ErrorKind::PermissionDenied, "permission denied");
+    Err(StoreError::Load {
+        key: key.to_string(),
+        source: DiskError::from(io),
+    }
+    .into())
+}
+
+fn chain<R: orion_error::DomainReason>(err: &StructError<R>) -> Vec<String> {
+    err.source_chain().map(|e| e.to_string()).collect()
+}
+
+#[test]
+fn test_owe_source_keeps_nested_chain() {
+    let err = load("A-1").owe_source(OrderReason::NotFound).unwrap_err();
+    assert_eq!(err.detail().as_deref(), Some("repository unavailable"));
+    assert_eq!(
+        chain(&err),
+        [
+            "repository unavailable",
+            "load key `A-1`",
+            "disk read failed",
+            "permission denied"
+        ]
+    );
+    assert_eq!(
+        err.error_source().map(|e| e.to_string()).as_deref(),
+        Some("repository unavailable")
+    );
+}
+
+#[test]
+fn test_owe_from_derives_reason() {
+    let err: StructError<OrderReason> = load("A-1").owe_from().unwrap_err();
+    assert_eq!(err.reason(), &OrderReason::Uvs(UvsReason::system_error()));
+    assert_eq!(chain(&err).len(), 4);
+
+    let corrupt: Result<(), RepoError> = Err(StoreError::Corrupt(7).into());
+    let err: StructError<OrderReason> = corrupt.owe_from().unwrap_err();
+    assert_eq!(err.reason(), &OrderReason::Uvs(UvsReason::data_error()));
+    assert_eq!(chain(&err), ["repository unavailable", "corrupt record 7"]);
+}
+
+#[test]
+fn test_transparent_and_leaf_sources() {
+    // transparent 变体直接转发内层的显示与错误源
+    let res: Result<(), StoreError> = Err(StoreError::Disk(DiskError::Full));
+    let err = res.owe_source(OrderReason::NotFound).unwrap_err();
+    assert_eq!(chain(&err), ["disk full"]);
+
+    let res: Result<(), DiskError> = Err(DiskError::Full);
+    let err = res.owe_source(UvsReason::system_error()).unwrap_err();
+    assert_eq!(chain(&err), ["disk full"]);
+
+    let err = OrderReason::NotFound.to_err();
+    assert_eq!(chain(&err).len(), 0);
+}
+
+#[test]
+fn test_chain_survives_reason_conversion() {
+    let res: Result<(), StructError<ApiReason>> =
+        load("B-2").owe_source(OrderReason::NotFound).err_conv();
+    let err = res.unwrap_err();
+    assert_eq!(err.reason(), &ApiReason::Order(OrderReason::NotFound));
+    assert_eq!(
+        chain(&err).last().map(String::as_str),
+        Some("permission denied")
+    );
+}
@@ -0,0 +1,129 @@
+use orion_error::{ErrorWith, OperationContext, StructError, UvsReason};
+
+fn load_config() -> Result<(), StructError<UvsReason>> {
+    Err(StructError::from(UvsReason::not_found_error())).want("load config")
+}
+
+#[test]
+fn test_want_records_caller_location() {
+    let err = load_config().unwrap_err();
+    let location = err.contexts()[0].location().unwrap();
+    assert!(location.starts_with("tests/test_track_caller.rs:4:"));
+    // 未设置位置时以首次 want 的调用点补上
+    assert_eq!(err.imp().position().as_deref(), Some(location));
+}
+
+#[test]
+fn test_explicit_position_is_kept() {
+    let err = StructError::from(UvsReason::network_error())
+        .with_position("src/net.rs:1:1")
+        .want("fetch")
+        .with(OperationContext::want("retry"));
+    assert_eq!(err.imp().position().as_deref(), Some("src/net.rs:1:1"));
+    assert!(err.contexts().iter().all(|c| c
+        .location()
+        .is_some_and(|l| l.contains("test_track_caller.rs"))));
+    assert!(err.to_stable_string().contains("at: <location>"));
+    assert!(!err.to_stable_string().contains("test_track_caller.rs"));
+}
+
+#[test]
+fn test_ctx_with_records_caller_location() {
+    use orion_error::ErrorWithLazy;
+
+    let res: Result<(), StructError<UvsReason>> = Err(UvsReason::system_error().into());
+    let err = res.ctx_with(|| OperationContext::want("lazy")).unwrap_err();
+    let position = err.imp().position().clone().unwrap();
+    assert!(
+        position.starts_with("tests/test_track_caller.rs:"),
+        "{position}"
+    );
+    assert_eq!(err.contexts()[0].location(), Some(position.as_str()));
+}
+
+#[test]
+fn test_owe_with_records_caller_location() {
+    use orion_error::{ErrorOwe, ErrorOweBase};
+
+    let res: Result<(), &str> = Err("disk full");
+    let err: StructError<UvsReason> = res
+        .owe_with(UvsReason::system_error(), OperationContext::want("save"))
+        .unwrap_err();
+    let position = err.imp().position().clone().unwrap();
+    assert!(
+        position.starts_with("tests/test_track_caller.rs:"),
+        "{position}"
+    );
+
+    let res: Result<(), &str> = Err("disk full");
+    let err: StructError<UvsReason> = res
+        .owe_sys_with(OperationContext::want("save"))
+        .unwrap_err();
+    let position = err.imp().position().clone().unwrap();
+    assert!(
+        position.starts_with("tests/test_track_caller.rs:"),
+        "{position}"
+    );
+    assert_eq!(err.contexts()[0].location(), Some(position.as_str()));
+}
+
+#[test]
+fn test_collect_keeps_error_position() {
+    use orion_error::{ErrorCollect, ErrorOwe};
+
+    fn parse(s: &str) -> Result<i32, StructError<UvsReason>> {
+        s.parse::<i32>().owe_data()
+    }
+
+    let (_, errors) = ["1", "x"].into_iter().map(parse).collect_all();
+    let err = errors.iter().next().unwrap();
+    assert_eq!(err.contexts()[0].get("index"), Some("1"));
+    assert_eq!(err.imp().position(), &None);
+
+    let err = ["x"]
+        .into_iter()
+        .map(|s| parse(s).want("parse"))
+        .collect_fail_fast()
+        .unwrap_err();
+    let position = err.imp().position().clone().unwrap();
+    assert!(
+        position.starts_with("tests/test_track_caller.rs:"),
+        "{position}"
+    );
+}
+
+#[test]
+fn test_push_want_and_with_kv_record_caller_location() {
+    let err = StructError::from(UvsReason::system_error()).push_want("boot");
+    let position = err.imp().position().clone().unwrap();
+    assert!(
+        position.starts_with("tests/test_track_caller.rs:"),
+        "{position}"
+    );
+
+    let res: Result<(), StructError<UvsReason>> = Err(UvsReason::system_error().into());
+    let err = res.want("boot").with_kv("port", 80).unwrap_err();
+    assert_eq!(err.contexts()[0].get("port"), Some("80"));
+    assert!(err
+        .imp()
+        .position()
+        .as_deref()
+        .unwrap()
+        .starts_with("tests/test_track_caller.rs:"));
+}
+
+#[test]
+fn test_with_and_want_extend_position_trail() {
+    let start = line!();
+    let err = StructError::from(UvsReason::data_error())
+        .with(("file", "a.toml"))
+        .want("parse config")
+        .with(("key", "port"));
+    let lines: Vec<u32> = err
+        .positions()
+        .iter()
+        .map(|p| p.split(':').nth(1).unwrap().parse().unwrap())
+        .collect();
+    assert_eq!(lines, [start + 2, start + 3, start + 4]);
+    assert_eq!(err.position_trail().len(), 2);
+}
@@ -0,0 +1,36 @@
+use orion_error::{set_want_mode, ErrorWith, StructError, UvsReason, WantMode};
+
+fn load_order() -> Result<(), StructError<UvsReason>> {
+    Err(StructError::from(UvsReason::not_found_error())).want("read order")
+}
+
+fn place_order() -> Result<(), StructError<UvsReason>> {
+    load_order().with_kv("order_id", "A-1").want("place_order")
+}
+
+// 切换的是全局模式，因此单独放在一个测试进程内
+#[test]
+fn test_want_mode_append_keeps_every_layer() {
+    set_want_mode(WantMode::Append);
+    let err = place_order().unwrap_err();
+    let targets: Vec<_> = err.contexts().iter().map(|c| c.target().clone()).collect();
+    assert_eq!(
+        targets,
+        [
+            Some("read order".to_string()),
+            Some("place_order".to_string())
+        ]
+    );
+    assert_eq!(err.contexts()[0].get("order_id"), Some("A-1"));
+
+    // 最近一帧尚无目标时直接补上，不额外追加
+    let err = StructError::from(UvsReason::network_error())
+        .with_kv("url", "http://x")
+        .want("fetch");
+    assert_eq!(err.contexts().len(), 1);
+
+    set_want_mode(WantMode::Replace);
+    let err = place_order().unwrap_err();
+    assert_eq!(err.contexts().len(), 1);
+    assert_eq!(err.target().as_deref(), Some("place_order"));
+}